@@ -44,7 +44,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // 4. Error Handling Example
     println!("\n4. Testing error handling (Misaligned slices)...");
-    let _short_out = vec![0.0; 10];
+    let _short_out = [0.0; 10];
     let _status = ArchX::compute().add(&a, &b, &mut out[..10]); 
     // Wait, the above is actually aligned if I use a slice, let's try mismatched
     let err_status = ArchX::compute().add(&a, &b, &mut out[..10]);
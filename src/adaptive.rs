@@ -1,10 +1,11 @@
 use crate::hardware::SystemInfo;
 use crate::optimizer::scheduler::{WorkloadHints, PowerMode};
+use std::sync::atomic::{AtomicU64, Ordering};
 
 pub use crate::decision::Policy;
 
 /// The strategy chosen by the adaptive engine in Sovereign v3.0.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
 pub enum Strategy {
     ScalarFallback,
     SingleThreadSimd,
@@ -13,15 +14,50 @@ pub enum Strategy {
     Hybrid,
 }
 
+impl Strategy {
+    pub const NAMES: [&'static str; 5] = ["ScalarFallback", "SingleThreadSimd", "ParallelSimd", "GpuOffload", "Hybrid"];
+
+    /// The variant name without the thread count.
+    pub fn name(&self) -> &'static str {
+        Self::NAMES[self.index()]
+    }
+
+    fn index(&self) -> usize {
+        match self {
+            Strategy::ScalarFallback => 0,
+            Strategy::SingleThreadSimd => 1,
+            Strategy::ParallelSimd(_) => 2,
+            Strategy::GpuOffload => 3,
+            Strategy::Hybrid => 4,
+        }
+    }
+}
+
+static CHOSEN: [AtomicU64; 5] = [const { AtomicU64::new(0) }; 5];
+
 /// A smart engine that decides the best compute path at runtime.
 pub struct AdaptiveEngine;
 
 impl AdaptiveEngine {
     /// Decides the best strategy based on data size and system state.
+    ///
+    /// Every decision is counted, see [`AdaptiveEngine::strategy_counts`].
     pub fn choose_strategy(len: usize, hints: &WorkloadHints, info: &SystemInfo) -> Strategy {
+        let strategy = Self::evaluate_strategy(len, hints, info);
+        CHOSEN[strategy.index()].fetch_add(1, Ordering::Relaxed);
+        strategy
+    }
+
+    /// How often each strategy was chosen, by [`Strategy::name`].
+    pub fn strategy_counts() -> Vec<(&'static str, u64)> {
+        Strategy::NAMES.iter().zip(&CHOSEN).map(|(&name, count)| (name, count.load(Ordering::Relaxed))).collect()
+    }
+
+    /// The strategy `choose_strategy` would pick, without counting it.
+    pub fn evaluate_strategy(len: usize, hints: &WorkloadHints, info: &SystemInfo) -> Strategy {
         // Sovereign Production Guard: Memory Check
         if !info.can_handle_dataset(len) {
-            println!("[ArchX Sovereign v3.0 WARNING] Dataset size {} exceeds safe memory limits. Forcing SingleThreadSimd.", len);
+            eprintln!("[ArchX Sovereign v3.0 WARNING] Dataset size {} exceeds safe memory limits. Forcing SingleThreadSimd.", len);
             return Strategy::SingleThreadSimd;
         }
 
@@ -44,13 +80,17 @@ impl AdaptiveEngine {
         }
 
         // 3. Power & Scaling Heuristics
-        let base_threads = match hints.power_mode {
-            PowerMode::PowerSaving => info.cpu.cores,
-            _ => info.cpu.logical_processors,
+        // Container CPU quotas cap every thread count below; low battery turns
+        // `Balanced` into `PowerSaving`.
+        let power_mode = hints.power_mode.resolve(&info.context);
+        let usable_threads = info.cpu.effective_threads.clamp(1, info.cpu.logical_processors.max(1));
+        let base_threads = match power_mode {
+            PowerMode::PowerSaving => info.cpu.cores.min(usable_threads),
+            _ => usable_threads,
         };
 
         let mut target_threads = if let Some(cap) = hints.max_cpu_usage {
-            (info.cpu.logical_processors as f32 * cap).max(1.0) as usize
+            (usable_threads as f32 * cap).max(1.0) as usize
         } else {
             base_threads
         };
@@ -60,7 +100,7 @@ impl AdaptiveEngine {
             target_threads = target_threads.min(manual);
         }
 
-        match hints.power_mode {
+        match power_mode {
             PowerMode::PowerSaving => {
                 if len < 1_000_000 {
                     Strategy::SingleThreadSimd
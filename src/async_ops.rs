@@ -1,6 +1,7 @@
 use std::future::Future;
 use std::pin::Pin;
 use crate::system::{add_advanced, WorkloadHints};
+use crate::runtime::BlockingPool;
 
 /// Asynchronous implementation of the vectorized addition operation.
 /// 
@@ -10,6 +11,8 @@ use crate::system::{add_advanced, WorkloadHints};
 ///
 /// # Returns
 /// A `Future` that resolves to a `Vec<f32>` containing the element-wise sum of `a` and `b`.
+/// Failures resolve to an empty `Vec`; use `SovereignBuilder::add_async` to receive an `ArchXResult`.
+#[deprecated(note = "failures resolve to an empty Vec; use `archx().add_async(a, b)` (SovereignBuilder::add_async), which resolves to an ArchXResult")]
 pub fn add_async(a: Vec<f32>, b: Vec<f32>, hints: WorkloadHints) -> Pin<Box<dyn Future<Output = Vec<f32>> + Send>> {
     let _scope = crate::profiler::ProfileScope::new("Async Operation", "CPU", "Async");
     Box::pin(async move {
@@ -26,17 +29,15 @@ pub fn add_async(a: Vec<f32>, b: Vec<f32>, hints: WorkloadHints) -> Pin<Box<dyn
             }
         }
 
-        // 2. Fallback to CPU execution (wrapped in a blocking task)
-        // Note: For a real library, we'd use tokio::spawn_blocking or similar.
-        // Here we'll simulate it using a standard thread for demo purposes 
-        // to keep dependencies minimal.
-        let (tx, rx) = std::sync::mpsc::channel();
-        std::thread::spawn(move || {
-            let mut out = vec![0.0; a.len()];
-            add_advanced(&a, &b, &mut out, hints);
-            let _ = tx.send(out);
-        });
-        
-        rx.recv().unwrap_or_else(|_| Vec::new())
+        // 2. Fallback to CPU execution on the ArchX blocking pool.
+        // The future is woken on completion, so the executor is never blocked.
+        BlockingPool::global()
+            .spawn(move || {
+                let mut out = vec![0.0; a.len()];
+                add_advanced(&a, &b, &mut out, hints);
+                Ok(out)
+            })
+            .await
+            .unwrap_or_default()
     })
 }
@@ -0,0 +1,84 @@
+//! Compares two profiler exports and fails when operations got slower.
+//!
+//! ```text
+//! archx-diff baseline.json candidate.json [--threshold 10] [--alpha 0.05] [--min-samples 5] [--json]
+//! ```
+//!
+//! Exits with 1 if any operation regressed and 2 on usage or input errors.
+
+use archx::profiler::{DiffThresholds, ProfileDiff, ProfileExport};
+use std::process::ExitCode;
+
+const USAGE: &str = "Usage: archx-diff BASELINE CANDIDATE [options]
+
+BASELINE and CANDIDATE are profiler exports (ProfileExport JSON or a plain
+metric list from Profiler::to_json).
+
+Options:
+  --threshold PCT     Mean slowdown that counts as a regression (default 10)
+  --alpha P           Significance level of the Mann-Whitney U test (default 0.05)
+  --min-samples N     Calls needed in both runs to test significance (default 5)
+  --json              Print the comparison as JSON
+  -h, --help          Show this help";
+
+fn parse<T: std::str::FromStr>(flag: &str, value: Option<String>) -> Result<T, String> {
+    let value = value.ok_or_else(|| format!("{} needs a value", flag))?;
+    value.parse().map_err(|_| format!("invalid value '{}' for {}", value, flag))
+}
+
+fn main() -> ExitCode {
+    let mut json = false;
+    let mut thresholds = DiffThresholds::default();
+    let mut files = Vec::new();
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let result = match arg.as_str() {
+            "--json" => {
+                json = true;
+                Ok(())
+            }
+            "--threshold" => parse(&arg, args.next()).map(|v| thresholds.max_regression_pct = v),
+            "--alpha" => parse(&arg, args.next()).map(|v| thresholds.alpha = v),
+            "--min-samples" => parse(&arg, args.next()).map(|v| thresholds.min_samples = v),
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return ExitCode::SUCCESS;
+            }
+            other if other.starts_with('-') => Err(format!("unknown argument '{}'", other)),
+            file => {
+                files.push(file.to_string());
+                Ok(())
+            }
+        };
+        if let Err(message) = result {
+            eprintln!("archx-diff: {}\n\n{}", message, USAGE);
+            return ExitCode::from(2);
+        }
+    }
+
+    let [baseline, candidate] = files.as_slice() else {
+        eprintln!("archx-diff: expected two files\n\n{}", USAGE);
+        return ExitCode::from(2);
+    };
+    let load = |path: &str| ProfileExport::load(path).map_err(|e| format!("cannot read {}: {}", path, e));
+    let (baseline, candidate) = match (load(baseline), load(candidate)) {
+        (Ok(b), Ok(c)) => (b, c),
+        (Err(message), _) | (_, Err(message)) => {
+            eprintln!("archx-diff: {}", message);
+            return ExitCode::from(2);
+        }
+    };
+
+    let diff = ProfileDiff::compare(&baseline, &candidate, &thresholds);
+    if json {
+        for warning in &diff.hardware_warnings {
+            eprintln!("warning: {}", warning);
+        }
+        println!("{}", diff.to_json());
+    } else {
+        print!("{}", diff);
+    }
+
+    if diff.has_regressions() { ExitCode::from(1) } else { ExitCode::SUCCESS }
+}
@@ -0,0 +1,59 @@
+//! Prints what ArchX detects on this machine and the strategies it would choose.
+//!
+//! ```text
+//! archx-info [--json] [--sizes 1024,1000000,...]
+//! ```
+
+use archx::report::{HardwareReport, DEFAULT_SIZES};
+use std::process::ExitCode;
+
+const USAGE: &str = "Usage: archx-info [--json] [--sizes N,N,...]
+
+Options:
+  --json         Print the report as JSON
+  --sizes LIST   Comma-separated workload sizes (elements) for the strategy table
+  -h, --help     Show this help";
+
+fn parse_sizes(list: &str) -> Result<Vec<usize>, String> {
+    list.split(',')
+        .map(|s| s.trim().replace('_', ""))
+        .filter(|s| !s.is_empty())
+        .map(|s| s.parse().map_err(|_| format!("invalid size '{}'", s)))
+        .collect()
+}
+
+fn main() -> ExitCode {
+    let mut json = false;
+    let mut sizes = DEFAULT_SIZES.to_vec();
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let result = match arg.as_str() {
+            "--json" => {
+                json = true;
+                Ok(())
+            }
+            "--sizes" => args.next()
+                .ok_or_else(|| "--sizes needs a value".to_string())
+                .and_then(|list| parse_sizes(&list))
+                .map(|parsed| sizes = parsed),
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return ExitCode::SUCCESS;
+            }
+            other => Err(format!("unknown argument '{}'", other)),
+        };
+        if let Err(message) = result {
+            eprintln!("archx-info: {}\n\n{}", message, USAGE);
+            return ExitCode::from(2);
+        }
+    }
+
+    let report = HardwareReport::collect_for(&sizes);
+    if json {
+        println!("{}", report.to_json());
+    } else {
+        print!("{}", report);
+    }
+    ExitCode::SUCCESS
+}
@@ -1,14 +1,39 @@
-use serde::Serialize;
-use crate::hardware::{CpuInfo, GpuInfo};
+use serde::{Deserialize, Serialize};
+use crate::core::error::{ArchXCoreError, CoreResult};
+use crate::detect::SystemContext;
+use crate::hardware::{CpuInfo, GpuInfo, SystemInfo};
+use std::path::Path;
 
-#[derive(Debug, Clone, Serialize)]
+/// A snapshot of the hardware the engine plans against.
+///
+/// Serializes to JSON, so a state captured on one machine can be replayed
+/// elsewhere with [`StaticHardwareProvider`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HardwareState {
     pub cpu: CpuInfo,
+    #[serde(default)]
     pub gpu: Option<GpuInfo>,
+    #[serde(default)]
+    pub gpus: Vec<GpuInfo>,
     pub available_memory_gb: f64,
+    #[serde(default)]
+    pub context: SystemContext,
+    #[serde(default)]
     pub timestamp: u64,
 }
 
+impl From<HardwareState> for SystemInfo {
+    fn from(state: HardwareState) -> Self {
+        Self {
+            cpu: state.cpu,
+            gpu: state.gpu.or_else(|| state.gpus.first().cloned()),
+            gpus: state.gpus,
+            available_memory_gb: state.available_memory_gb,
+            context: state.context,
+        }
+    }
+}
+
 pub trait HardwareProvider: Send + Sync {
     fn capture_state(&self) -> HardwareState;
 }
@@ -21,7 +46,9 @@ impl HardwareProvider for DefaultHardwareProvider {
         HardwareState {
             cpu: info.cpu,
             gpu: info.gpu,
+            gpus: info.gpus,
             available_memory_gb: info.available_memory_gb,
+            context: info.context,
             timestamp: std::time::SystemTime::now()
                 .duration_since(std::time::UNIX_EPOCH)
                 .unwrap_or_default()
@@ -29,3 +56,40 @@ impl HardwareProvider for DefaultHardwareProvider {
         }
     }
 }
+
+/// Always reports the same state, for deterministic tests and what-if simulation.
+#[derive(Debug, Clone)]
+pub struct StaticHardwareProvider {
+    state: HardwareState,
+}
+
+impl StaticHardwareProvider {
+    pub fn new(state: HardwareState) -> Self {
+        Self { state }
+    }
+
+    /// Parses a state previously written with `serde_json` (e.g. a customer hardware profile).
+    pub fn from_json(json: &str) -> CoreResult<Self> {
+        serde_json::from_str(json)
+            .map(Self::new)
+            .map_err(|e| ArchXCoreError::InvalidInput(format!("Invalid hardware profile: {}", e)))
+    }
+
+    /// Reads a JSON hardware profile from `path`.
+    pub fn from_file(path: impl AsRef<Path>) -> CoreResult<Self> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path)
+            .map_err(|e| ArchXCoreError::DetectionError(format!("Cannot read {}: {}", path.display(), e)))?;
+        Self::from_json(&json)
+    }
+
+    pub fn state(&self) -> &HardwareState {
+        &self.state
+    }
+}
+
+impl HardwareProvider for StaticHardwareProvider {
+    fn capture_state(&self) -> HardwareState {
+        self.state.clone()
+    }
+}
@@ -1,5 +1,7 @@
 use crate::core::error::CoreResult;
 use crate::math::MathMode;
+use crate::runtime::control::ExecControl;
+use crate::runtime::ArchXSched;
 
 #[derive(Debug, Clone, Copy)]
 pub enum ArithmeticMode {
@@ -24,6 +26,30 @@ pub trait MathProcessor: Send + Sync {
     fn mul(&self, a: &[f32], b: &[f32], out: &mut [f32], mode: ArithmeticMode) -> CoreResult<()>;
     fn dot(&self, a: &[f32], b: &[f32], mode: ArithmeticMode) -> CoreResult<f32>;
     fn sum(&self, a: &[f32], mode: ArithmeticMode) -> CoreResult<f32>;
+
+    // Controlled variants: processors that can stop mid-operation override these.
+    // The defaults only check the control once before running the plain operation.
+
+    fn add_with(&self, a: &[f32], b: &[f32], out: &mut [f32], mode: ArithmeticMode, ctl: &ExecControl) -> CoreResult<()> {
+        ctl.check()?;
+        self.add(a, b, out, mode)
+    }
+    fn sub_with(&self, a: &[f32], b: &[f32], out: &mut [f32], mode: ArithmeticMode, ctl: &ExecControl) -> CoreResult<()> {
+        ctl.check()?;
+        self.sub(a, b, out, mode)
+    }
+    fn mul_with(&self, a: &[f32], b: &[f32], out: &mut [f32], mode: ArithmeticMode, ctl: &ExecControl) -> CoreResult<()> {
+        ctl.check()?;
+        self.mul(a, b, out, mode)
+    }
+    fn dot_with(&self, a: &[f32], b: &[f32], mode: ArithmeticMode, ctl: &ExecControl) -> CoreResult<f32> {
+        ctl.check()?;
+        self.dot(a, b, mode)
+    }
+    fn sum_with(&self, a: &[f32], mode: ArithmeticMode, ctl: &ExecControl) -> CoreResult<f32> {
+        ctl.check()?;
+        self.sum(a, mode)
+    }
 }
 
 pub struct DefaultMathProcessor;
@@ -75,4 +101,36 @@ impl MathProcessor for DefaultMathProcessor {
     fn sum(&self, a: &[f32], _mode: ArithmeticMode) -> CoreResult<f32> {
         Ok(crate::runtime::ArchXSched::parallel_sum(a))
     }
+
+    // f32 arithmetic is identical in every mode, so the controlled variants can
+    // use the chunked SIMD scheduler directly.
+
+    fn add_with(&self, a: &[f32], b: &[f32], out: &mut [f32], mode: ArithmeticMode, ctl: &ExecControl) -> CoreResult<()> {
+        if !ctl.is_active() {
+            return self.add(a, b, out, mode);
+        }
+        Ok(ArchXSched::parallel_add_with(a, b, out, ctl)?)
+    }
+
+    fn sub_with(&self, a: &[f32], b: &[f32], out: &mut [f32], mode: ArithmeticMode, ctl: &ExecControl) -> CoreResult<()> {
+        if !ctl.is_active() {
+            return self.sub(a, b, out, mode);
+        }
+        Ok(ArchXSched::parallel_sub_with(a, b, out, ctl)?)
+    }
+
+    fn mul_with(&self, a: &[f32], b: &[f32], out: &mut [f32], mode: ArithmeticMode, ctl: &ExecControl) -> CoreResult<()> {
+        if !ctl.is_active() {
+            return self.mul(a, b, out, mode);
+        }
+        Ok(ArchXSched::parallel_mul_with(a, b, out, ctl)?)
+    }
+
+    fn dot_with(&self, a: &[f32], b: &[f32], _mode: ArithmeticMode, ctl: &ExecControl) -> CoreResult<f32> {
+        Ok(ArchXSched::parallel_dot_with(a, b, ctl)?)
+    }
+
+    fn sum_with(&self, a: &[f32], _mode: ArithmeticMode, ctl: &ExecControl) -> CoreResult<f32> {
+        Ok(ArchXSched::parallel_sum_with(a, ctl)?)
+    }
 }
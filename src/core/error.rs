@@ -18,6 +18,10 @@ pub enum ArchXCoreError {
     InvalidInput(String),
     /// A general engine failure.
     EngineFault(String),
+    /// The task was cancelled before completion.
+    Cancelled,
+    /// The task ran past its deadline.
+    DeadlineExceeded,
 }
 
 impl fmt::Display for ArchXCoreError {
@@ -31,10 +35,29 @@ impl fmt::Display for ArchXCoreError {
             ArchXCoreError::MathOverflow => write!(f, "Core Math Overflow"),
             ArchXCoreError::InvalidInput(msg) => write!(f, "Core Invalid Input: {}", msg),
             ArchXCoreError::EngineFault(msg) => write!(f, "Core Engine Fault: {}", msg),
+            ArchXCoreError::Cancelled => write!(f, "Core Task Cancelled"),
+            ArchXCoreError::DeadlineExceeded => write!(f, "Core Task Deadline Exceeded"),
         }
     }
 }
 
 impl std::error::Error for ArchXCoreError {}
 
+impl From<crate::error::ArchXError> for ArchXCoreError {
+    fn from(err: crate::error::ArchXError) -> Self {
+        use crate::error::ArchXError;
+        match err {
+            ArchXError::GpuError(msg) => ArchXCoreError::GpuFailure(msg),
+            ArchXError::SimdError(msg) => ArchXCoreError::SimdFailure(msg),
+            ArchXError::InvalidInput(msg) => ArchXCoreError::InvalidInput(msg),
+            ArchXError::ArithmeticOverflow => ArchXCoreError::MathOverflow,
+            ArchXError::HardwareError(msg) => ArchXCoreError::DetectionError(msg),
+            ArchXError::ExecutionError(msg) => ArchXCoreError::EngineFault(msg),
+            ArchXError::Cancelled => ArchXCoreError::Cancelled,
+            ArchXError::DeadlineExceeded => ArchXCoreError::DeadlineExceeded,
+            ArchXError::ResourceLimit(msg) => ArchXCoreError::ResourceError(msg),
+        }
+    }
+}
+
 pub type CoreResult<T> = Result<T, ArchXCoreError>;
@@ -17,19 +17,20 @@ pub struct CoreEngine {
     pub(crate) resources: Arc<dyn resource::ResourceManager>,
 }
 
+impl Default for CoreEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl CoreEngine {
     pub fn new() -> Self {
-        let resources = Arc::new(resource::DefaultResourceManager::new());
-        let hardware = Arc::new(detect::DefaultHardwareProvider);
-        let scheduler = Arc::new(scheduler::DefaultScheduler::new(resources.clone()));
-        let engine = Arc::new(engine::DefaultMathProcessor);
-
-        Self {
-            hardware,
-            scheduler,
-            engine,
-            resources,
-        }
+        Self::builder().build()
+    }
+
+    /// Starts a builder whose unset components fall back to the defaults.
+    pub fn builder() -> CoreEngineBuilder {
+        CoreEngineBuilder::default()
     }
 
     pub fn global() -> &'static Self {
@@ -37,12 +38,85 @@ impl CoreEngine {
         INSTANCE.get_or_init(CoreEngine::new)
     }
 
+    pub fn hardware(&self) -> &dyn detect::HardwareProvider {
+        self.hardware.as_ref()
+    }
+
+    pub fn scheduler(&self) -> &dyn scheduler::UnifiedScheduler {
+        self.scheduler.as_ref()
+    }
+
+    pub fn math(&self) -> &dyn engine::MathProcessor {
+        self.engine.as_ref()
+    }
+
     pub fn resources(&self) -> &dyn resource::ResourceManager {
         self.resources.as_ref()
     }
+
+    /// Captures the current state from the hardware provider.
+    pub fn hardware_state(&self) -> HardwareState {
+        self.hardware.capture_state()
+    }
+
+    /// The provider's state as `SystemInfo`, as consumed by `AdaptiveEngine::choose_strategy`.
+    pub fn system_info(&self) -> crate::hardware::SystemInfo {
+        self.hardware_state().into()
+    }
+}
+
+/// Assembles a [`CoreEngine`] from custom components.
+///
+/// ```
+/// use archx::core::{CoreEngine, StaticHardwareProvider};
+/// # let json = serde_json::to_string(&CoreEngine::global().hardware_state()).unwrap();
+/// let provider = StaticHardwareProvider::from_json(&json).unwrap();
+/// let engine = CoreEngine::builder().with_hardware(provider).build();
+/// assert_eq!(engine.hardware_state().cpu.cores, CoreEngine::global().hardware_state().cpu.cores);
+/// ```
+#[derive(Default)]
+pub struct CoreEngineBuilder {
+    hardware: Option<Arc<dyn detect::HardwareProvider>>,
+    scheduler: Option<Arc<dyn scheduler::UnifiedScheduler>>,
+    engine: Option<Arc<dyn engine::MathProcessor>>,
+    resources: Option<Arc<dyn resource::ResourceManager>>,
+}
+
+impl CoreEngineBuilder {
+    pub fn with_hardware(mut self, hardware: impl detect::HardwareProvider + 'static) -> Self {
+        self.hardware = Some(Arc::new(hardware));
+        self
+    }
+
+    pub fn with_scheduler(mut self, scheduler: impl scheduler::UnifiedScheduler + 'static) -> Self {
+        self.scheduler = Some(Arc::new(scheduler));
+        self
+    }
+
+    pub fn with_math(mut self, engine: impl engine::MathProcessor + 'static) -> Self {
+        self.engine = Some(Arc::new(engine));
+        self
+    }
+
+    pub fn with_resources(mut self, resources: impl resource::ResourceManager + 'static) -> Self {
+        self.resources = Some(Arc::new(resources));
+        self
+    }
+
+    /// Builds the engine. The default scheduler reserves threads on the
+    /// engine's resource manager, custom or not.
+    pub fn build(self) -> CoreEngine {
+        let resources = self.resources.unwrap_or_else(|| Arc::new(resource::DefaultResourceManager::new()));
+        CoreEngine {
+            hardware: self.hardware.unwrap_or_else(|| Arc::new(detect::DefaultHardwareProvider)),
+            scheduler: self.scheduler.unwrap_or_else(|| Arc::new(scheduler::DefaultScheduler::new(resources.clone()))),
+            engine: self.engine.unwrap_or_else(|| Arc::new(engine::DefaultMathProcessor)),
+            resources,
+        }
+    }
 }
 
-pub use detect::{HardwareProvider, HardwareState};
-pub use scheduler::UnifiedScheduler;
-pub use engine::{MathProcessor, ArithmeticMode};
-pub use resource::ResourceManager;
+pub use detect::{DefaultHardwareProvider, HardwareProvider, HardwareState, StaticHardwareProvider};
+pub use scheduler::{DefaultScheduler, UnifiedScheduler};
+pub use engine::{DefaultMathProcessor, MathProcessor, ArithmeticMode};
+pub use resource::{DefaultResourceManager, MemoryReservation, ResourceManager};
@@ -1,11 +1,12 @@
 use serde::Serialize;
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 
 #[derive(Debug, Clone, Serialize, Default)]
 pub struct UsageInfo {
     pub active_threads: usize,
     pub gpu_memory_used_kb: usize,
     pub active_tasks: usize,
+    pub reserved_memory_bytes: u64,
 }
 
 pub trait ResourceManager: Send + Sync {
@@ -13,21 +14,86 @@ pub trait ResourceManager: Send + Sync {
     fn get_current_usage(&self) -> UsageInfo;
     fn reserve_threads(&self, count: usize) -> bool;
     fn release_threads(&self, count: usize);
+
+    /// Reserves `bytes` of host memory before an allocation. Admits everything by default.
+    fn reserve_memory(&self, bytes: u64) -> bool {
+        let _ = bytes;
+        true
+    }
+
+    fn release_memory(&self, bytes: u64) {
+        let _ = bytes;
+    }
+}
+
+/// Releases a memory reservation when dropped.
+pub struct MemoryReservation<'a> {
+    manager: &'a dyn ResourceManager,
+    bytes: u64,
 }
 
+impl<'a> MemoryReservation<'a> {
+    /// Reserves `bytes` on `manager`, or returns `None` if admission is refused.
+    pub fn new(manager: &'a dyn ResourceManager, bytes: u64) -> Option<Self> {
+        // Lazily: an eagerly built guard would be dropped, releasing memory never reserved.
+        manager.reserve_memory(bytes).then(|| Self { manager, bytes })
+    }
+}
+
+impl Drop for MemoryReservation<'_> {
+    fn drop(&mut self) {
+        self.manager.release_memory(self.bytes);
+    }
+}
+
+/// Share of currently available memory ArchX may reserve.
+const MEMORY_HEADROOM: f64 = 0.8;
+
 pub struct DefaultResourceManager {
     active_threads: AtomicUsize,
     active_tasks: AtomicUsize,
-    // Add more tracking such as GPU memory if platform supported
+    reserved_memory: AtomicU64,
+    max_threads: usize,
+    /// Fixed memory budget; `None` follows the (cgroup-aware) available memory.
+    memory_budget: Option<u64>,
+}
+
+impl Default for DefaultResourceManager {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl DefaultResourceManager {
+    /// Limits threads to the rayon pool and the cgroup CPU quota, and memory to
+    /// 80% of what is currently available to the process (container limits included).
     pub fn new() -> Self {
+        let max_threads = rayon::current_num_threads().min(crate::detect::effective_threads());
+        Self::with_limits(max_threads, None)
+    }
+
+    /// Uses explicit limits instead of the detected ones.
+    pub fn with_limits(max_threads: usize, memory_budget_bytes: Option<u64>) -> Self {
         Self {
             active_threads: AtomicUsize::new(0),
             active_tasks: AtomicUsize::new(0),
+            reserved_memory: AtomicU64::new(0),
+            max_threads: max_threads.max(1),
+            memory_budget: memory_budget_bytes,
         }
     }
+
+    pub fn max_threads(&self) -> usize {
+        self.max_threads
+    }
+
+    /// Bytes that may be reserved in total right now.
+    pub fn memory_budget(&self) -> u64 {
+        self.memory_budget.unwrap_or_else(|| {
+            let memory = &crate::detect::HardwareCache::global().dynamic_facts().memory;
+            (memory.available_kb as f64 * 1024.0 * MEMORY_HEADROOM) as u64
+        })
+    }
 }
 
 impl ResourceManager for DefaultResourceManager {
@@ -42,21 +108,35 @@ impl ResourceManager for DefaultResourceManager {
             active_threads: self.active_threads.load(Ordering::Relaxed),
             gpu_memory_used_kb: 0, // TODO: Interop with GPU backend
             active_tasks: self.active_tasks.load(Ordering::Relaxed),
+            reserved_memory_bytes: self.reserved_memory.load(Ordering::Relaxed),
         }
     }
 
     fn reserve_threads(&self, count: usize) -> bool {
         // Rudimentary admission control
-        let current = self.active_threads.load(Ordering::SeqCst);
-        let max = rayon::current_num_threads();
-        if current + count > max {
-            return false;
-        }
-        self.active_threads.fetch_add(count, Ordering::SeqCst);
-        true
+        self.active_threads
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |current| {
+                (current + count <= self.max_threads).then_some(current + count)
+            })
+            .is_ok()
     }
 
     fn release_threads(&self, count: usize) {
         self.active_threads.fetch_sub(count, Ordering::SeqCst);
     }
+
+    fn reserve_memory(&self, bytes: u64) -> bool {
+        let budget = self.memory_budget();
+        self.reserved_memory
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |reserved| {
+                reserved.checked_add(bytes).filter(|&total| total <= budget)
+            })
+            .is_ok()
+    }
+
+    fn release_memory(&self, bytes: u64) {
+        let _ = self.reserved_memory.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |reserved| {
+            Some(reserved.saturating_sub(bytes))
+        });
+    }
 }
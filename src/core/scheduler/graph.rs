@@ -0,0 +1,535 @@
+use crate::core::engine::ArithmeticMode;
+use crate::core::CoreEngine;
+use crate::error::{ArchXError, ArchXResult};
+use crate::math::MathMode;
+use crate::profiler::{get_profiler, Metric};
+use std::collections::{HashMap, VecDeque};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::mpsc::channel;
+use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::time::{Duration, Instant};
+
+/// Handle to a node in a [`TaskGraph`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeId(usize);
+
+/// Handle to a buffer owned by a [`TaskGraph`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BufferId(usize);
+
+type TaskFn = Box<dyn FnOnce(&GraphContext) -> ArchXResult<()> + Send>;
+
+enum NodeOp {
+    Add { a: BufferId, b: BufferId, out: BufferId },
+    Sub { a: BufferId, b: BufferId, out: BufferId },
+    Mul { a: BufferId, b: BufferId, out: BufferId },
+    Dot { a: BufferId, b: BufferId, out: BufferId },
+    Sum { a: BufferId, out: BufferId },
+    Task(TaskFn),
+}
+
+struct Node {
+    name: String,
+    op: NodeOp,
+    after: Vec<NodeId>,
+    reads: Vec<BufferId>,
+    writes: Vec<BufferId>,
+}
+
+/// Declared dependencies and buffer accesses of a closure node.
+///
+/// Buffer accesses create ordering edges automatically: a node runs after
+/// every earlier node that writes a buffer it touches, and a writer runs
+/// after every earlier reader of that buffer.
+#[derive(Debug, Clone, Default)]
+pub struct TaskSpec {
+    after: Vec<NodeId>,
+    reads: Vec<BufferId>,
+    writes: Vec<BufferId>,
+}
+
+impl TaskSpec {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Runs the task only after `nodes` have completed.
+    pub fn after(mut self, nodes: &[NodeId]) -> Self {
+        self.after.extend_from_slice(nodes);
+        self
+    }
+
+    /// Declares buffers the task reads.
+    pub fn reads(mut self, buffers: &[BufferId]) -> Self {
+        self.reads.extend_from_slice(buffers);
+        self
+    }
+
+    /// Declares buffers the task writes.
+    pub fn writes(mut self, buffers: &[BufferId]) -> Self {
+        self.writes.extend_from_slice(buffers);
+        self
+    }
+}
+
+/// Buffer access handed to closure nodes. Only declared buffers are accessible.
+pub struct GraphContext<'a> {
+    buffers: &'a [RwLock<Vec<f32>>],
+    reads: &'a [BufferId],
+    writes: &'a [BufferId],
+}
+
+impl GraphContext<'_> {
+    /// Borrows a buffer declared in `reads` or `writes`.
+    pub fn read(&self, id: BufferId) -> ArchXResult<RwLockReadGuard<'_, Vec<f32>>> {
+        if !self.reads.contains(&id) && !self.writes.contains(&id) {
+            return Err(ArchXError::InvalidInput(format!("Buffer {} was not declared for reading", id.0)));
+        }
+        Ok(self.buffers[id.0].read().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    /// Mutably borrows a buffer declared in `writes`.
+    pub fn write(&self, id: BufferId) -> ArchXResult<RwLockWriteGuard<'_, Vec<f32>>> {
+        if !self.writes.contains(&id) {
+            return Err(ArchXError::InvalidInput(format!("Buffer {} was not declared for writing", id.0)));
+        }
+        Ok(self.buffers[id.0].write().unwrap_or_else(|e| e.into_inner()))
+    }
+}
+
+/// Timing of one executed node.
+#[derive(Debug, Clone)]
+pub struct NodeTiming {
+    pub node: NodeId,
+    pub name: String,
+    /// Offset from the start of the graph run.
+    pub start: Duration,
+    pub duration: Duration,
+}
+
+/// Execution summary of a graph run.
+#[derive(Debug, Clone)]
+pub struct GraphReport {
+    pub wall_time: Duration,
+    pub nodes: Vec<NodeTiming>,
+    /// Nodes on the longest dependency chain, in execution order.
+    pub critical_path: Vec<NodeId>,
+    /// Sum of node durations along the critical path.
+    pub critical_path_time: Duration,
+    /// Highest number of nodes that ran at the same time.
+    pub max_concurrency: usize,
+}
+
+impl GraphReport {
+    /// Names of the critical path nodes, for display. Names need not be unique;
+    /// use `critical_path` to identify nodes.
+    pub fn critical_path_names(&self) -> Vec<&str> {
+        self.critical_path.iter()
+            .filter_map(|id| self.nodes.iter().find(|n| n.node == *id))
+            .map(|n| n.name.as_str())
+            .collect()
+    }
+}
+
+/// Buffers and report produced by [`TaskGraph::run`].
+pub struct GraphOutput {
+    buffers: Vec<Vec<f32>>,
+    pub report: GraphReport,
+}
+
+impl GraphOutput {
+    pub fn buffer(&self, id: BufferId) -> &[f32] {
+        &self.buffers[id.0]
+    }
+
+    pub fn take(&mut self, id: BufferId) -> Vec<f32> {
+        std::mem::take(&mut self.buffers[id.0])
+    }
+}
+
+/// A DAG of ArchX operations and closures with declared dependencies.
+///
+/// Independent nodes run concurrently, each admitted through the engine's
+/// `ResourceManager`; when no thread can be reserved and nothing is running,
+/// the next node still runs so the graph always makes progress.
+///
+/// ```rust
+/// use archx::{TaskGraph, TaskSpec};
+///
+/// let mut graph = TaskGraph::new();
+/// let x = graph.buffer(vec![3.0; 1024]);
+/// let y = graph.buffer(vec![4.0; 1024]);
+/// let xy = graph.buffer(vec![0.0; 1024]);
+/// let total = graph.buffer(vec![0.0]);
+///
+/// graph.mul("x*y", x, y, xy);
+/// graph.sum("reduce", xy, total);
+/// graph.task("scale", TaskSpec::new().writes(&[total]), move |ctx| {
+///     ctx.write(total)?[0] /= 1024.0;
+///     Ok(())
+/// });
+///
+/// let out = graph.run().unwrap();
+/// assert_eq!(out.buffer(total)[0], 12.0);
+/// ```
+pub struct TaskGraph {
+    nodes: Vec<Node>,
+    buffers: Vec<Vec<f32>>,
+    mode: MathMode,
+}
+
+impl Default for TaskGraph {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TaskGraph {
+    pub fn new() -> Self {
+        Self {
+            nodes: Vec::new(),
+            buffers: Vec::new(),
+            mode: MathMode::Balanced,
+        }
+    }
+
+    /// Sets the arithmetic mode used by operation nodes.
+    pub fn with_mode(mut self, mode: MathMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Registers a buffer owned by the graph.
+    pub fn buffer(&mut self, data: Vec<f32>) -> BufferId {
+        self.buffers.push(data);
+        BufferId(self.buffers.len() - 1)
+    }
+
+    /// `out = a + b`
+    pub fn add(&mut self, name: &str, a: BufferId, b: BufferId, out: BufferId) -> NodeId {
+        self.push(name, NodeOp::Add { a, b, out }, Vec::new(), vec![a, b], vec![out])
+    }
+
+    /// `out = a - b`
+    pub fn sub(&mut self, name: &str, a: BufferId, b: BufferId, out: BufferId) -> NodeId {
+        self.push(name, NodeOp::Sub { a, b, out }, Vec::new(), vec![a, b], vec![out])
+    }
+
+    /// `out = a * b`
+    pub fn mul(&mut self, name: &str, a: BufferId, b: BufferId, out: BufferId) -> NodeId {
+        self.push(name, NodeOp::Mul { a, b, out }, Vec::new(), vec![a, b], vec![out])
+    }
+
+    /// `out[0] = dot(a, b)`
+    pub fn dot(&mut self, name: &str, a: BufferId, b: BufferId, out: BufferId) -> NodeId {
+        self.push(name, NodeOp::Dot { a, b, out }, Vec::new(), vec![a, b], vec![out])
+    }
+
+    /// `out[0] = sum(a)`
+    pub fn sum(&mut self, name: &str, a: BufferId, out: BufferId) -> NodeId {
+        self.push(name, NodeOp::Sum { a, out }, Vec::new(), vec![a], vec![out])
+    }
+
+    /// Adds a closure node with explicit dependencies and buffer accesses.
+    pub fn task<F>(&mut self, name: &str, spec: TaskSpec, f: F) -> NodeId
+    where
+        F: FnOnce(&GraphContext) -> ArchXResult<()> + Send + 'static,
+    {
+        self.push(name, NodeOp::Task(Box::new(f)), spec.after, spec.reads, spec.writes)
+    }
+
+    /// Makes `node` wait for `on`. `on` must have been added before `node`.
+    pub fn add_dependency(&mut self, node: NodeId, on: NodeId) {
+        if let Some(n) = self.nodes.get_mut(node.0) {
+            n.after.push(on);
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    fn push(&mut self, name: &str, op: NodeOp, after: Vec<NodeId>, reads: Vec<BufferId>, writes: Vec<BufferId>) -> NodeId {
+        self.nodes.push(Node { name: name.to_string(), op, after, reads, writes });
+        NodeId(self.nodes.len() - 1)
+    }
+
+    /// Runs the graph on the global engine.
+    ///
+    /// The first failing node fails the run; nodes that depend on it are not
+    /// started. A panicking node counts as failed with `ExecutionError`.
+    pub fn run(self) -> ArchXResult<GraphOutput> {
+        self.run_on(CoreEngine::global())
+    }
+
+    /// Runs the graph on `engine`, using its math processor and resource manager.
+    pub fn run_on(self, engine: &CoreEngine) -> ArchXResult<GraphOutput> {
+        let deps = self.resolve_dependencies()?;
+        let n = self.nodes.len();
+
+        let mut dependents = vec![Vec::new(); n];
+        let mut pending: Vec<usize> = deps.iter().map(|d| d.len()).collect();
+        for (node, node_deps) in deps.iter().enumerate() {
+            for dep in node_deps {
+                dependents[dep.0].push(node);
+            }
+        }
+
+        let mode: ArithmeticMode = self.mode.into();
+        let buffers: Vec<RwLock<Vec<f32>>> = self.buffers.into_iter().map(RwLock::new).collect();
+        let mut nodes: Vec<Option<Node>> = self.nodes.into_iter().map(Some).collect();
+        let mut names = vec![String::new(); n];
+        let mut timings: Vec<Option<(Duration, Duration)>> = vec![None; n];
+
+        let mut ready: VecDeque<usize> = (0..n).filter(|&i| pending[i] == 0).collect();
+        let mut running = 0usize;
+        let mut max_concurrency = 0usize;
+        let mut completed = 0usize;
+        let mut failure: Option<ArchXError> = None;
+        let graph_start = Instant::now();
+
+        let _scope = crate::profiler::ProfileScope::new("Task Graph", "CPU", "TaskGraph");
+        let (tx, rx) = channel::<(usize, bool, ArchXResult<()>, Duration, Duration)>();
+        let span = crate::profiler::SpanContext::current();
+
+        std::thread::scope(|s| {
+            while completed < n {
+                // Launch every ready node the resource manager admits.
+                while failure.is_none() {
+                    let Some(&idx) = ready.front() else { break };
+                    let reserved = engine.resources.reserve_threads(1);
+                    if !reserved && running > 0 {
+                        break;
+                    }
+                    ready.pop_front();
+
+                    let node = nodes[idx].take().expect("graph node scheduled twice");
+                    names[idx] = node.name.clone();
+                    let tx = tx.clone();
+                    let buffers = &buffers;
+                    running += 1;
+                    max_concurrency = max_concurrency.max(running);
+
+                    s.spawn(move || {
+                        let _entered = span.enter();
+                        let start = graph_start.elapsed();
+                        let name = node.name.clone();
+                        // A panicking node still reports back, so the loop below
+                        // does not wait for it forever and its threads are released.
+                        let result = catch_unwind(AssertUnwindSafe(|| Self::execute(node, buffers, engine, mode)))
+                            .unwrap_or_else(|_| Err(ArchXError::ExecutionError(format!("Node '{}' panicked", name))));
+                        let end = graph_start.elapsed();
+                        let _ = tx.send((idx, reserved, result, start, end - start));
+                    });
+                }
+
+                if running == 0 {
+                    break;
+                }
+
+                let Ok((idx, reserved, result, start, duration)) = rx.recv() else { break };
+                running -= 1;
+                completed += 1;
+                if reserved {
+                    engine.resources.release_threads(1);
+                }
+                timings[idx] = Some((start, duration));
+
+                match result {
+                    Ok(()) => {
+                        for &next in &dependents[idx] {
+                            pending[next] -= 1;
+                            if pending[next] == 0 {
+                                ready.push_back(next);
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        failure.get_or_insert(e);
+                    }
+                }
+            }
+        });
+
+        if let Some(e) = failure {
+            return Err(e);
+        }
+
+        let report = Self::build_report(&names, &timings, &deps, graph_start.elapsed(), max_concurrency);
+        Self::record_report(&report);
+
+        let buffers = buffers.into_iter().map(|b| b.into_inner().unwrap_or_else(|e| e.into_inner())).collect();
+        Ok(GraphOutput { buffers, report })
+    }
+
+    /// Combines explicit dependencies with hazards inferred from buffer accesses.
+    fn resolve_dependencies(&self) -> ArchXResult<Vec<Vec<NodeId>>> {
+        let mut last_writer: HashMap<BufferId, NodeId> = HashMap::new();
+        let mut readers: HashMap<BufferId, Vec<NodeId>> = HashMap::new();
+        let mut all = Vec::with_capacity(self.nodes.len());
+
+        for (i, node) in self.nodes.iter().enumerate() {
+            let id = NodeId(i);
+            for b in node.reads.iter().chain(&node.writes) {
+                if b.0 >= self.buffers.len() {
+                    return Err(ArchXError::InvalidInput(format!("Node '{}' uses unknown buffer {}", node.name, b.0)));
+                }
+            }
+            if node.writes.iter().any(|w| node.reads.contains(w)) && !matches!(node.op, NodeOp::Task(_)) {
+                return Err(ArchXError::InvalidInput(format!("Node '{}' reads and writes the same buffer", node.name)));
+            }
+
+            let mut deps = Vec::new();
+            for dep in &node.after {
+                if dep.0 >= i {
+                    return Err(ArchXError::InvalidInput(format!("Node '{}' depends on a node added after it", node.name)));
+                }
+                deps.push(*dep);
+            }
+            for r in &node.reads {
+                deps.extend(last_writer.get(r));
+            }
+            for w in &node.writes {
+                deps.extend(last_writer.get(w));
+                deps.extend(readers.get(w).into_iter().flatten());
+            }
+            deps.retain(|d| *d != id);
+            deps.sort();
+            deps.dedup();
+
+            for r in &node.reads {
+                readers.entry(*r).or_default().push(id);
+            }
+            for w in &node.writes {
+                last_writer.insert(*w, id);
+                readers.remove(w);
+            }
+            all.push(deps);
+        }
+        Ok(all)
+    }
+
+    fn execute(node: Node, buffers: &[RwLock<Vec<f32>>], engine: &CoreEngine, mode: ArithmeticMode) -> ArchXResult<()> {
+        let read = |id: BufferId| buffers[id.0].read().unwrap_or_else(|e| e.into_inner());
+        let write = |id: BufferId| buffers[id.0].write().unwrap_or_else(|e| e.into_inner());
+        let check = |a: &[f32], b: &[f32], out: usize| {
+            if a.len() != b.len() || a.len() != out {
+                return Err(ArchXError::InvalidInput(format!("Node '{}': slices must have identical lengths", node.name)));
+            }
+            Ok(())
+        };
+
+        match node.op {
+            NodeOp::Add { a, b, out } => {
+                let (a, b, mut out) = (read(a), read(b), write(out));
+                check(&a, &b, out.len())?;
+                engine.engine.add(&a, &b, &mut out, mode).map_err(ArchXError::from)
+            }
+            NodeOp::Sub { a, b, out } => {
+                let (a, b, mut out) = (read(a), read(b), write(out));
+                check(&a, &b, out.len())?;
+                engine.engine.sub(&a, &b, &mut out, mode).map_err(ArchXError::from)
+            }
+            NodeOp::Mul { a, b, out } => {
+                let (a, b, mut out) = (read(a), read(b), write(out));
+                check(&a, &b, out.len())?;
+                engine.engine.mul(&a, &b, &mut out, mode).map_err(ArchXError::from)
+            }
+            NodeOp::Dot { a, b, out } => {
+                let (a, b) = (read(a), read(b));
+                check(&a, &b, a.len())?;
+                let value = engine.engine.dot(&a, &b, mode).map_err(ArchXError::from)?;
+                Self::store_scalar(&mut write(out), value);
+                Ok(())
+            }
+            NodeOp::Sum { a, out } => {
+                let value = engine.engine.sum(&read(a), mode).map_err(ArchXError::from)?;
+                Self::store_scalar(&mut write(out), value);
+                Ok(())
+            }
+            NodeOp::Task(f) => {
+                let ctx = GraphContext { buffers, reads: &node.reads, writes: &node.writes };
+                f(&ctx)
+            }
+        }
+    }
+
+    fn store_scalar(out: &mut Vec<f32>, value: f32) {
+        if out.is_empty() {
+            out.push(value);
+        } else {
+            out[0] = value;
+        }
+    }
+
+    fn build_report(
+        names: &[String],
+        timings: &[Option<(Duration, Duration)>],
+        deps: &[Vec<NodeId>],
+        wall_time: Duration,
+        max_concurrency: usize,
+    ) -> GraphReport {
+        let n = names.len();
+        let durations: Vec<Duration> = timings.iter().map(|t| t.map(|(_, d)| d).unwrap_or_default()).collect();
+
+        // Longest path ending at each node; insertion order is a topological order.
+        let mut path_time = vec![Duration::ZERO; n];
+        let mut via: Vec<Option<usize>> = vec![None; n];
+        for i in 0..n {
+            let best = deps[i].iter().map(|d| d.0).max_by_key(|&d| path_time[d]);
+            if let Some(d) = best {
+                via[i] = Some(d);
+                path_time[i] = path_time[d];
+            }
+            path_time[i] += durations[i];
+        }
+
+        let mut critical_path = Vec::new();
+        let mut critical_path_time = Duration::ZERO;
+        if let Some(end) = (0..n).max_by_key(|&i| path_time[i]) {
+            critical_path_time = path_time[end];
+            let mut cursor = Some(end);
+            while let Some(i) = cursor {
+                critical_path.push(NodeId(i));
+                cursor = via[i];
+            }
+            critical_path.reverse();
+        }
+
+        let nodes = (0..n)
+            .filter_map(|i| timings[i].map(|(start, duration)| NodeTiming { node: NodeId(i), name: names[i].clone(), start, duration }))
+            .collect();
+
+        GraphReport { wall_time, nodes, critical_path, critical_path_time, max_concurrency }
+    }
+
+    fn record_report(report: &GraphReport) {
+        let profiler = get_profiler();
+        // The report is recorded right after the run, so the graph started `wall_time` ago.
+        let graph_start = profiler.now().saturating_sub(report.wall_time);
+        for node in &report.nodes {
+            let mut metadata = HashMap::new();
+            metadata.insert("start_offset_ms".to_string(), format!("{:.4}", node.start.as_secs_f64() * 1000.0));
+            metadata.insert("critical".to_string(), report.critical_path.contains(&node.node).to_string());
+            profiler.record(Metric {
+                start: graph_start + node.start,
+                metadata,
+                ..Metric::new(node.name.clone(), "TaskGraph", "CPU", node.duration)
+            });
+        }
+
+        let mut metadata = HashMap::new();
+        metadata.insert("critical_path".to_string(), report.critical_path_names().join(" -> "));
+        metadata.insert("wall_time_ms".to_string(), format!("{:.4}", report.wall_time.as_secs_f64() * 1000.0));
+        metadata.insert("max_concurrency".to_string(), report.max_concurrency.to_string());
+        profiler.record(Metric {
+            start: graph_start,
+            metadata,
+            ..Metric::new("Critical Path", "TaskGraph", "CPU", report.critical_path_time)
+        });
+    }
+}
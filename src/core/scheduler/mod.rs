@@ -4,6 +4,10 @@ use crate::core::resource::ResourceManager;
 use crate::gpu::GpuPolicy;
 use std::sync::Arc;
 
+pub mod graph;
+
+pub use graph::{TaskGraph, TaskSpec, GraphContext, GraphOutput, GraphReport, NodeId, BufferId};
+
 pub trait UnifiedScheduler: Send + Sync {
     fn prepare_task(&self) -> CoreResult<()>;
     fn complete_task(&self);
@@ -12,7 +16,7 @@ pub trait UnifiedScheduler: Send + Sync {
     fn set_gpu_policy(&self, policy: GpuPolicy);
 }
 
-impl dyn UnifiedScheduler {
+impl dyn UnifiedScheduler + '_ {
     pub fn run<F, R>(&self, task: F) -> CoreResult<R>
     where F: FnOnce() -> R 
     {
@@ -39,7 +43,7 @@ impl DefaultScheduler {
     }
 
     pub fn get_split_decision(&self, len: usize, state: &HardwareState) -> (usize, usize) {
-        let policy = *self.policy.read().unwrap();
+        let policy = self.policy.read().unwrap().resolve(&state.context);
         let gpu_policy = *self.gpu_policy.read().unwrap();
 
         match gpu_policy {
@@ -1,5 +1,5 @@
 /// Represents the word size of the CPU.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum Bitness {
     Bit32,
     Bit64,
@@ -1,5 +1,6 @@
 /// Represents specific CPU instruction set extensions.
-#[derive(Debug, Clone, Copy, Default, serde::Serialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
 pub struct CpuFeatures {
     /// Streaming SIMD Extensions 2
     pub sse2: bool,
@@ -11,6 +12,50 @@ pub struct CpuFeatures {
     pub avx512f: bool,
     /// ARM NEON (Advanced SIMD)
     pub neon: bool,
+
+    // --- x86 / x86_64 ---
+    /// Fused multiply-add (FMA3)
+    pub fma: bool,
+    /// Half-precision float conversion
+    pub f16c: bool,
+    /// Bit Manipulation Instruction Set 1
+    pub bmi1: bool,
+    /// Bit Manipulation Instruction Set 2
+    pub bmi2: bool,
+    /// Population count
+    pub popcnt: bool,
+    /// AVX-512 Byte and Word
+    pub avx512bw: bool,
+    /// AVX-512 Doubleword and Quadword
+    pub avx512dq: bool,
+    /// AVX-512 Vector Length extensions
+    pub avx512vl: bool,
+    /// AVX-512 Vector Neural Network Instructions
+    pub avx512vnni: bool,
+    /// AVX-512 bfloat16
+    pub avx512bf16: bool,
+    /// AVX-512 half-precision arithmetic
+    pub avx512fp16: bool,
+    /// VEX-encoded Vector Neural Network Instructions
+    pub avxvnni: bool,
+    /// Advanced Matrix Extensions tile architecture
+    pub amx_tile: bool,
+    /// AMX int8 tile multiply
+    pub amx_int8: bool,
+    /// AMX bfloat16 tile multiply
+    pub amx_bf16: bool,
+
+    // --- AArch64 ---
+    /// Scalable Vector Extension
+    pub sve: bool,
+    /// Scalable Vector Extension 2
+    pub sve2: bool,
+    /// Int8 dot product (SDOT/UDOT)
+    pub dotprod: bool,
+    /// Half-precision floating point arithmetic
+    pub fp16: bool,
+    /// Int8 matrix multiply
+    pub i8mm: bool,
 }
 
 impl CpuFeatures {
@@ -24,15 +69,177 @@ impl CpuFeatures {
             features.avx = std::is_x86_feature_detected!("avx");
             features.avx2 = std::is_x86_feature_detected!("avx2");
             features.avx512f = std::is_x86_feature_detected!("avx512f");
+
+            features.fma = std::is_x86_feature_detected!("fma");
+            features.f16c = std::is_x86_feature_detected!("f16c");
+            features.bmi1 = std::is_x86_feature_detected!("bmi1");
+            features.bmi2 = std::is_x86_feature_detected!("bmi2");
+            features.popcnt = std::is_x86_feature_detected!("popcnt");
+            features.avx512bw = std::is_x86_feature_detected!("avx512bw");
+            features.avx512dq = std::is_x86_feature_detected!("avx512dq");
+            features.avx512vl = std::is_x86_feature_detected!("avx512vl");
+            features.avx512vnni = std::is_x86_feature_detected!("avx512vnni");
+            features.avx512bf16 = std::is_x86_feature_detected!("avx512bf16");
+            features.avx512fp16 = std::is_x86_feature_detected!("avx512fp16");
+            features.avxvnni = std::is_x86_feature_detected!("avxvnni");
+
+            let (tile, int8, bf16) = detect_amx();
+            features.amx_tile = tile;
+            features.amx_int8 = int8;
+            features.amx_bf16 = bf16;
         }
 
         #[cfg(target_arch = "aarch64")]
         {
-            // Neon is mandatory on AArch64, but we can check specifically if needed
-            // or just set to true on this platform.
-            features.neon = true; 
+            // The std macros read HWCAP/HWCAP2 via getauxval on Linux.
+            features.neon = std::arch::is_aarch64_feature_detected!("neon");
+            features.sve = std::arch::is_aarch64_feature_detected!("sve");
+            features.sve2 = std::arch::is_aarch64_feature_detected!("sve2");
+            features.dotprod = std::arch::is_aarch64_feature_detected!("dotprod");
+            features.fp16 = std::arch::is_aarch64_feature_detected!("fp16");
+            features.i8mm = std::arch::is_aarch64_feature_detected!("i8mm");
+
+            // Older kernels may not expose every HWCAP bit; fill gaps from cpuinfo.
+            #[cfg(target_os = "linux")]
+            if let Ok(cpuinfo) = std::fs::read_to_string("/proc/cpuinfo") {
+                let parsed = Self::from_cpuinfo(&cpuinfo);
+                features.neon |= parsed.neon;
+                features.sve |= parsed.sve;
+                features.sve2 |= parsed.sve2;
+                features.dotprod |= parsed.dotprod;
+                features.fp16 |= parsed.fp16;
+                features.i8mm |= parsed.i8mm;
+            }
         }
 
         features
     }
+
+    /// Parses the feature list of a Linux `/proc/cpuinfo` dump.
+    ///
+    /// Reads the first `flags` (x86) or `Features` (ARM) line, so it can be
+    /// used on fixtures captured from other machines.
+    pub fn from_cpuinfo(cpuinfo: &str) -> Self {
+        let mut features = CpuFeatures::default();
+        let flags = cpuinfo.lines()
+            .filter_map(|line| line.split_once(':'))
+            .find(|(key, _)| matches!(key.trim(), "flags" | "Features"))
+            .map(|(_, value)| value)
+            .unwrap_or("");
+
+        for flag in flags.split_whitespace() {
+            match flag {
+                "sse2" => features.sse2 = true,
+                "avx" => features.avx = true,
+                "avx2" => features.avx2 = true,
+                "avx512f" => features.avx512f = true,
+                "fma" => features.fma = true,
+                "f16c" => features.f16c = true,
+                "bmi1" => features.bmi1 = true,
+                "bmi2" => features.bmi2 = true,
+                "popcnt" => features.popcnt = true,
+                "avx512bw" => features.avx512bw = true,
+                "avx512dq" => features.avx512dq = true,
+                "avx512vl" => features.avx512vl = true,
+                "avx512_vnni" => features.avx512vnni = true,
+                "avx512_bf16" => features.avx512bf16 = true,
+                "avx512_fp16" => features.avx512fp16 = true,
+                "avx_vnni" => features.avxvnni = true,
+                "amx_tile" => features.amx_tile = true,
+                "amx_int8" => features.amx_int8 = true,
+                "amx_bf16" => features.amx_bf16 = true,
+                "asimd" => features.neon = true,
+                "sve" => features.sve = true,
+                "sve2" => features.sve2 = true,
+                "asimddp" => features.dotprod = true,
+                "fphp" | "asimdhp" => features.fp16 = true,
+                "i8mm" => features.i8mm = true,
+                _ => {}
+            }
+        }
+        features
+    }
+
+    /// Returns the names of all detected features, in declaration order.
+    pub fn enabled(&self) -> Vec<&'static str> {
+        self.flags().into_iter()
+            .filter(|(_, on)| *on)
+            .map(|(name, _)| name)
+            .collect()
+    }
+
+    /// Returns every known feature name paired with its detection result.
+    pub fn flags(&self) -> [(&'static str, bool); 25] {
+        [
+            ("sse2", self.sse2),
+            ("avx", self.avx),
+            ("avx2", self.avx2),
+            ("avx512f", self.avx512f),
+            ("neon", self.neon),
+            ("fma", self.fma),
+            ("f16c", self.f16c),
+            ("bmi1", self.bmi1),
+            ("bmi2", self.bmi2),
+            ("popcnt", self.popcnt),
+            ("avx512bw", self.avx512bw),
+            ("avx512dq", self.avx512dq),
+            ("avx512vl", self.avx512vl),
+            ("avx512vnni", self.avx512vnni),
+            ("avx512bf16", self.avx512bf16),
+            ("avx512fp16", self.avx512fp16),
+            ("avxvnni", self.avxvnni),
+            ("amx_tile", self.amx_tile),
+            ("amx_int8", self.amx_int8),
+            ("amx_bf16", self.amx_bf16),
+            ("sve", self.sve),
+            ("sve2", self.sve2),
+            ("dotprod", self.dotprod),
+            ("fp16", self.fp16),
+            ("i8mm", self.i8mm),
+        ]
+    }
+
+    /// True when 256-bit fused multiply-add kernels can be used.
+    pub fn has_fma_avx2(&self) -> bool {
+        self.avx2 && self.fma
+    }
+
+    /// True for the Skylake-X baseline (F + BW + DQ + VL).
+    pub fn has_avx512_common(&self) -> bool {
+        self.avx512f && self.avx512bw && self.avx512dq && self.avx512vl
+    }
+}
+
+/// Detects AMX support as `(tile, int8, bf16)`.
+///
+/// `is_x86_feature_detected!` does not accept the AMX features on stable, so
+/// this reads CPUID leaf 7 directly and checks that the OS enabled tile state
+/// in XCR0 (bits 17 and 18).
+#[cfg(target_arch = "x86_64")]
+fn detect_amx() -> (bool, bool, bool) {
+    use std::arch::x86_64::{__cpuid, __cpuid_count};
+
+    let max_leaf = __cpuid(0).eax;
+    let osxsave = __cpuid(1).ecx & (1 << 27) != 0;
+    if max_leaf < 7 || !osxsave {
+        return (false, false, false);
+    }
+
+    let edx = __cpuid_count(7, 0).edx;
+    // SAFETY: OSXSAVE (checked above) guarantees XGETBV is available.
+    let xcr0 = unsafe { read_xcr0() };
+    let tile_state = xcr0 & (0b11 << 17) == (0b11 << 17);
+    let tile = tile_state && edx & (1 << 24) != 0;
+    (tile, tile && edx & (1 << 25) != 0, tile && edx & (1 << 22) != 0)
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "xsave")]
+unsafe fn read_xcr0() -> u64 {
+    std::arch::x86_64::_xgetbv(0)
+}
+
+#[cfg(target_arch = "x86")]
+fn detect_amx() -> (bool, bool, bool) {
+    (false, false, false)
 }
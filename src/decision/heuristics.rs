@@ -1,5 +1,6 @@
 use crate::detect::HardwareState;
 use crate::decision::policy::Policy;
+use crate::runtime::governor::HIGH_LOAD_PERCENT;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ExecutionStrategy {
@@ -14,8 +15,10 @@ pub struct Heuristics;
 
 impl Heuristics {
     pub fn decide(state: &HardwareState, policy: Policy) -> ExecutionStrategy {
+        let policy = policy.resolve(&state.context);
+
         // High load protection
-        if state.cpu.usage > 90.0 || policy == Policy::ProtectDevice {
+        if state.cpu.usage > HIGH_LOAD_PERCENT || policy == Policy::ProtectDevice {
             return ExecutionStrategy::Throttled;
         }
 
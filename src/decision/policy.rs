@@ -1,16 +1,26 @@
+use crate::detect::SystemContext;
 use serde::Serialize;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Default)]
 pub enum Policy {
     Performance,
+    #[default]
     Balanced,
     PowerSaving,
     ProtectDevice, // Special mode for thermal/battery issues
     SmartAuto,     // Backward compatibility for legacy v2.x
 }
 
-impl Default for Policy {
-    fn default() -> Self {
-        Policy::Balanced
+impl Policy {
+    /// The policy to apply under `context`.
+    ///
+    /// `Balanced` and `SmartAuto` switch to `PowerSaving` while the system is in
+    /// low-power mode (on battery below the configured threshold). Explicitly
+    /// chosen policies are kept.
+    pub fn resolve(self, context: &SystemContext) -> Policy {
+        match self {
+            Policy::Balanced | Policy::SmartAuto if context.is_low_power_mode => Policy::PowerSaving,
+            other => other,
+        }
     }
 }
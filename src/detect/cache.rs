@@ -0,0 +1,240 @@
+use crate::cpu::arch::{detect_arch, CpuArch};
+use crate::cpu::bits::{detect_bits, Bitness};
+use crate::cpu::features::CpuFeatures;
+use crate::detect::memory::{memory_from, MemoryDetails};
+use crate::detect::system::{detect_context, SystemContext};
+use crate::detect::cgroup::CgroupLimits;
+use crate::detect::cores::CoreTopology;
+use crate::detect::gpu_info::{enumerate_gpus, GpuDetails};
+use crate::detect::numa::NumaTopology;
+use crate::detect::sysfs::SYSFS_ROOT;
+use crate::detect::topology::CacheTopology;
+use crate::runtime::periodic::PeriodicThread;
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use sysinfo::{Components, CpuRefreshKind, MemoryRefreshKind, RefreshKind, System};
+
+/// Default lifetime of dynamic facts when no background sampler is running.
+pub const DEFAULT_DYNAMIC_TTL: Duration = Duration::from_millis(250);
+
+/// Facts that never change for the lifetime of the process.
+#[derive(Debug, Clone, Serialize)]
+pub struct StaticFacts {
+    pub arch: CpuArch,
+    pub bits: Bitness,
+    pub features: CpuFeatures,
+    pub brand: String,
+    pub physical_cores: usize,
+    pub logical_threads: usize,
+    pub caches: CacheTopology,
+    pub numa: NumaTopology,
+    pub cores: CoreTopology,
+    pub cgroup: CgroupLimits,
+    /// DRM devices, primary first.
+    pub gpus: Vec<GpuDetails>,
+    /// Worker threads the process can usefully run: the OS-visible parallelism
+    /// capped by the cgroup CPU quota.
+    pub effective_threads: usize,
+}
+
+/// Facts that drift over time and are refreshed incrementally.
+#[derive(Debug, Clone, Serialize)]
+pub struct DynamicFacts {
+    /// Average CPU usage (%) since the previous refresh.
+    pub cpu_usage: f32,
+    pub frequency_mhz: u64,
+    pub memory: MemoryDetails,
+    pub context: SystemContext,
+    /// Hottest reported sensor, in degrees Celsius.
+    pub temperature_c: Option<f32>,
+    /// Unix timestamp (seconds) of the refresh.
+    pub timestamp: u64,
+    #[serde(skip)]
+    pub captured_at: Instant,
+}
+
+struct Probe {
+    system: System,
+    components: Components,
+}
+
+/// Process-wide hardware detection cache.
+///
+/// Static facts are probed once. Dynamic facts are kept in an `Arc` snapshot
+/// that is either refreshed by a background sampler or lazily once it is older
+/// than the configured TTL. Reading a snapshot is a lock read and an `Arc` clone.
+pub struct HardwareCache {
+    static_facts: OnceLock<Arc<StaticFacts>>,
+    dynamic: RwLock<Option<Arc<DynamicFacts>>>,
+    probe: Mutex<Option<Probe>>,
+    ttl_nanos: AtomicU64,
+    sampler: Mutex<Option<PeriodicThread>>,
+}
+
+static CACHE: OnceLock<HardwareCache> = OnceLock::new();
+
+impl HardwareCache {
+    fn new() -> Self {
+        Self {
+            static_facts: OnceLock::new(),
+            dynamic: RwLock::new(None),
+            probe: Mutex::new(None),
+            ttl_nanos: AtomicU64::new(DEFAULT_DYNAMIC_TTL.as_nanos() as u64),
+            sampler: Mutex::new(None),
+        }
+    }
+
+    pub fn global() -> &'static Self {
+        CACHE.get_or_init(Self::new)
+    }
+
+    /// Returns the static facts, probing them on first use.
+    pub fn static_facts(&self) -> Arc<StaticFacts> {
+        self.static_facts.get_or_init(|| Arc::new(Self::probe_static())).clone()
+    }
+
+    /// Returns the current dynamic snapshot.
+    ///
+    /// Without a background sampler, a snapshot older than the TTL is refreshed
+    /// inline. If another thread is already refreshing, the stale snapshot is
+    /// returned instead of waiting.
+    pub fn dynamic_facts(&self) -> Arc<DynamicFacts> {
+        if let Some(current) = self.current() {
+            if self.is_sampling() || current.captured_at.elapsed() < self.ttl() {
+                return current;
+            }
+            return self.try_refresh().unwrap_or(current);
+        }
+        self.refresh()
+    }
+
+    /// Forces a refresh of the dynamic facts and returns the new snapshot.
+    pub fn refresh(&self) -> Arc<DynamicFacts> {
+        let mut probe = self.probe.lock().unwrap_or_else(|e| e.into_inner());
+        self.refresh_locked(&mut probe)
+    }
+
+    /// Sets how long dynamic facts stay fresh without a background sampler.
+    pub fn set_ttl(&self, ttl: Duration) {
+        self.ttl_nanos.store(ttl.as_nanos() as u64, Ordering::Relaxed);
+    }
+
+    pub fn ttl(&self) -> Duration {
+        Duration::from_nanos(self.ttl_nanos.load(Ordering::Relaxed))
+    }
+
+    /// Marks the dynamic snapshot as stale so the next read refreshes it.
+    pub fn invalidate(&self) {
+        if let Ok(mut lock) = self.dynamic.write() {
+            *lock = None;
+        }
+    }
+
+    /// Starts a background thread refreshing dynamic facts every `interval`.
+    ///
+    /// Replaces any sampler that is already running.
+    pub fn start_sampler(&'static self, interval: Duration) {
+        self.start_sampler_with(interval, |_| {});
+    }
+
+    /// Like [`start_sampler`](Self::start_sampler), also passing every
+    /// refreshed snapshot to `on_refresh`, starting with one taken right away.
+    pub fn start_sampler_with<F>(&'static self, interval: Duration, on_refresh: F)
+    where
+        F: Fn(&DynamicFacts) + Send + 'static,
+    {
+        self.stop_sampler();
+        on_refresh(&self.refresh());
+        let thread = PeriodicThread::spawn("archx-hw-sampler", interval, move || on_refresh(&self.refresh()));
+        if let (Some(thread), Ok(mut lock)) = (thread, self.sampler.lock()) {
+            *lock = Some(thread);
+        }
+    }
+
+    /// Stops the background sampler, if one is running.
+    pub fn stop_sampler(&self) {
+        let sampler = self.sampler.lock().ok().and_then(|mut lock| lock.take());
+        if let Some(sampler) = sampler {
+            sampler.stop();
+        }
+    }
+
+    pub fn is_sampling(&self) -> bool {
+        self.sampler.lock().map(|lock| lock.is_some()).unwrap_or(false)
+    }
+
+    fn current(&self) -> Option<Arc<DynamicFacts>> {
+        self.dynamic.read().ok().and_then(|lock| lock.clone())
+    }
+
+    fn try_refresh(&self) -> Option<Arc<DynamicFacts>> {
+        let mut probe = self.probe.try_lock().ok()?;
+        Some(self.refresh_locked(&mut probe))
+    }
+
+    fn refresh_locked(&self, probe: &mut Option<Probe>) -> Arc<DynamicFacts> {
+        let probe = probe.get_or_insert_with(|| Probe {
+            system: System::new_with_specifics(
+                RefreshKind::nothing()
+                    .with_cpu(CpuRefreshKind::nothing().with_cpu_usage().with_frequency())
+                    .with_memory(MemoryRefreshKind::everything())
+            ),
+            components: Components::new_with_refreshed_list(),
+        });
+
+        // CPU usage is measured since the previous refresh, so no sleep is needed.
+        probe.system.refresh_cpu_specifics(CpuRefreshKind::nothing().with_cpu_usage().with_frequency());
+        probe.system.refresh_memory();
+        probe.components.refresh(false);
+
+        let mut memory = memory_from(&probe.system);
+        memory.apply_limits(&self.static_facts().cgroup);
+
+        let cpus = probe.system.cpus();
+        let facts = Arc::new(DynamicFacts {
+            cpu_usage: probe.system.global_cpu_usage(),
+            frequency_mhz: cpus.first().map(|c| c.frequency()).unwrap_or(0),
+            memory,
+            context: detect_context(),
+            temperature_c: probe.components.iter()
+                .filter_map(|c| c.temperature())
+                .filter(|t| t.is_finite())
+                .reduce(f32::max),
+            timestamp: SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0),
+            captured_at: Instant::now(),
+        });
+
+        if let Ok(mut lock) = self.dynamic.write() {
+            *lock = Some(facts.clone());
+        }
+        facts
+    }
+
+    fn probe_static() -> StaticFacts {
+        let mut sys = System::new_with_specifics(
+            RefreshKind::nothing().with_cpu(CpuRefreshKind::nothing())
+        );
+        sys.refresh_cpu_specifics(CpuRefreshKind::nothing());
+        let cpus = sys.cpus();
+        let cgroup = CgroupLimits::detect();
+
+        StaticFacts {
+            arch: detect_arch(),
+            bits: detect_bits(),
+            features: CpuFeatures::detect(),
+            brand: cpus.first().map(|c| c.brand().to_string()).unwrap_or_else(|| "Unknown".to_string()),
+            physical_cores: System::physical_core_count().unwrap_or(0),
+            logical_threads: cpus.len(),
+            caches: CacheTopology::detect(),
+            numa: NumaTopology::detect(),
+            cores: CoreTopology::detect(),
+            gpus: enumerate_gpus(SYSFS_ROOT),
+            effective_threads: cgroup.effective_cpus(
+                std::thread::available_parallelism().map(|n| n.get()).unwrap_or(cpus.len().max(1))
+            ),
+            cgroup,
+        }
+    }
+}
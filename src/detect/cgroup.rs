@@ -0,0 +1,147 @@
+use crate::detect::sysfs::{read_parsed, read_trimmed};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// v1 memory limits at or above this are the kernel's "unlimited" sentinel.
+const V1_UNLIMITED: u64 = 1 << 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CgroupVersion {
+    V1,
+    V2,
+}
+
+/// CPU and memory limits imposed on this process by its cgroup.
+///
+/// `None` means unlimited (or not running under cgroups). Limits set on
+/// ancestor cgroups are honoured: the tightest one wins.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct CgroupLimits {
+    pub version: Option<CgroupVersion>,
+    /// CPU bandwidth quota in cores (`quota / period`), e.g. `2.5`.
+    pub cpu_quota: Option<f64>,
+    pub memory_limit_bytes: Option<u64>,
+    /// Directory holding the memory controller files, used to read current usage.
+    #[serde(skip)]
+    memory_dir: Option<PathBuf>,
+}
+
+impl CgroupLimits {
+    /// Reads the limits of the current process from the live system.
+    pub fn detect() -> Self {
+        Self::from_root("/")
+    }
+
+    /// Reads `<root>/proc/self/cgroup` and the controllers under `<root>/sys/fs/cgroup`.
+    ///
+    /// The cgroup path is resolved under the mount and, when that directory does
+    /// not exist (cgroup namespaces), the mount root is used instead.
+    pub fn from_root(root: impl AsRef<Path>) -> Self {
+        let root = root.as_ref();
+        let Some(membership) = read_trimmed(root.join("proc/self/cgroup")) else {
+            return Self::default();
+        };
+        let mount = root.join("sys/fs/cgroup");
+
+        let mut v1_cpu = None;
+        let mut v1_memory = None;
+        let mut v2 = None;
+        for line in membership.lines() {
+            let mut parts = line.splitn(3, ':');
+            let (Some(id), Some(controllers), Some(path)) = (parts.next(), parts.next(), parts.next()) else { continue };
+            let path = path.trim_start_matches('/');
+            if id == "0" && controllers.is_empty() {
+                v2 = Some(path.to_string());
+            }
+            for controller in controllers.split(',') {
+                match controller {
+                    "cpu" => v1_cpu = Some((controllers.to_string(), path.to_string())),
+                    "memory" => v1_memory = Some((controllers.to_string(), path.to_string())),
+                    _ => {}
+                }
+            }
+        }
+
+        if v1_cpu.is_some() || v1_memory.is_some() {
+            let cpu_dirs = v1_cpu.map(|(c, p)| v1_dirs(&mount, &c, "cpu", &p)).unwrap_or_default();
+            let memory_dirs = v1_memory.map(|(c, p)| v1_dirs(&mount, &c, "memory", &p)).unwrap_or_default();
+            return Self {
+                version: Some(CgroupVersion::V1),
+                cpu_quota: min_over(&cpu_dirs, |dir| {
+                    let quota: i64 = read_parsed(dir.join("cpu.cfs_quota_us"))?;
+                    let period: i64 = read_parsed(dir.join("cpu.cfs_period_us"))?;
+                    (quota > 0 && period > 0).then(|| quota as f64 / period as f64)
+                }),
+                memory_limit_bytes: min_over(&memory_dirs, |dir| {
+                    read_parsed::<u64>(dir.join("memory.limit_in_bytes")).filter(|&l| l < V1_UNLIMITED)
+                }),
+                memory_dir: memory_dirs.into_iter().next(),
+            };
+        }
+
+        match v2 {
+            Some(path) if mount.join("cgroup.controllers").exists() => {
+                let dirs = ancestors(&mount, &path);
+                Self {
+                    version: Some(CgroupVersion::V2),
+                    cpu_quota: min_over(&dirs, |dir| {
+                        let max = read_trimmed(dir.join("cpu.max"))?;
+                        let mut fields = max.split_whitespace();
+                        let quota: f64 = fields.next()?.parse().ok()?;
+                        let period: f64 = fields.next().unwrap_or("100000").parse().ok()?;
+                        (quota > 0.0 && period > 0.0).then_some(quota / period)
+                    }),
+                    memory_limit_bytes: min_over(&dirs, |dir| read_parsed(dir.join("memory.max"))),
+                    memory_dir: dirs.into_iter().next(),
+                }
+            }
+            _ => Self::default(),
+        }
+    }
+
+    /// Logical CPUs usable under the quota: `ceil(quota)` capped at `host_cpus`.
+    pub fn effective_cpus(&self, host_cpus: usize) -> usize {
+        match self.cpu_quota {
+            Some(quota) => (quota.ceil() as usize).clamp(1, host_cpus.max(1)),
+            None => host_cpus.max(1),
+        }
+    }
+
+    /// Memory currently charged to the cgroup, in bytes.
+    pub fn memory_usage_bytes(&self) -> Option<u64> {
+        let dir = self.memory_dir.as_ref()?;
+        match self.version? {
+            CgroupVersion::V2 => read_parsed(dir.join("memory.current")),
+            CgroupVersion::V1 => read_parsed(dir.join("memory.usage_in_bytes")),
+        }
+    }
+}
+
+/// Candidate directories for a v1 controller mounted as e.g. `cpu,cpuacct` or `cpu`.
+fn v1_dirs(mount: &Path, controllers: &str, name: &str, path: &str) -> Vec<PathBuf> {
+    [controllers, name].iter()
+        .map(|dir| mount.join(dir))
+        .find(|dir| dir.is_dir())
+        .map(|base| ancestors(&base, path))
+        .unwrap_or_default()
+}
+
+/// `base/path` and each of its ancestors up to `base`, innermost first.
+///
+/// If `base/path` does not exist, only `base` is returned.
+fn ancestors(base: &Path, path: &str) -> Vec<PathBuf> {
+    let leaf = base.join(path);
+    if path.is_empty() || !leaf.is_dir() {
+        return vec![base.to_path_buf()];
+    }
+    leaf.ancestors().take_while(|dir| dir.starts_with(base)).map(Path::to_path_buf).collect()
+}
+
+fn min_over<T: PartialOrd>(dirs: &[PathBuf], read: impl Fn(&Path) -> Option<T>) -> Option<T> {
+    dirs.iter()
+        .filter_map(|dir| read(dir))
+        .fold(None, |min, value| match min {
+            Some(m) if m <= value => Some(m),
+            _ => Some(value),
+        })
+}
@@ -0,0 +1,144 @@
+use crate::detect::sysfs::{self, numbered_entries, parse_cpu_list, read_parsed, read_trimmed};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Capacity of the fastest core, following the kernel's `cpu_capacity` scale.
+pub const MAX_CAPACITY: u32 = 1024;
+
+/// Share of the fastest core's capacity (percent) below which a core counts
+/// as an efficiency core. Preferred-core boost on homogeneous CPUs (AMD
+/// preferred cores, Intel Turbo Boost Max 3.0) differs by a few percent;
+/// E-cores and ARM LITTLE cores are well below this.
+pub const EFFICIENCY_CAPACITY_PERCENT: u32 = 80;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CoreType {
+    /// Big / performance core (Intel P-core, ARM "big").
+    Performance,
+    /// Little / efficiency core (Intel E-core, ARM "LITTLE").
+    Efficiency,
+}
+
+/// One logical CPU and the kind of core it runs on.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CoreInfo {
+    pub cpu: usize,
+    pub core_type: CoreType,
+    /// Relative throughput, `MAX_CAPACITY` for the fastest cores.
+    pub capacity: u32,
+    pub core_id: Option<usize>,
+    pub package_id: Option<usize>,
+    pub max_freq_khz: Option<u64>,
+}
+
+/// Per-CPU core types and capacities.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct CoreTopology {
+    pub cores: Vec<CoreInfo>,
+}
+
+impl CoreTopology {
+    /// Reads the live sysfs tree, or assumes `available_parallelism` identical cores.
+    pub fn detect() -> Self {
+        Self::from_sysfs(sysfs::SYSFS_ROOT).unwrap_or_else(|| {
+            Self::homogeneous(std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1))
+        })
+    }
+
+    /// Classifies the CPUs under `<root>/devices/system/cpu`.
+    ///
+    /// Sources, in order of preference:
+    /// 1. Intel hybrid PMU lists `<root>/devices/cpu_core/cpus` and `cpu_atom/cpus`.
+    /// 2. Per-CPU `cpu_capacity` (ARM big.LITTLE and recent x86 kernels).
+    /// 3. `cpufreq/cpuinfo_max_freq`, scaled against the fastest CPU.
+    ///
+    /// Without the Intel lists, CPUs below [`EFFICIENCY_CAPACITY_PERCENT`] of
+    /// the highest capacity are efficiency cores. Returns `None` if no CPUs
+    /// are listed.
+    pub fn from_sysfs(root: impl AsRef<Path>) -> Option<Self> {
+        let root = root.as_ref();
+        let cpus = numbered_entries(root.join("devices/system/cpu"), "cpu");
+        if cpus.is_empty() {
+            return None;
+        }
+
+        let read_list = |name: &str| read_trimmed(root.join("devices").join(name).join("cpus")).map(|l| parse_cpu_list(&l));
+        let atom_cpus = read_list("cpu_atom").filter(|_| read_list("cpu_core").is_some());
+
+        // A missing `cpu_capacity` is recorded as 0 and derived from frequency below.
+        let mut cores: Vec<CoreInfo> = cpus.iter()
+            .map(|(cpu, path)| CoreInfo {
+                cpu: *cpu,
+                core_type: CoreType::Performance,
+                capacity: read_parsed(path.join("cpu_capacity")).unwrap_or(0),
+                core_id: read_parsed(path.join("topology/core_id")),
+                package_id: read_parsed(path.join("topology/physical_package_id")),
+                max_freq_khz: read_parsed(path.join("cpufreq/cpuinfo_max_freq")),
+            })
+            .collect();
+
+        let max_freq = cores.iter().filter_map(|c| c.max_freq_khz).max().filter(|&f| f > 0);
+        for core in cores.iter_mut().filter(|c| c.capacity == 0) {
+            core.capacity = match (core.max_freq_khz, max_freq) {
+                (Some(freq), Some(max)) => (freq * MAX_CAPACITY as u64 / max) as u32,
+                _ => MAX_CAPACITY,
+            };
+        }
+
+        let top = cores.iter().map(|c| c.capacity).max().unwrap_or(MAX_CAPACITY);
+        for core in &mut cores {
+            let efficient = match &atom_cpus {
+                Some(atoms) => atoms.contains(&core.cpu),
+                None => (core.capacity as u64) * 100 < top as u64 * EFFICIENCY_CAPACITY_PERCENT as u64,
+            };
+            if efficient {
+                core.core_type = CoreType::Efficiency;
+            }
+        }
+        Some(Self { cores })
+    }
+
+    /// `cpus` identical performance cores.
+    pub fn homogeneous(cpus: usize) -> Self {
+        Self {
+            cores: (0..cpus).map(|cpu| CoreInfo {
+                cpu,
+                core_type: CoreType::Performance,
+                capacity: MAX_CAPACITY,
+                core_id: None,
+                package_id: None,
+                max_freq_khz: None,
+            }).collect(),
+        }
+    }
+
+    /// True when both performance and efficiency cores are present.
+    pub fn is_hybrid(&self) -> bool {
+        self.cores.iter().any(|c| c.core_type == CoreType::Efficiency)
+            && self.cores.iter().any(|c| c.core_type == CoreType::Performance)
+    }
+
+    pub fn performance_cpus(&self) -> Vec<usize> {
+        self.cpus_of(CoreType::Performance)
+    }
+
+    pub fn efficiency_cpus(&self) -> Vec<usize> {
+        self.cpus_of(CoreType::Efficiency)
+    }
+
+    /// Capacity of `cpu`, or `MAX_CAPACITY` if unknown.
+    pub fn capacity_of(&self, cpu: usize) -> u32 {
+        self.cores.iter().find(|c| c.cpu == cpu).map(|c| c.capacity).unwrap_or(MAX_CAPACITY)
+    }
+
+    /// CPUs ordered fastest first, then by CPU number.
+    pub fn by_capacity(&self) -> Vec<usize> {
+        let mut cores: Vec<&CoreInfo> = self.cores.iter().collect();
+        cores.sort_by_key(|c| (std::cmp::Reverse(c.capacity), c.cpu));
+        cores.into_iter().map(|c| c.cpu).collect()
+    }
+
+    fn cpus_of(&self, core_type: CoreType) -> Vec<usize> {
+        self.cores.iter().filter(|c| c.core_type == core_type).map(|c| c.cpu).collect()
+    }
+}
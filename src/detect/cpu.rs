@@ -1,5 +1,5 @@
-use sysinfo::{System, CpuRefreshKind, RefreshKind};
-use crate::cpu::arch::{detect_arch, CpuArch};
+use crate::cpu::arch::CpuArch;
+use crate::detect::cache::{DynamicFacts, HardwareCache, StaticFacts};
 use serde::Serialize;
 
 #[derive(Debug, Clone, Serialize)]
@@ -7,38 +7,35 @@ pub struct CpuDetails {
     pub arch: CpuArch,
     pub physical_cores: usize,
     pub logical_threads: usize,
+    /// Logical CPUs on performance cores (all of them on non-hybrid CPUs).
+    pub performance_threads: usize,
+    /// Logical CPUs on efficiency cores.
+    pub efficiency_threads: usize,
     pub brand: String,
     pub frequency_mhz: u64,
     pub usage: f32,
+    pub temperature_c: Option<f32>,
 }
 
+/// Returns CPU details from the hardware cache.
+///
+/// Topology and brand are probed once per process; usage, frequency and
+/// temperature come from the current dynamic snapshot.
 pub fn detect_cpu() -> CpuDetails {
-    let mut sys = System::new_with_specifics(
-        RefreshKind::nothing().with_cpu(CpuRefreshKind::everything())
-    );
-    sys.refresh_cpu_all();
-    std::thread::sleep(std::time::Duration::from_millis(100));
-    sys.refresh_cpu_all();
-
-    let cpus = sys.cpus();
-    let brand = if !cpus.is_empty() {
-        cpus[0].brand().to_string()
-    } else {
-        "Unknown".to_string()
-    };
-
-    let usage = if !cpus.is_empty() {
-        cpus.iter().map(|c| c.cpu_usage()).sum::<f32>() / cpus.len() as f32
-    } else {
-        0.0
-    };
+    let cache = HardwareCache::global();
+    cpu_details(&cache.static_facts(), &cache.dynamic_facts())
+}
 
+pub(crate) fn cpu_details(statics: &StaticFacts, dynamic: &DynamicFacts) -> CpuDetails {
     CpuDetails {
-        arch: detect_arch(),
-        physical_cores: System::physical_core_count().unwrap_or(0),
-        logical_threads: cpus.len(),
-        brand,
-        frequency_mhz: if !cpus.is_empty() { cpus[0].frequency() } else { 0 },
-        usage,
+        arch: statics.arch,
+        physical_cores: statics.physical_cores,
+        logical_threads: statics.logical_threads,
+        performance_threads: statics.cores.performance_cpus().len(),
+        efficiency_threads: statics.cores.efficiency_cpus().len(),
+        brand: statics.brand.clone(),
+        frequency_mhz: dynamic.frequency_mhz,
+        usage: dynamic.cpu_usage,
+        temperature_c: dynamic.temperature_c,
     }
 }
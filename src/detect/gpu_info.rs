@@ -1,44 +1,172 @@
+use crate::detect::sysfs::{numbered_entries, read_hex, read_parsed, read_trimmed};
+use crate::detect::HardwareCache;
 use serde::Serialize;
+use std::path::Path;
 
-#[derive(Debug, Clone, Serialize)]
+/// PCI vendor ID of Intel, whose integrated GPUs sit on bus 0.
+const INTEL: u16 = 0x8086;
+
+/// GTT (system memory the GPU can map) at least this many times the VRAM
+/// marks a carve-out rather than dedicated memory, as on AMD APUs.
+const APU_GTT_RATIO: u64 = 8;
+
+/// DRM drivers that expose a firmware framebuffer or a virtual device, not a GPU.
+const NON_GPU_DRIVERS: &[&str] = &["simpledrm", "efidrm", "vesadrm", "ofdrm", "vkms"];
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct GpuDetails {
     pub name: String,
     pub vendor: String,
     pub is_integrated: bool,
     pub shared_memory: bool,
+    /// PCI vendor ID, e.g. `0x10de` for NVIDIA.
+    pub vendor_id: Option<u16>,
+    pub device_id: Option<u16>,
+    /// Kernel driver bound to the device (`amdgpu`, `i915`, `nvidia`, ...).
+    pub driver: Option<String>,
+    /// Dedicated video memory, where the driver exposes it.
+    pub vram_bytes: Option<u64>,
+    /// PCI address such as `0000:01:00.0`.
+    pub pci_slot: Option<String>,
+    /// The firmware used this device for the boot console.
+    pub boot_vga: bool,
 }
 
-pub fn detect_gpu(cpu_brand: Option<&str>) -> Option<GpuDetails> {
-    // 1. Try to find a registered/active backend
-    if let Some(name) = crate::gpu::get_active_backend_name() {
-        return Some(GpuDetails {
-            name: name.clone(),
-            vendor: "Detected API".to_string(),
-            is_integrated: false, // Could be determined by API specifics
-            shared_memory: name == "OpenGL" || name == "OpenCL",
-        });
+/// Every GPU of the host, primary first.
+///
+/// Devices come from `/sys/class/drm`. When none are visible (non-Linux hosts,
+/// sandboxes) but a compute backend is registered, the backend is reported instead.
+pub fn detect_gpus() -> Vec<GpuDetails> {
+    let gpus = HardwareCache::global().static_facts().gpus.clone();
+    if !gpus.is_empty() {
+        return gpus;
     }
+    backend_gpu().into_iter().collect()
+}
+
+/// The primary GPU, see [`detect_gpus`].
+pub fn detect_gpu() -> Option<GpuDetails> {
+    detect_gpus().into_iter().next()
+}
+
+/// Enumerates the DRM cards under `<root>/class/drm`.
+///
+/// Only display controllers (PCI class `0x03`) and platform GPUs are kept.
+/// A device counts as integrated on evidence from the device itself:
+/// - it is not on PCI at all (SoC GPUs);
+/// - it is an Intel GPU on bus 0 (`00:02.0`; Intel discrete cards sit behind a bridge);
+/// - its driver reports no dedicated VRAM, or VRAM that is small next to
+///   the GTT size (AMD APUs, which can sit on any bus, e.g. `c1:00.0`).
+///
+/// The result is ordered by preference: discrete devices with the most VRAM
+/// first, then the remaining devices in card order.
+pub fn enumerate_gpus(root: impl AsRef<Path>) -> Vec<GpuDetails> {
+    let mut gpus: Vec<GpuDetails> = numbered_entries(root.as_ref().join("class/drm"), "card")
+        .into_iter()
+        .filter_map(|(_, card)| read_card(&card.join("device")))
+        .collect();
+
+    gpus.sort_by_key(|g| (g.is_integrated, std::cmp::Reverse(g.vram_bytes.unwrap_or(0))));
+    gpus
+}
+
+/// Reads one `card*/device` directory.
+fn read_card(device: &Path) -> Option<GpuDetails> {
+    let uevent = read_trimmed(device.join("uevent")).unwrap_or_default();
+    let uevent_value = |key: &str| {
+        uevent.lines().find_map(|l| l.strip_prefix(key)?.strip_prefix('=')).map(str::to_string)
+    };
 
-    // 2. Fallback to predictive iGPU detection based on CPU brand
-    if let Some(brand) = cpu_brand {
-        let brand_lower = brand.to_lowercase();
-        if brand_lower.contains("radeon") || brand_lower.contains("vega") || brand_lower.contains("graphics") {
-            return Some(GpuDetails {
-                name: "Integrated Radeon Graphics".to_string(),
-                vendor: "AMD".to_string(),
-                is_integrated: true,
-                shared_memory: true,
-            });
-        }
-        if brand_lower.contains("intel") || brand_lower.contains("iris") || brand_lower.contains("uhd") {
-            return Some(GpuDetails {
-                name: "Integrated Intel Graphics".to_string(),
-                vendor: "Intel".to_string(),
-                is_integrated: true,
-                shared_memory: true,
-            });
-        }
+    let driver = uevent_value("DRIVER").or_else(|| link_name(&device.join("driver")));
+    if driver.as_deref().is_some_and(|d| NON_GPU_DRIVERS.contains(&d)) {
+        return None;
+    }
+    let class = read_hex(device.join("class"));
+    if class.is_some_and(|c| c >> 16 != 0x03) {
+        return None;
     }
 
-    None
+    let vendor_id = read_hex(device.join("vendor")).map(|v| v as u16);
+    let device_id = read_hex(device.join("device")).map(|v| v as u16);
+    let vendor = vendor_id.map(vendor_name).or_else(|| driver.clone()).unwrap_or_else(|| "Unknown".to_string());
+    let name = read_trimmed(device.join("product_name"))
+        .filter(|n| !n.is_empty())
+        .unwrap_or_else(|| match (vendor_id, device_id) {
+            (Some(v), Some(d)) => format!("{} GPU [{:04x}:{:04x}]", vendor, v, d),
+            _ => format!("{} GPU", vendor),
+        });
+
+    let pci_slot = uevent_value("PCI_SLOT_NAME").or_else(|| link_name(device).filter(|n| n.contains(':')));
+    let vram_total = read_parsed::<u64>(device.join("mem_info_vram_total"));
+    let gtt_total = read_parsed::<u64>(device.join("mem_info_gtt_total"));
+
+    let intel_on_root_bus = vendor_id == Some(INTEL) && pci_slot.as_deref().and_then(pci_bus) == Some(0);
+    let carve_out = match (vram_total, gtt_total) {
+        (Some(0), _) => true,
+        (Some(vram), Some(gtt)) => vram.saturating_mul(APU_GTT_RATIO) <= gtt,
+        _ => false,
+    };
+    let is_integrated = class.is_none() || intel_on_root_bus || carve_out;
+
+    Some(GpuDetails {
+        name,
+        vendor,
+        is_integrated,
+        shared_memory: is_integrated,
+        vendor_id,
+        device_id,
+        driver,
+        vram_bytes: vram_total.filter(|&v| v > 0),
+        pci_slot,
+        boot_vga: read_parsed::<u8>(device.join("boot_vga")).is_some_and(|v| v == 1),
+    })
+}
+
+/// Vendor name for a PCI vendor ID.
+pub fn vendor_name(vendor_id: u16) -> String {
+    match vendor_id {
+        0x10de => "NVIDIA",
+        0x1002 | 0x1022 => "AMD",
+        0x8086 => "Intel",
+        0x13b5 => "ARM",
+        0x5143 => "Qualcomm",
+        0x1af4 => "Red Hat (virtio)",
+        0x15ad => "VMware",
+        0x1414 => "Microsoft",
+        0x1234 => "QEMU",
+        0x106b => "Apple",
+        0x1a03 => "ASPEED",
+        0x102b => "Matrox",
+        _ => return format!("Vendor {:04x}", vendor_id),
+    }.to_string()
+}
+
+/// Bus number of a PCI address `dddd:bb:ss.f`.
+fn pci_bus(slot: &str) -> Option<u8> {
+    let mut parts = slot.split(':');
+    let (_domain, bus) = (parts.next()?, parts.next()?);
+    u8::from_str_radix(bus, 16).ok()
+}
+
+/// File name of the resolved `path` (symlinks such as `device/driver`).
+fn link_name(path: &Path) -> Option<String> {
+    let target = std::fs::canonicalize(path).ok()?;
+    target.file_name().map(|n| n.to_string_lossy().into_owned())
+}
+
+/// A placeholder for the registered compute backend when no device is enumerated.
+fn backend_gpu() -> Option<GpuDetails> {
+    let name = crate::gpu::get_active_backend_name()?;
+    Some(GpuDetails {
+        shared_memory: name == "OpenGL" || name == "OpenCL",
+        name,
+        vendor: "Detected API".to_string(),
+        is_integrated: false,
+        vendor_id: None,
+        device_id: None,
+        driver: None,
+        vram_bytes: None,
+        pci_slot: None,
+        boot_vga: false,
+    })
 }
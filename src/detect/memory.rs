@@ -1,4 +1,5 @@
 use sysinfo::{System, MemoryRefreshKind, RefreshKind};
+use crate::detect::cgroup::CgroupLimits;
 use serde::Serialize;
 
 #[derive(Debug, Clone, Serialize)]
@@ -16,10 +17,18 @@ pub fn detect_memory() -> MemoryDetails {
         RefreshKind::nothing().with_memory(MemoryRefreshKind::everything())
     );
     sys.refresh_memory();
+    let mut memory = memory_from(&sys);
+    memory.apply_limits(&crate::detect::HardwareCache::global().static_facts().cgroup);
+    memory
+}
 
-    let total = sys.total_memory();
-    let available = sys.available_memory();
-    let used = sys.used_memory();
+/// Builds `MemoryDetails` from an already refreshed `System`.
+///
+/// `sysinfo` reports bytes; the fields of `MemoryDetails` are KiB.
+pub(crate) fn memory_from(sys: &System) -> MemoryDetails {
+    let total = sys.total_memory() / 1024;
+    let available = sys.available_memory() / 1024;
+    let used = sys.used_memory() / 1024;
     
     let pressure = if total > 0 {
         used as f32 / total as f32
@@ -31,8 +40,31 @@ pub fn detect_memory() -> MemoryDetails {
         total_kb: total,
         available_kb: available,
         used_kb: used,
-        swap_total_kb: sys.total_swap(),
-        swap_free_kb: sys.free_swap(),
+        swap_total_kb: sys.total_swap() / 1024,
+        swap_free_kb: sys.free_swap() / 1024,
         pressure_estimate: pressure,
     }
 }
+
+impl MemoryDetails {
+    /// Caps the host figures at the cgroup memory limit, if there is one.
+    ///
+    /// Usage is taken from the cgroup's own accounting when available, so
+    /// `available_kb` is what this container can still allocate.
+    pub fn apply_limits(&mut self, limits: &CgroupLimits) {
+        let Some(limit) = limits.memory_limit_bytes else { return };
+        let limit_kb = limit / 1024;
+
+        self.total_kb = self.total_kb.min(limit_kb);
+        self.used_kb = limits.memory_usage_bytes()
+            .map(|bytes| bytes / 1024)
+            .unwrap_or(self.used_kb)
+            .min(self.total_kb);
+        self.available_kb = self.available_kb.min(self.total_kb - self.used_kb);
+        self.pressure_estimate = if self.total_kb > 0 {
+            self.used_kb as f32 / self.total_kb as f32
+        } else {
+            0.0
+        };
+    }
+}
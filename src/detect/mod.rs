@@ -2,38 +2,68 @@ pub mod cpu;
 pub mod memory;
 pub mod gpu_info;
 pub mod system;
+pub mod cache;
+pub mod sysfs;
+pub mod topology;
+pub mod numa;
+pub mod cores;
+pub mod cgroup;
+pub mod power;
+pub mod rapl;
 
-use cpu::{detect_cpu, CpuDetails};
-use memory::{detect_memory, MemoryDetails};
-use gpu_info::{detect_gpu, GpuDetails};
-use system::{detect_context, SystemContext};
+use cpu::{cpu_details, CpuDetails};
+use memory::MemoryDetails;
+use gpu_info::detect_gpus;
 use serde::Serialize;
 
+pub use cache::{HardwareCache, StaticFacts, DynamicFacts};
+pub use topology::{CacheTopology, CacheInfo, CacheKind, TopologySource};
+pub use numa::{NumaNode, NumaTopology};
+pub use cores::{CoreInfo, CoreTopology, CoreType};
+pub use cgroup::{CgroupLimits, CgroupVersion};
+pub use gpu_info::GpuDetails;
+pub use power::{BatteryStatus, PowerConfig, PowerSupply, PowerSupplyKind};
+pub use system::{PowerSource, SystemContext};
+pub use rapl::{RaplDomain, RaplZone};
+
 #[derive(Debug, Clone, Serialize)]
 pub struct HardwareState {
     pub cpu: CpuDetails,
     pub memory: MemoryDetails,
+    /// The primary GPU, `gpus[0]`.
     pub gpu: Option<GpuDetails>,
+    pub gpus: Vec<GpuDetails>,
     pub context: SystemContext,
     pub timestamp: u64,
 }
 
 impl HardwareState {
+    /// Builds a state from the hardware cache.
+    ///
+    /// This no longer probes the hardware on every call: static facts are
+    /// detected once and dynamic facts are at most `HardwareCache::ttl()` old.
     pub fn capture() -> Self {
-        use std::time::{SystemTime, UNIX_EPOCH};
-        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
-        
-        let cpu = detect_cpu();
-        let memory = detect_memory();
-        let gpu = detect_gpu(Some(&cpu.brand));
-        let context = detect_context();
+        let cache = HardwareCache::global();
+        let statics = cache.static_facts();
+        let dynamic = cache.dynamic_facts();
+
+        let cpu = cpu_details(&statics, &dynamic);
+        let gpus = detect_gpus();
 
         Self {
             cpu,
-            memory,
-            gpu,
-            context,
-            timestamp: now,
+            memory: dynamic.memory.clone(),
+            gpu: gpus.first().cloned(),
+            gpus,
+            context: dynamic.context.clone(),
+            timestamp: dynamic.timestamp,
         }
     }
 }
+
+/// Number of worker threads ArchX should use by default.
+///
+/// Honours cgroup CPU quotas, so a pod limited to 4 CPUs on a 64-core host gets 4.
+pub fn effective_threads() -> usize {
+    HardwareCache::global().static_facts().effective_threads
+}
@@ -0,0 +1,92 @@
+use crate::detect::sysfs::{self, numbered_entries, parse_cpu_list, read_trimmed};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// One NUMA node: the CPUs attached to it and its local memory.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NumaNode {
+    pub id: usize,
+    pub cpus: Vec<usize>,
+    pub memory_bytes: Option<u64>,
+    /// Relative access cost to every node, indexed like `NumaTopology::nodes` (10 = local).
+    pub distances: Vec<u32>,
+}
+
+/// NUMA layout of the host.
+///
+/// Built from sysfs by [`NumaTopology::detect`], or constructed directly to
+/// exercise multi-node code paths on single-node machines.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NumaTopology {
+    pub nodes: Vec<NumaNode>,
+}
+
+impl NumaTopology {
+    /// Reads `/sys/devices/system/node`, or reports one node with every CPU.
+    pub fn detect() -> Self {
+        Self::from_sysfs(sysfs::SYSFS_ROOT).unwrap_or_else(|| {
+            Self::single_node(std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1))
+        })
+    }
+
+    /// Reads `<root>/devices/system/node/node*`.
+    ///
+    /// Returns `None` if the tree does not exist (non-Linux or no NUMA support).
+    pub fn from_sysfs(root: impl AsRef<Path>) -> Option<Self> {
+        let nodes: Vec<NumaNode> = numbered_entries(root.as_ref().join("devices/system/node"), "node")
+            .into_iter()
+            .map(|(id, path)| NumaNode {
+                id,
+                cpus: read_trimmed(path.join("cpulist")).map(|l| parse_cpu_list(&l)).unwrap_or_default(),
+                memory_bytes: read_trimmed(path.join("meminfo")).and_then(|m| parse_mem_total(&m)),
+                distances: read_trimmed(path.join("distance"))
+                    .map(|d| d.split_whitespace().filter_map(|v| v.parse().ok()).collect())
+                    .unwrap_or_default(),
+            })
+            .collect();
+
+        (!nodes.is_empty()).then_some(Self { nodes })
+    }
+
+    /// A single node owning CPUs `0..cpus`.
+    pub fn single_node(cpus: usize) -> Self {
+        Self::uniform(1, cpus)
+    }
+
+    /// `nodes` nodes with `cpus_per_node` consecutive CPUs each.
+    pub fn uniform(nodes: usize, cpus_per_node: usize) -> Self {
+        let nodes = nodes.max(1);
+        Self {
+            nodes: (0..nodes)
+                .map(|id| NumaNode {
+                    id,
+                    cpus: (id * cpus_per_node..(id + 1) * cpus_per_node).collect(),
+                    memory_bytes: None,
+                    distances: (0..nodes).map(|other| if other == id { 10 } else { 20 }).collect(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Nodes that have CPUs and can therefore run workers.
+    pub fn compute_nodes(&self) -> impl Iterator<Item = &NumaNode> {
+        self.nodes.iter().filter(|n| !n.cpus.is_empty())
+    }
+
+    /// True when more than one node has CPUs.
+    pub fn is_numa(&self) -> bool {
+        self.compute_nodes().count() > 1
+    }
+
+    /// Returns the id of the node owning `cpu`.
+    pub fn node_of_cpu(&self, cpu: usize) -> Option<usize> {
+        self.nodes.iter().find(|n| n.cpus.contains(&cpu)).map(|n| n.id)
+    }
+}
+
+/// Extracts `MemTotal` from a per-node `meminfo` file (`Node 0 MemTotal: 1234 kB`).
+fn parse_mem_total(meminfo: &str) -> Option<u64> {
+    let line = meminfo.lines().find(|l| l.contains("MemTotal:"))?;
+    let kb: u64 = line.split_once("MemTotal:")?.1.split_whitespace().next()?.parse().ok()?;
+    Some(kb * 1024)
+}
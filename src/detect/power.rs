@@ -0,0 +1,150 @@
+use crate::detect::sysfs::{self, read_parsed, read_trimmed};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+/// Battery percentage below which battery-powered systems enter low-power mode.
+pub const DEFAULT_LOW_BATTERY_THRESHOLD: f32 = 20.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PowerSupplyKind {
+    Mains,
+    Battery,
+    Usb,
+    Ups,
+    Other,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BatteryStatus {
+    Charging,
+    Discharging,
+    Full,
+    NotCharging,
+    Unknown,
+}
+
+/// One entry of `/sys/class/power_supply`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PowerSupply {
+    pub name: String,
+    pub kind: PowerSupplyKind,
+    /// Whether an external supply is connected (`online`).
+    pub online: Option<bool>,
+    /// Charge level in percent (`capacity`).
+    pub capacity: Option<f32>,
+    pub status: Option<BatteryStatus>,
+}
+
+impl PowerSupply {
+    fn is_external(&self) -> bool {
+        matches!(self.kind, PowerSupplyKind::Mains | PowerSupplyKind::Usb | PowerSupplyKind::Ups)
+    }
+}
+
+/// Where power supplies are read from and when to enter low-power mode.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PowerConfig {
+    /// Root of the sysfs tree; supplies are read from `<root>/class/power_supply`.
+    pub sysfs_root: PathBuf,
+    /// Battery percentage below which `SystemContext::is_low_power_mode` is set.
+    pub low_battery_threshold: f32,
+}
+
+impl Default for PowerConfig {
+    fn default() -> Self {
+        Self {
+            sysfs_root: PathBuf::from(sysfs::SYSFS_ROOT),
+            low_battery_threshold: DEFAULT_LOW_BATTERY_THRESHOLD,
+        }
+    }
+}
+
+static CONFIG: RwLock<Option<PowerConfig>> = RwLock::new(None);
+
+impl PowerConfig {
+    /// The configuration used by `detect_context`.
+    pub fn global() -> Self {
+        CONFIG.read().ok().and_then(|c| c.clone()).unwrap_or_default()
+    }
+
+    /// Replaces the global configuration and invalidates cached dynamic facts.
+    pub fn set_global(config: PowerConfig) {
+        if let Ok(mut lock) = CONFIG.write() {
+            *lock = Some(config);
+        }
+        crate::detect::HardwareCache::global().invalidate();
+    }
+
+    /// Changes only the low-battery threshold (percent).
+    pub fn set_low_battery_threshold(percent: f32) {
+        Self::set_global(Self { low_battery_threshold: percent, ..Self::global() });
+    }
+}
+
+/// Reads every supply under `<root>/class/power_supply`, sorted by name.
+///
+/// Batteries with `scope` set to `Device` (mice, keyboards, headsets) are skipped
+/// because they do not power the system.
+pub fn read_power_supplies(root: impl AsRef<Path>) -> Vec<PowerSupply> {
+    let mut supplies: Vec<PowerSupply> = std::fs::read_dir(root.as_ref().join("class/power_supply"))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            if read_trimmed(path.join("scope")).is_some_and(|s| s.eq_ignore_ascii_case("device")) {
+                return None;
+            }
+            Some(PowerSupply {
+                name: entry.file_name().to_string_lossy().into_owned(),
+                kind: match read_trimmed(path.join("type"))?.as_str() {
+                    "Mains" => PowerSupplyKind::Mains,
+                    "Battery" => PowerSupplyKind::Battery,
+                    "USB" | "USB_C" | "USB_PD" => PowerSupplyKind::Usb,
+                    "UPS" => PowerSupplyKind::Ups,
+                    _ => PowerSupplyKind::Other,
+                },
+                online: read_parsed::<u8>(path.join("online")).map(|v| v != 0),
+                capacity: read_parsed::<f32>(path.join("capacity")).map(|c| c.clamp(0.0, 100.0)),
+                status: read_trimmed(path.join("status")).map(|s| match s.as_str() {
+                    "Charging" => BatteryStatus::Charging,
+                    "Discharging" => BatteryStatus::Discharging,
+                    "Full" => BatteryStatus::Full,
+                    "Not charging" => BatteryStatus::NotCharging,
+                    _ => BatteryStatus::Unknown,
+                }),
+            })
+        })
+        .collect();
+    supplies.sort_by(|a, b| a.name.cmp(&b.name));
+    supplies
+}
+
+/// Summary of the system's power state derived from its supplies.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct PowerState {
+    pub on_battery: Option<bool>,
+    pub battery_level: Option<f32>,
+    pub battery_status: Option<BatteryStatus>,
+}
+
+pub(crate) fn summarize(supplies: &[PowerSupply]) -> PowerState {
+    let batteries: Vec<&PowerSupply> = supplies.iter().filter(|s| s.kind == PowerSupplyKind::Battery).collect();
+    let external_online = supplies.iter().filter(|s| s.is_external()).filter_map(|s| s.online).reduce(|a, b| a || b);
+    let discharging = batteries.iter().any(|b| b.status == Some(BatteryStatus::Discharging));
+
+    let on_battery = match (external_online, batteries.is_empty()) {
+        (Some(true), _) => Some(false),
+        (Some(false), false) => Some(true),
+        (None, false) => Some(discharging),
+        (_, true) => external_online.map(|_| false),
+    };
+
+    let levels: Vec<f32> = batteries.iter().filter_map(|b| b.capacity).collect();
+    PowerState {
+        on_battery,
+        battery_level: (!levels.is_empty()).then(|| levels.iter().sum::<f32>() / levels.len() as f32),
+        battery_status: batteries.iter().filter_map(|b| b.status).next(),
+    }
+}
@@ -0,0 +1,81 @@
+use crate::detect::sysfs::{read_parsed, read_trimmed};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// What a RAPL zone measures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum RaplDomain {
+    /// A whole CPU socket.
+    Package,
+    /// The CPU cores of a package.
+    Core,
+    /// Integrated graphics and other uncore parts of a package.
+    Uncore,
+    Dram,
+    /// The whole platform (SoC), where supported.
+    Psys,
+    Other,
+}
+
+/// One energy counter under `/sys/class/powercap`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RaplZone {
+    /// Directory name, e.g. `intel-rapl:0:1`.
+    pub zone: String,
+    /// The zone's `name`, e.g. `package-0` or `dram`.
+    pub name: String,
+    pub domain: RaplDomain,
+    pub energy_path: PathBuf,
+    /// The counter wraps to zero after this many microjoules; `u64::MAX`
+    /// when the zone does not report its range.
+    pub max_energy_range_uj: u64,
+}
+
+impl RaplZone {
+    pub fn read_energy_uj(&self) -> Option<u64> {
+        read_parsed(&self.energy_path)
+    }
+}
+
+fn domain(name: &str) -> RaplDomain {
+    match name {
+        n if n.starts_with("package") => RaplDomain::Package,
+        "core" => RaplDomain::Core,
+        "uncore" => RaplDomain::Uncore,
+        "dram" => RaplDomain::Dram,
+        "psys" => RaplDomain::Psys,
+        _ => RaplDomain::Other,
+    }
+}
+
+/// Reads the RAPL zones under `<root>/class/powercap`, sorted by directory name.
+///
+/// Intel and AMD both expose their counters as `intel-rapl:*` zones. Zones
+/// whose `energy_uj` cannot be read are skipped; recent kernels restrict it
+/// to root.
+pub fn read_rapl_zones(root: impl AsRef<Path>) -> Vec<RaplZone> {
+    let mut zones: Vec<RaplZone> = std::fs::read_dir(root.as_ref().join("class/powercap"))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let zone = entry.file_name().to_string_lossy().into_owned();
+            // `intel-rapl` itself is the control type, not a zone.
+            if !zone.starts_with("intel-rapl:") {
+                return None;
+            }
+            let path = entry.path();
+            let name = read_trimmed(path.join("name"))?;
+            let zone = RaplZone {
+                zone,
+                domain: domain(&name),
+                name,
+                energy_path: path.join("energy_uj"),
+                max_energy_range_uj: read_parsed(path.join("max_energy_range_uj")).unwrap_or(u64::MAX),
+            };
+            zone.read_energy_uj().map(|_| zone)
+        })
+        .collect();
+    zones.sort_by(|a, b| a.zone.cmp(&b.zone));
+    zones
+}
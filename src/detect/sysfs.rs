@@ -0,0 +1,72 @@
+//! Small helpers for reading Linux sysfs-style trees.
+//!
+//! Every reader takes an explicit root so detection can be pointed at a
+//! fixture directory instead of the live `/sys`.
+
+use std::path::{Path, PathBuf};
+
+/// Root of the live sysfs tree.
+pub const SYSFS_ROOT: &str = "/sys";
+
+/// Reads a file and trims surrounding whitespace.
+pub(crate) fn read_trimmed(path: impl AsRef<Path>) -> Option<String> {
+    std::fs::read_to_string(path).ok().map(|s| s.trim().to_string())
+}
+
+/// Reads and parses a file holding a single value.
+pub(crate) fn read_parsed<T: std::str::FromStr>(path: impl AsRef<Path>) -> Option<T> {
+    read_trimmed(path)?.parse().ok()
+}
+
+/// Returns `(index, path)` for every entry of `dir` named `<prefix><index>`,
+/// sorted by index.
+pub(crate) fn numbered_entries(dir: impl AsRef<Path>, prefix: &str) -> Vec<(usize, PathBuf)> {
+    let mut entries: Vec<(usize, PathBuf)> = std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name();
+            let index = name.to_str()?.strip_prefix(prefix)?.parse().ok()?;
+            Some((index, entry.path()))
+        })
+        .collect();
+    entries.sort_by_key(|(index, _)| *index);
+    entries
+}
+
+/// Parses a kernel CPU list such as `0-3,8,10-11`.
+pub(crate) fn parse_cpu_list(list: &str) -> Vec<usize> {
+    let mut cpus = Vec::new();
+    for part in list.trim().split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        match part.split_once('-') {
+            Some((start, end)) => {
+                if let (Ok(start), Ok(end)) = (start.parse::<usize>(), end.parse::<usize>()) {
+                    cpus.extend(start..=end);
+                }
+            }
+            None => cpus.extend(part.parse::<usize>().ok()),
+        }
+    }
+    cpus.sort_unstable();
+    cpus.dedup();
+    cpus
+}
+
+/// Parses a sysfs size such as `48K`, `2048K` or `30M` into bytes.
+pub(crate) fn parse_size(size: &str) -> Option<u64> {
+    let size = size.trim();
+    let (digits, multiplier) = match size.chars().last()? {
+        'K' | 'k' => (&size[..size.len() - 1], 1024),
+        'M' | 'm' => (&size[..size.len() - 1], 1024 * 1024),
+        'G' | 'g' => (&size[..size.len() - 1], 1024 * 1024 * 1024),
+        _ => (size, 1),
+    };
+    digits.trim().parse::<u64>().ok().map(|n| n * multiplier)
+}
+
+/// Reads a hexadecimal value such as `0x10de` (PCI IDs and class codes).
+pub(crate) fn read_hex(path: impl AsRef<Path>) -> Option<u32> {
+    let value = read_trimmed(path)?;
+    u32::from_str_radix(value.trim_start_matches("0x").trim_start_matches("0X"), 16).ok()
+}
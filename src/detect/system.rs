@@ -1,24 +1,54 @@
-use serde::Serialize;
+use crate::detect::power::{read_power_supplies, summarize, BatteryStatus, PowerConfig};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum PowerSource {
     AC,
     Battery,
     Unknown,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SystemContext {
     pub power_source: PowerSource,
     pub battery_level: Option<f32>,
+    pub battery_status: Option<BatteryStatus>,
+    /// Running on battery below `PowerConfig::low_battery_threshold`.
     pub is_low_power_mode: bool,
 }
 
+impl Default for SystemContext {
+    fn default() -> Self {
+        Self {
+            power_source: PowerSource::Unknown,
+            battery_level: None,
+            battery_status: None,
+            is_low_power_mode: false,
+        }
+    }
+}
+
 pub fn detect_context() -> SystemContext {
-    // Basic detection for now, could be expanded with battery crates later
+    detect_context_with(&PowerConfig::global())
+}
+
+/// Reads the power supplies under `config.sysfs_root`.
+///
+/// Without any supply information (desktops without a battery driver,
+/// non-Linux hosts) the power source is `Unknown` and low-power mode stays off.
+pub fn detect_context_with(config: &PowerConfig) -> SystemContext {
+    let state = summarize(&read_power_supplies(&config.sysfs_root));
+    let power_source = match state.on_battery {
+        Some(true) => PowerSource::Battery,
+        Some(false) => PowerSource::AC,
+        None => PowerSource::Unknown,
+    };
+
     SystemContext {
-        power_source: PowerSource::Unknown,
-        battery_level: None,
-        is_low_power_mode: false,
+        power_source,
+        battery_level: state.battery_level,
+        battery_status: state.battery_status,
+        is_low_power_mode: power_source == PowerSource::Battery
+            && state.battery_level.is_some_and(|level| level < config.low_battery_threshold),
     }
 }
@@ -0,0 +1,206 @@
+use crate::detect::sysfs::{self, numbered_entries, parse_cpu_list, parse_size, read_parsed, read_trimmed};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Cache line size assumed when nothing better is known.
+pub const DEFAULT_LINE_SIZE: usize = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum CacheKind {
+    Data,
+    Instruction,
+    Unified,
+}
+
+/// Where a `CacheTopology` was read from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TopologySource {
+    Sysfs,
+    Cpuid,
+    /// Conservative defaults used when detection failed.
+    Default,
+}
+
+/// One cache instance, e.g. the L2 shared by CPUs 0 and 1.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheInfo {
+    pub level: u8,
+    pub kind: CacheKind,
+    pub size_bytes: u64,
+    pub line_size: usize,
+    pub ways: Option<usize>,
+    /// Logical CPUs sharing this cache instance.
+    pub shared_cpus: Vec<usize>,
+}
+
+impl CacheInfo {
+    /// Share of this cache available to a single logical CPU.
+    pub fn per_cpu_bytes(&self) -> u64 {
+        self.size_bytes / self.shared_cpus.len().max(1) as u64
+    }
+
+    fn holds_data(&self) -> bool {
+        self.kind != CacheKind::Instruction
+    }
+}
+
+/// Cache hierarchy of the host, one entry per distinct cache instance.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CacheTopology {
+    pub caches: Vec<CacheInfo>,
+    pub source: TopologySource,
+}
+
+impl CacheTopology {
+    /// Detects the cache hierarchy from sysfs, then CPUID leaf 4, then defaults.
+    pub fn detect() -> Self {
+        Self::from_sysfs(sysfs::SYSFS_ROOT)
+            .or_else(Self::from_cpuid)
+            .unwrap_or_default()
+    }
+
+    /// Reads `<root>/devices/system/cpu/cpu*/cache/index*`.
+    ///
+    /// Caches reported by several CPUs are merged using their shared CPU set.
+    /// Returns `None` if the tree has no cache entries.
+    pub fn from_sysfs(root: impl AsRef<Path>) -> Option<Self> {
+        let cpu_dir = root.as_ref().join("devices/system/cpu");
+        let mut caches: Vec<CacheInfo> = Vec::new();
+
+        for (cpu, cpu_path) in numbered_entries(&cpu_dir, "cpu") {
+            for (_, index) in numbered_entries(cpu_path.join("cache"), "index") {
+                let Some(cache) = read_cache_index(&index, cpu) else { continue };
+                if !caches.iter().any(|c| c.level == cache.level && c.kind == cache.kind && c.shared_cpus == cache.shared_cpus) {
+                    caches.push(cache);
+                }
+            }
+        }
+
+        if caches.is_empty() {
+            return None;
+        }
+        caches.sort_by(|a, b| (a.level, a.kind, &a.shared_cpus).cmp(&(b.level, b.kind, &b.shared_cpus)));
+        Some(Self { caches, source: TopologySource::Sysfs })
+    }
+
+    /// Reads the deterministic cache parameters leaf (CPUID 4).
+    ///
+    /// CPUID reports how many logical CPUs share a cache but not which ones, so
+    /// `shared_cpus` is filled with a contiguous range of that size.
+    #[cfg(target_arch = "x86_64")]
+    pub fn from_cpuid() -> Option<Self> {
+        use std::arch::x86_64::{__cpuid, __cpuid_count};
+
+        if __cpuid(0).eax < 4 {
+            return None;
+        }
+        let mut caches = Vec::new();
+        for subleaf in 0..16 {
+            let r = __cpuid_count(4, subleaf);
+            let kind = match r.eax & 0x1f {
+                1 => CacheKind::Data,
+                2 => CacheKind::Instruction,
+                3 => CacheKind::Unified,
+                _ => break,
+            };
+            let line_size = (r.ebx & 0xfff) as usize + 1;
+            let partitions = ((r.ebx >> 12) & 0x3ff) as u64 + 1;
+            let ways = ((r.ebx >> 22) & 0x3ff) as usize + 1;
+            let sets = r.ecx as u64 + 1;
+            let sharing = ((r.eax >> 14) & 0xfff) as usize + 1;
+            caches.push(CacheInfo {
+                level: ((r.eax >> 5) & 0x7) as u8,
+                kind,
+                size_bytes: ways as u64 * partitions * line_size as u64 * sets,
+                line_size,
+                ways: Some(ways),
+                shared_cpus: (0..sharing).collect(),
+            });
+        }
+        (!caches.is_empty()).then_some(Self { caches, source: TopologySource::Cpuid })
+    }
+
+    #[cfg(not(target_arch = "x86_64"))]
+    pub fn from_cpuid() -> Option<Self> {
+        None
+    }
+
+    /// Returns the first data or unified cache at `level`.
+    pub fn data_cache(&self, level: u8) -> Option<&CacheInfo> {
+        self.caches.iter().find(|c| c.level == level && c.holds_data())
+    }
+
+    /// Deepest cache level present.
+    pub fn last_level(&self) -> Option<&CacheInfo> {
+        self.caches.iter().filter(|c| c.holds_data()).max_by_key(|c| c.level)
+    }
+
+    /// Line size of the L1 data cache.
+    pub fn line_size(&self) -> usize {
+        self.data_cache(1).map(|c| c.line_size).unwrap_or(DEFAULT_LINE_SIZE).max(1)
+    }
+
+    /// Number of elements so that `streams` arrays of `elem_bytes` elements fit
+    /// together in one CPU's share of the cache at `level`.
+    ///
+    /// The result is rounded down to whole cache lines and is at least one line.
+    /// Falls back to the closest lower level when `level` is not present.
+    pub fn tile_elements(&self, level: u8, elem_bytes: usize, streams: usize) -> usize {
+        let elem_bytes = elem_bytes.max(1);
+        let line_elems = (self.line_size() / elem_bytes).max(1);
+        let bytes = (1..=level).rev()
+            .find_map(|l| self.data_cache(l))
+            .map(|c| c.per_cpu_bytes())
+            .unwrap_or(32 * 1024) as usize;
+
+        let elems = bytes / (streams.max(1) * elem_bytes);
+        (elems / line_elems * line_elems).max(line_elems)
+    }
+}
+
+impl Default for CacheTopology {
+    /// A 32 KiB L1d, 512 KiB L2 and 8 MiB shared L3 with 64-byte lines.
+    fn default() -> Self {
+        let cache = |level, kind, size_bytes| CacheInfo {
+            level,
+            kind,
+            size_bytes,
+            line_size: DEFAULT_LINE_SIZE,
+            ways: None,
+            shared_cpus: vec![0],
+        };
+        Self {
+            caches: vec![
+                cache(1, CacheKind::Data, 32 * 1024),
+                cache(1, CacheKind::Instruction, 32 * 1024),
+                cache(2, CacheKind::Unified, 512 * 1024),
+                cache(3, CacheKind::Unified, 8 * 1024 * 1024),
+            ],
+            source: TopologySource::Default,
+        }
+    }
+}
+
+fn read_cache_index(index: &Path, cpu: usize) -> Option<CacheInfo> {
+    let kind = match read_trimmed(index.join("type"))?.as_str() {
+        "Data" => CacheKind::Data,
+        "Instruction" => CacheKind::Instruction,
+        "Unified" => CacheKind::Unified,
+        _ => return None,
+    };
+    let mut shared_cpus = read_trimmed(index.join("shared_cpu_list"))
+        .map(|list| parse_cpu_list(&list))
+        .unwrap_or_default();
+    if shared_cpus.is_empty() {
+        shared_cpus.push(cpu);
+    }
+
+    Some(CacheInfo {
+        level: read_parsed(index.join("level"))?,
+        kind,
+        size_bytes: parse_size(&read_trimmed(index.join("size"))?)?,
+        line_size: read_parsed(index.join("coherency_line_size")).unwrap_or(DEFAULT_LINE_SIZE),
+        ways: read_parsed(index.join("ways_of_associativity")),
+        shared_cpus,
+    })
+}
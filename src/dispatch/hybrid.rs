@@ -1,87 +1,162 @@
+use crate::error::{ArchXError, ArchXResult};
 use crate::gpu::{self, GpuBackend, GpuPolicy};
+use crate::profiler::{get_profiler, Metric};
+use std::collections::HashMap;
+use std::time::Instant;
 
 pub struct HybridScheduler;
 
 impl HybridScheduler {
     /// Dispatches a task cooperatively between CPU and GPU.
-    pub fn dispatch_add(a: &[f32], b: &[f32], out: &mut [f32]) {
-        let (gpu_part, cpu_part) = Self::split(a.len());
-        
-        if let Some((gpu, len)) = gpu_part {
-            let _ = gpu.add(&a[..len], &b[..len], &mut out[..len]);
+    ///
+    /// If the GPU partition fails it is recomputed on the CPU, so the output is
+    /// always complete. The fault is recorded in the profiler and counts toward
+    /// the backend's quarantine: after `QUARANTINE_THRESHOLD` consecutive faults
+    /// the backend is skipped until `gpu::quarantine_cooldown()` has passed
+    /// without a fault, then probed again (`gpu::clear_quarantine` lifts it at once).
+    ///
+    /// # Errors
+    /// Returns `ArchXError::InvalidInput` if slice lengths do not match.
+    pub fn dispatch_add(a: &[f32], b: &[f32], out: &mut [f32]) -> ArchXResult<()> {
+        Self::check_lengths(a.len(), b.len(), Some(out.len()))?;
+        let gpu_len = Self::split(a.len());
+
+        if gpu_len > 0 {
+            let res = Self::run_gpu("Hybrid Add", gpu_len, |gpu| gpu.add(&a[..gpu_len], &b[..gpu_len], &mut out[..gpu_len]));
+            if res.is_err() {
+                Self::cpu_add(&a[..gpu_len], &b[..gpu_len], &mut out[..gpu_len]);
+            }
         }
-        
-        if let Some((start, end)) = cpu_part {
-            Self::cpu_add(&a[start..end], &b[start..end], &mut out[start..end]);
+
+        if gpu_len < a.len() {
+            Self::cpu_add(&a[gpu_len..], &b[gpu_len..], &mut out[gpu_len..]);
         }
+        Ok(())
     }
 
-    pub fn dispatch_sub(a: &[f32], b: &[f32], out: &mut [f32]) {
-        let (gpu_part, cpu_part) = Self::split(a.len());
-        
-        if let Some((gpu, len)) = gpu_part {
-            let _ = gpu.sub(&a[..len], &b[..len], &mut out[..len]);
+    /// Dispatches a subtraction cooperatively between CPU and GPU.
+    ///
+    /// See [`HybridScheduler::dispatch_add`] for the fallback behaviour.
+    pub fn dispatch_sub(a: &[f32], b: &[f32], out: &mut [f32]) -> ArchXResult<()> {
+        Self::check_lengths(a.len(), b.len(), Some(out.len()))?;
+        let gpu_len = Self::split(a.len());
+
+        if gpu_len > 0 {
+            let res = Self::run_gpu("Hybrid Sub", gpu_len, |gpu| gpu.sub(&a[..gpu_len], &b[..gpu_len], &mut out[..gpu_len]));
+            if res.is_err() {
+                Self::cpu_sub(&a[..gpu_len], &b[..gpu_len], &mut out[..gpu_len]);
+            }
         }
-        
-        if let Some((start, end)) = cpu_part {
-            Self::cpu_sub(&a[start..end], &b[start..end], &mut out[start..end]);
+
+        if gpu_len < a.len() {
+            Self::cpu_sub(&a[gpu_len..], &b[gpu_len..], &mut out[gpu_len..]);
         }
+        Ok(())
     }
 
-    pub fn dispatch_mul(a: &[f32], b: &[f32], out: &mut [f32]) {
-        let (gpu_part, cpu_part) = Self::split(a.len());
-        
-        if let Some((gpu, len)) = gpu_part {
-            let _ = gpu.mul(&a[..len], &b[..len], &mut out[..len]);
+    /// Dispatches a multiplication cooperatively between CPU and GPU.
+    ///
+    /// See [`HybridScheduler::dispatch_add`] for the fallback behaviour.
+    pub fn dispatch_mul(a: &[f32], b: &[f32], out: &mut [f32]) -> ArchXResult<()> {
+        Self::check_lengths(a.len(), b.len(), Some(out.len()))?;
+        let gpu_len = Self::split(a.len());
+
+        if gpu_len > 0 {
+            let res = Self::run_gpu("Hybrid Mul", gpu_len, |gpu| gpu.mul(&a[..gpu_len], &b[..gpu_len], &mut out[..gpu_len]));
+            if res.is_err() {
+                Self::cpu_mul(&a[..gpu_len], &b[..gpu_len], &mut out[..gpu_len]);
+            }
         }
-        
-        if let Some((start, end)) = cpu_part {
-            Self::cpu_mul(&a[start..end], &b[start..end], &mut out[start..end]);
+
+        if gpu_len < a.len() {
+            Self::cpu_mul(&a[gpu_len..], &b[gpu_len..], &mut out[gpu_len..]);
         }
+        Ok(())
     }
 
-    pub fn dispatch_dot(a: &[f32], b: &[f32]) -> f32 {
-        let (gpu_part, cpu_part) = Self::split(a.len());
+    /// Dispatches a dot product cooperatively between CPU and GPU.
+    ///
+    /// A failed GPU partial sum is recomputed on the CPU instead of being
+    /// treated as zero.
+    pub fn dispatch_dot(a: &[f32], b: &[f32]) -> ArchXResult<f32> {
+        Self::check_lengths(a.len(), b.len(), None)?;
+        let gpu_len = Self::split(a.len());
         let mut total = 0.0;
-        
-        if let Some((gpu, len)) = gpu_part {
-            total += gpu.dot(&a[..len], &b[..len]).unwrap_or(0.0);
+
+        if gpu_len > 0 {
+            total += match Self::run_gpu("Hybrid Dot", gpu_len, |gpu| gpu.dot(&a[..gpu_len], &b[..gpu_len])) {
+                Ok(partial) => partial,
+                Err(_) => Self::cpu_dot(&a[..gpu_len], &b[..gpu_len]),
+            };
+        }
+
+        if gpu_len < a.len() {
+            total += Self::cpu_dot(&a[gpu_len..], &b[gpu_len..]);
         }
-        
-        if let Some((start, end)) = cpu_part {
-            total += Self::cpu_dot(&a[start..end], &b[start..end]);
+        Ok(total)
+    }
+
+    fn check_lengths(a: usize, b: usize, out: Option<usize>) -> ArchXResult<()> {
+        if a != b || out.is_some_and(|o| o != a) {
+            return Err(ArchXError::InvalidInput("Slices must have identical lengths".to_string()));
         }
-        total
+        Ok(())
     }
 
-    fn split(len: usize) -> (Option<(Box<dyn GpuBackend>, usize)>, Option<(usize, usize)>) {
+    /// Returns how many leading elements should be sent to the GPU.
+    fn split(len: usize) -> usize {
         let policy = gpu::get_gpu_policy();
-        
+        if policy == GpuPolicy::ForceCpu || gpu::usable_backend_name().is_none() {
+            return 0;
+        }
+
         match policy {
-            GpuPolicy::ForceCpu => (None, Some((0, len))),
-            GpuPolicy::ForceGpu => {
-                if let Some(gpu) = gpu::select_best_backend() {
-                    (Some((gpu, len)), None)
-                } else {
-                    (None, Some((0, len)))
-                }
+            GpuPolicy::ForceGpu => len,
+            // Intelligent split: GPU takes 70% if len > 1M, else CPU
+            _ if len > 1_000_000 => (len as f32 * 0.7) as usize,
+            _ => 0,
+        }
+    }
+
+    /// Runs a GPU partition, recording faults and updating backend health.
+    fn run_gpu<F, R>(op: &str, len: usize, f: F) -> ArchXResult<R>
+    where
+        F: FnOnce(&dyn GpuBackend) -> Result<R, String>
+    {
+        let start = Instant::now();
+        let outcome = gpu::with_usable_backend(|gpu: &dyn GpuBackend| (gpu.name(), f(gpu)));
+
+        match outcome {
+            Some((name, Ok(value))) => {
+                gpu::report_success(name);
+                Ok(value)
+            }
+            Some((name, Err(msg))) => {
+                let quarantined = gpu::report_fault(name);
+                Self::record_fallback(op, name, len, &msg, quarantined, start);
+                Err(ArchXError::GpuError(msg))
             }
-            GpuPolicy::Adaptive => {
-                // Intelligent split: GPU takes 70% if len > 1M, else CPU
-                if len > 1_000_000 {
-                    if let Some(gpu) = gpu::select_best_backend() {
-                        let gpu_len = (len as f32 * 0.7) as usize;
-                        (Some((gpu, gpu_len)), Some((gpu_len, len)))
-                    } else {
-                        (None, Some((0, len)))
-                    }
-                } else {
-                    (None, Some((0, len)))
-                }
+            None => {
+                let msg = "No usable GPU backend".to_string();
+                Self::record_fallback(op, "None", len, &msg, false, start);
+                Err(ArchXError::GpuError(msg))
             }
         }
     }
 
+    fn record_fallback(op: &str, backend: &str, len: usize, msg: &str, quarantined: bool, start: Instant) {
+        let mut metadata = HashMap::new();
+        metadata.insert("operation".to_string(), op.to_string());
+        metadata.insert("error".to_string(), msg.to_string());
+        metadata.insert("elements".to_string(), len.to_string());
+        metadata.insert("quarantined".to_string(), quarantined.to_string());
+
+        get_profiler().record(Metric {
+            metadata,
+            ..Metric::new("GPU Fallback", backend, "GPU", start.elapsed())
+        });
+    }
+
     fn cpu_add(a: &[f32], b: &[f32], out: &mut [f32]) {
         crate::optimizer::parallel::add_parallel_impl(a, b, out, &crate::WorkloadHints::default());
     }
@@ -7,7 +7,7 @@ use crate::optimizer::simd::{sse2, avx, avx2};
 type AddFn = fn(&[f32], &[f32], &mut [f32]);
 
 /// Defines the strategy for execution path selection.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
 pub enum DispatchPath {
     Scalar,
     SSE2,
@@ -20,6 +20,13 @@ pub enum ArchXError {
     HardwareError(String),
     /// A general execution failure within the task runner or hybrid scheduler.
     ExecutionError(String),
+    /// The operation was stopped through its `CancellationToken`.
+    Cancelled,
+    /// The operation did not finish before its configured deadline.
+    DeadlineExceeded,
+    /// Admission control refused the operation (thread or memory budget exhausted).
+    /// Budgets follow cgroup limits when running in a container.
+    ResourceLimit(String),
 }
 
 impl fmt::Display for ArchXError {
@@ -31,10 +38,30 @@ impl fmt::Display for ArchXError {
             ArchXError::ArithmeticOverflow => write!(f, "Arithmetic Overflow/Infinity encountered"),
             ArchXError::HardwareError(msg) => write!(f, "Hardware Error: {}", msg),
             ArchXError::ExecutionError(msg) => write!(f, "Execution Error: {}", msg),
+            ArchXError::Cancelled => write!(f, "Operation cancelled"),
+            ArchXError::DeadlineExceeded => write!(f, "Operation deadline exceeded"),
+            ArchXError::ResourceLimit(msg) => write!(f, "Resource Limit: {}", msg),
         }
     }
 }
 
 impl std::error::Error for ArchXError {}
 
+impl From<crate::core::ArchXCoreError> for ArchXError {
+    fn from(err: crate::core::ArchXCoreError) -> Self {
+        use crate::core::ArchXCoreError;
+        match err {
+            ArchXCoreError::GpuFailure(msg) => ArchXError::GpuError(msg),
+            ArchXCoreError::SimdFailure(msg) => ArchXError::SimdError(msg),
+            ArchXCoreError::InvalidInput(msg) => ArchXError::InvalidInput(msg),
+            ArchXCoreError::MathOverflow => ArchXError::ArithmeticOverflow,
+            ArchXCoreError::DetectionError(msg) => ArchXError::HardwareError(msg),
+            ArchXCoreError::Cancelled => ArchXError::Cancelled,
+            ArchXCoreError::DeadlineExceeded => ArchXError::DeadlineExceeded,
+            ArchXCoreError::ResourceError(msg) => ArchXError::ResourceLimit(msg),
+            other => ArchXError::ExecutionError(other.to_string()),
+        }
+    }
+}
+
 pub type ArchXResult<T> = Result<T, ArchXError>;
@@ -0,0 +1,78 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// Number of consecutive faults after which a backend is quarantined.
+pub const QUARANTINE_THRESHOLD: usize = 3;
+
+/// How long a quarantined backend is skipped before it is tried again.
+pub const DEFAULT_QUARANTINE_COOLDOWN: Duration = Duration::from_secs(30);
+
+/// Consecutive faults of one backend and the time of the latest one.
+struct Faults {
+    count: usize,
+    last: Instant,
+}
+
+static FAULTS: OnceLock<Mutex<HashMap<&'static str, Faults>>> = OnceLock::new();
+static FALLBACKS: AtomicU64 = AtomicU64::new(0);
+static COOLDOWN_NANOS: AtomicU64 = AtomicU64::new(DEFAULT_QUARANTINE_COOLDOWN.as_nanos() as u64);
+
+fn faults() -> &'static Mutex<HashMap<&'static str, Faults>> {
+    FAULTS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Records a failed GPU call for `backend`.
+///
+/// Returns `true` if this fault pushed the backend into quarantine.
+pub fn report_fault(backend: &'static str) -> bool {
+    FALLBACKS.fetch_add(1, Ordering::Relaxed);
+    if let Ok(mut lock) = faults().lock() {
+        let faults = lock.entry(backend).or_insert(Faults { count: 0, last: Instant::now() });
+        faults.count += 1;
+        faults.last = Instant::now();
+        return faults.count == QUARANTINE_THRESHOLD;
+    }
+    false
+}
+
+/// Records a successful GPU call, resetting the consecutive fault counter.
+pub fn report_success(backend: &'static str) {
+    if let Ok(mut lock) = faults().lock() {
+        lock.remove(backend);
+    }
+}
+
+/// Returns true if `backend` has faulted too often to be trusted with work.
+///
+/// The quarantine expires once the backend has not faulted for the cooldown
+/// (see [`set_quarantine_cooldown`]). The next call then probes it: a success
+/// clears its record, a fault quarantines it again right away.
+pub fn is_quarantined(backend: &str) -> bool {
+    let cooldown = quarantine_cooldown();
+    faults().lock()
+        .map(|lock| lock.get(backend).is_some_and(|f| f.count >= QUARANTINE_THRESHOLD && f.last.elapsed() < cooldown))
+        .unwrap_or(false)
+}
+
+/// Sets how long a quarantined backend is skipped before it is probed again.
+pub fn set_quarantine_cooldown(cooldown: Duration) {
+    COOLDOWN_NANOS.store(cooldown.as_nanos() as u64, Ordering::Relaxed);
+}
+
+pub fn quarantine_cooldown() -> Duration {
+    Duration::from_nanos(COOLDOWN_NANOS.load(Ordering::Relaxed))
+}
+
+/// Lifts the quarantine on every backend (e.g. after a driver reset).
+pub fn clear_quarantine() {
+    if let Ok(mut lock) = faults().lock() {
+        lock.clear();
+    }
+}
+
+/// Total number of GPU partitions that were recomputed on the CPU.
+pub fn fallback_count() -> u64 {
+    FALLBACKS.load(Ordering::Relaxed)
+}
@@ -1,7 +1,8 @@
 use crate::gpu::{GpuBackend, vulkan::VulkanBackend, opencl::OpenCLBackend, opengl::OpenGLBackend};
+use crate::gpu::health::is_quarantined;
 use std::sync::{OnceLock, RwLock};
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize)]
 pub enum GpuPolicy {
     Adaptive,
     ForceGpu,
@@ -20,24 +21,20 @@ pub fn set_gpu_policy(policy: GpuPolicy) {
 pub fn get_gpu_policy() -> GpuPolicy {
     GPU_POLICY.get()
         .and_then(|l| l.read().ok())
-        .map(|lock| match *lock {
-            GpuPolicy::Adaptive => GpuPolicy::Adaptive,
-            GpuPolicy::ForceGpu => GpuPolicy::ForceGpu,
-            GpuPolicy::ForceCpu => GpuPolicy::ForceCpu,
-        })
+        .map(|lock| *lock)
         .unwrap_or(GpuPolicy::Adaptive)
 }
 
 pub fn select_best_backend() -> Option<Box<dyn GpuBackend>> {
-    // Priority: Vulkan > OpenCL > OpenGL
+    // Priority: Vulkan > OpenCL > OpenGL, skipping quarantined backends
     let v = VulkanBackend;
-    if v.is_available() { return Some(Box::new(v)); }
+    if v.is_available() && !is_quarantined(v.name()) { return Some(Box::new(v)); }
     
     let oc = OpenCLBackend;
-    if oc.is_available() { return Some(Box::new(oc)); }
+    if oc.is_available() && !is_quarantined(oc.name()) { return Some(Box::new(oc)); }
     
     let og = OpenGLBackend;
-    if og.is_available() { return Some(Box::new(og)); }
+    if og.is_available() && !is_quarantined(og.name()) { return Some(Box::new(og)); }
     
     None
 }
@@ -0,0 +1,233 @@
+use super::GpuBackend;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+/// A failure mode the mock backend can inject.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GpuFault {
+    /// The device ran out of memory; the call returns an error.
+    OutOfMemory,
+    /// The kernel did not complete in time; the call returns an error.
+    Timeout,
+    /// The device disappeared (driver reset, hot unplug); the call returns an error.
+    DeviceLost,
+    /// The call "succeeds" but the output is garbage (NaN).
+    CorruptData,
+}
+
+impl fmt::Display for GpuFault {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GpuFault::OutOfMemory => write!(f, "out of device memory"),
+            GpuFault::Timeout => write!(f, "kernel execution timed out"),
+            GpuFault::DeviceLost => write!(f, "device lost"),
+            GpuFault::CorruptData => write!(f, "corrupted output"),
+        }
+    }
+}
+
+/// When the mock backend should inject its fault.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FailureSchedule {
+    /// Never fail.
+    Never,
+    /// Fail on every Nth call (1-based), e.g. `EveryNth(3)` fails calls 3, 6, 9...
+    EveryNth(usize),
+    /// Fail every call once the cumulative input exceeds this many bytes.
+    AfterBytes(u64),
+    /// Fail each call with the given probability, using a deterministic seed.
+    Random { probability: f32, seed: u64 },
+}
+
+/// One call observed by the mock backend.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MockCall {
+    pub op: &'static str,
+    pub elements: usize,
+    pub bytes: u64,
+    pub fault: Option<GpuFault>,
+}
+
+#[derive(Default)]
+struct MockState {
+    calls: Vec<MockCall>,
+    bytes: u64,
+    rng: u64,
+}
+
+/// A scriptable `GpuBackend` for resilience testing.
+///
+/// Clones share their counters, so a test can keep one handle while
+/// registering another through `gpu::register_backend`.
+///
+/// ```rust
+/// use archx::gpu::{self, MockGpuBackend, GpuFault, FailureSchedule};
+///
+/// let mock = MockGpuBackend::new()
+///     .fail_with(GpuFault::Timeout, FailureSchedule::EveryNth(2));
+/// gpu::register_backend(Box::new(mock.clone()));
+/// assert_eq!(mock.call_count(), 0);
+/// ```
+#[derive(Clone)]
+pub struct MockGpuBackend {
+    name: &'static str,
+    available: bool,
+    fault: GpuFault,
+    schedule: FailureSchedule,
+    state: Arc<Mutex<MockState>>,
+}
+
+impl Default for MockGpuBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MockGpuBackend {
+    pub fn new() -> Self {
+        Self {
+            name: "Mock",
+            available: true,
+            fault: GpuFault::DeviceLost,
+            schedule: FailureSchedule::Never,
+            state: Arc::new(Mutex::new(MockState::default())),
+        }
+    }
+
+    /// Sets the backend name reported to the scheduler and quarantine registry.
+    pub fn with_name(mut self, name: &'static str) -> Self {
+        self.name = name;
+        self
+    }
+
+    /// Sets whether the backend reports itself as available.
+    pub fn available(mut self, available: bool) -> Self {
+        self.available = available;
+        self
+    }
+
+    /// Injects `fault` according to `schedule`.
+    pub fn fail_with(mut self, fault: GpuFault, schedule: FailureSchedule) -> Self {
+        self.fault = fault;
+        self.schedule = schedule;
+        if let FailureSchedule::Random { seed, .. } = schedule {
+            if let Ok(mut state) = self.state.lock() {
+                // xorshift must never be seeded with zero
+                state.rng = seed.max(1);
+            }
+        }
+        self
+    }
+
+    /// Total number of calls received.
+    pub fn call_count(&self) -> usize {
+        self.state.lock().map(|s| s.calls.len()).unwrap_or(0)
+    }
+
+    /// Number of calls that had a fault injected.
+    pub fn fault_count(&self) -> usize {
+        self.state.lock()
+            .map(|s| s.calls.iter().filter(|c| c.fault.is_some()).count())
+            .unwrap_or(0)
+    }
+
+    /// Total input bytes submitted to the backend.
+    pub fn bytes_processed(&self) -> u64 {
+        self.state.lock().map(|s| s.bytes).unwrap_or(0)
+    }
+
+    /// Returns every call in order.
+    pub fn calls(&self) -> Vec<MockCall> {
+        self.state.lock().map(|s| s.calls.clone()).unwrap_or_default()
+    }
+
+    /// Clears counters and the call log. The random schedule keeps its position.
+    pub fn reset(&self) {
+        if let Ok(mut state) = self.state.lock() {
+            state.calls.clear();
+            state.bytes = 0;
+        }
+    }
+
+    /// Logs a call and decides whether it should fault.
+    fn begin(&self, op: &'static str, elements: usize, inputs: usize) -> Option<GpuFault> {
+        let bytes = (elements * inputs * std::mem::size_of::<f32>()) as u64;
+        let mut state = match self.state.lock() {
+            Ok(state) => state,
+            Err(_) => return Some(GpuFault::DeviceLost),
+        };
+
+        let call_no = state.calls.len() + 1;
+        let fail = match self.schedule {
+            FailureSchedule::Never => false,
+            FailureSchedule::EveryNth(n) => n > 0 && call_no % n == 0,
+            FailureSchedule::AfterBytes(limit) => state.bytes + bytes > limit,
+            FailureSchedule::Random { probability, .. } => {
+                // xorshift64
+                let mut x = state.rng;
+                x ^= x << 13;
+                x ^= x >> 7;
+                x ^= x << 17;
+                state.rng = x;
+                ((x >> 40) as f32 / (1u64 << 24) as f32) < probability
+            }
+        };
+
+        let fault = if fail { Some(self.fault) } else { None };
+        state.bytes += bytes;
+        state.calls.push(MockCall { op, elements, bytes, fault });
+        fault
+    }
+
+    fn binary_op(&self, op: &'static str, a: &[f32], b: &[f32], out: &mut [f32], f: fn(f32, f32) -> f32) -> Result<(), String> {
+        if a.len() != b.len() || a.len() != out.len() {
+            return Err(format!("{}: slices must have identical lengths", self.name));
+        }
+        match self.begin(op, a.len(), 2) {
+            Some(GpuFault::CorruptData) => {
+                out.iter_mut().for_each(|o| *o = f32::NAN);
+                Ok(())
+            }
+            Some(fault) => Err(format!("{}: {}", self.name, fault)),
+            None => {
+                for ((o, &x), &y) in out.iter_mut().zip(a).zip(b) {
+                    *o = f(x, y);
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl GpuBackend for MockGpuBackend {
+    fn is_available(&self) -> bool {
+        self.available
+    }
+
+    fn add(&self, a: &[f32], b: &[f32], out: &mut [f32]) -> Result<(), String> {
+        self.binary_op("add", a, b, out, |x, y| x + y)
+    }
+
+    fn sub(&self, a: &[f32], b: &[f32], out: &mut [f32]) -> Result<(), String> {
+        self.binary_op("sub", a, b, out, |x, y| x - y)
+    }
+
+    fn mul(&self, a: &[f32], b: &[f32], out: &mut [f32]) -> Result<(), String> {
+        self.binary_op("mul", a, b, out, |x, y| x * y)
+    }
+
+    fn dot(&self, a: &[f32], b: &[f32]) -> Result<f32, String> {
+        if a.len() != b.len() {
+            return Err(format!("{}: slices must have identical lengths", self.name));
+        }
+        match self.begin("dot", a.len(), 2) {
+            Some(GpuFault::CorruptData) => Ok(f32::NAN),
+            Some(fault) => Err(format!("{}: {}", self.name, fault)),
+            None => Ok(a.iter().zip(b).map(|(x, y)| x * y).sum()),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        self.name
+    }
+}
@@ -39,8 +39,12 @@ pub mod vulkan;
 pub mod opencl;
 pub mod opengl;
 pub mod manager;
+pub mod health;
+pub mod mock;
 
 pub use manager::{GpuPolicy, set_gpu_policy, get_gpu_policy, select_best_backend};
+pub use health::{report_fault, report_success, is_quarantined, clear_quarantine, fallback_count, set_quarantine_cooldown, quarantine_cooldown};
+pub use mock::{MockGpuBackend, MockCall, GpuFault, FailureSchedule};
 
 pub struct DisabledBackend;
 
@@ -86,6 +90,51 @@ where
         .and_then(|lock| lock.as_ref().map(|b| f(b.as_ref())))
 }
 
+/// Executes a closure with the best backend that is available and not quarantined.
+///
+/// A registered backend takes precedence over the built-in ones.
+pub fn with_usable_backend<F, R>(f: F) -> Option<R>
+where
+    F: FnOnce(&dyn GpuBackend) -> R
+{
+    if let Some(lock) = ACTIVE_BACKEND.get().and_then(|l| l.read().ok()) {
+        if let Some(backend) = lock.as_ref().filter(|b| b.is_available() && !is_quarantined(b.name())) {
+            return Some(f(backend.as_ref()));
+        }
+    }
+    select_best_backend().map(|b| f(b.as_ref()))
+}
+
+/// Availability of one GPU backend, as reported by [`backend_status`].
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct BackendStatus {
+    pub name: &'static str,
+    /// Registered with [`register_backend`] rather than built in.
+    pub registered: bool,
+    pub available: bool,
+    pub quarantined: bool,
+}
+
+/// Lists the registered backend (if any) followed by the built-in ones, in selection order.
+pub fn backend_status() -> Vec<BackendStatus> {
+    let status = |backend: &dyn GpuBackend, registered| BackendStatus {
+        name: backend.name(),
+        registered,
+        available: backend.is_available(),
+        quarantined: is_quarantined(backend.name()),
+    };
+    let mut backends: Vec<BackendStatus> = with_backend(|b| status(b, true)).into_iter().collect();
+    backends.push(status(&vulkan::VulkanBackend, false));
+    backends.push(status(&opencl::OpenCLBackend, false));
+    backends.push(status(&opengl::OpenGLBackend, false));
+    backends
+}
+
+/// Returns the name of the backend `with_usable_backend` would pick, if any.
+pub fn usable_backend_name() -> Option<&'static str> {
+    with_usable_backend(|backend: &dyn GpuBackend| backend.name())
+}
+
 /// Convenience: Executes addition on the active GPU backend.
 pub fn add(a: &[f32], b: &[f32], out: &mut [f32]) -> Result<(), String> {
     with_backend(|backend: &dyn GpuBackend| backend.add(a, b, out))
@@ -3,25 +3,48 @@ use crate::cpu::arch::CpuArch;
 use crate::cpu::bits::Bitness;
 
 /// Aggregated information about the host CPU.
-#[derive(Debug, Clone, serde::Serialize)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct CpuInfo {
     pub arch: CpuArch,
     pub bits: Bitness,
     pub features: CpuFeatures,
     pub cores: usize,
     pub logical_processors: usize,
+    /// Threads usable by this process after cgroup CPU quotas.
+    pub effective_threads: usize,
     pub brand: String,
 }
 
 /// Information about a detected GPU device.
-#[derive(Debug, Clone, serde::Serialize)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct GpuInfo {
     pub name: String,
     pub vendor: Option<String>,
+    /// Dedicated video memory, if the driver reports it.
     pub memory_gb: Option<f32>,
+    /// Compute API of the registered GPU backend, if any.
     pub api: Option<String>,
     pub is_integrated: bool,
     pub memory_shared: bool,
+    pub vendor_id: Option<u16>,
+    pub device_id: Option<u16>,
+    pub driver: Option<String>,
+}
+
+impl From<crate::detect::GpuDetails> for GpuInfo {
+    fn from(g: crate::detect::GpuDetails) -> Self {
+        Self {
+            name: g.name,
+            vendor: Some(g.vendor),
+            memory_gb: g.vram_bytes.map(|b| (b as f64 / 1024.0 / 1024.0 / 1024.0) as f32),
+            api: crate::gpu::get_active_backend_name(),
+            is_integrated: g.is_integrated,
+            memory_shared: g.shared_memory,
+            vendor_id: g.vendor_id,
+            device_id: g.device_id,
+            driver: g.driver,
+        }
+    }
 }
 
 /// Supported GPU APIs for detection records.
@@ -35,42 +58,41 @@ pub enum GpuApi {
 }
 
 /// The unified source of truth for system hardware in v3.0.0.
-#[derive(Debug, Clone, serde::Serialize)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct SystemInfo {
     pub cpu: CpuInfo,
+    /// The primary GPU, `gpus[0]`.
     pub gpu: Option<GpuInfo>,
+    pub gpus: Vec<GpuInfo>,
     pub available_memory_gb: f64,
+    /// Power source and battery state.
+    pub context: crate::detect::SystemContext,
 }
 
 impl SystemInfo {
-    /// Gathers all hardware information.
+    /// Gathers all hardware information from the hardware cache.
     pub fn detect() -> Self {
         let state = crate::detect::HardwareState::capture();
+        let statics = crate::detect::HardwareCache::global().static_facts();
 
         let cpu = CpuInfo {
             arch: state.cpu.arch,
-            bits: crate::cpu::bits::detect_bits(),
-            features: crate::cpu::features::CpuFeatures::detect(),
+            bits: statics.bits,
+            features: statics.features,
             cores: state.cpu.physical_cores,
             logical_processors: state.cpu.logical_threads,
+            effective_threads: statics.effective_threads,
             brand: state.cpu.brand,
         };
 
-        let gpu = state.gpu.map(|g| {
-            GpuInfo {
-                name: g.name,
-                vendor: Some(g.vendor),
-                memory_gb: Some(4.0),
-                api: Some("Auto-Detected-Driver".to_string()),
-                is_integrated: g.is_integrated,
-                memory_shared: g.shared_memory,
-            }
-        });
+        let gpus: Vec<GpuInfo> = state.gpus.into_iter().map(GpuInfo::from).collect();
 
         Self {
             cpu,
-            gpu,
-            available_memory_gb: (state.memory.total_kb as f64) / 1024.0 / 1024.0,
+            gpu: gpus.first().cloned(),
+            gpus,
+            available_memory_gb: (state.memory.available_kb as f64) / 1024.0 / 1024.0,
+            context: state.context,
         }
     }
 
@@ -75,6 +75,8 @@ pub mod gpu;
 pub mod async_ops;
 pub mod hardware;
 pub mod adaptive;
+pub mod report;
+pub mod metrics;
 pub mod error;
 
 
@@ -90,10 +92,12 @@ pub use gpu::GpuPolicy;
 pub use hardware::{SystemInfo, CpuInfo, GpuInfo, GpuApi};
 pub use system::{add, add_advanced, get_info, get_system_info, WorkloadHints};
 pub use adaptive::AdaptiveEngine;
+#[allow(deprecated)]
 pub use async_ops::add_async;
 pub use math::{SafeMath, ArithmeticResult, MathMode, AdaptiveMath};
-pub use profiler::{JsonExporter, CsvExporter, ReportExporter, get_profiler};
-pub use runtime::ArchXSched;
+pub use profiler::{JsonExporter, CsvExporter, ChromeTraceExporter, HtmlReportExporter, ReportExporter, get_profiler};
+pub use runtime::{ArchXSched, ArchXFuture};
+pub use core::scheduler::{TaskGraph, TaskSpec};
 pub use optimizer::scheduler::PowerMode;
 
 #[cfg(test)]
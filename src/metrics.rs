@@ -0,0 +1,184 @@
+//! Prometheus / OpenMetrics text exposition of ArchX runtime metrics.
+//!
+//! [`render_openmetrics`] produces the text; [`serve_metrics`] optionally
+//! serves it at `http://127.0.0.1:<port>/metrics` for scraping.
+
+use crate::adaptive::AdaptiveEngine;
+use crate::core::CoreEngine;
+use crate::profiler::get_profiler;
+use crate::runtime::Governor;
+use std::fmt::Write as _;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+/// Content type of [`render_openmetrics`] output.
+pub const OPENMETRICS_CONTENT_TYPE: &str = "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
+/// Upper bounds (seconds) of the operation duration histogram buckets.
+pub const DURATION_BUCKETS: &[f64] = &crate::profiler::counters::DURATION_BUCKETS;
+
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+fn family(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+}
+
+/// Renders the current metrics in the OpenMetrics text format.
+///
+/// Operation counters and histograms are cumulative over every span
+/// recorded while the profiler was enabled, see
+/// [`Profiler::operation_totals`](crate::profiler::Profiler::operation_totals).
+/// Thread usage comes from the global engine's `ResourceManager`.
+pub fn render_openmetrics() -> String {
+    let mut out = String::new();
+    let profiler = get_profiler();
+    let totals = profiler.operation_totals();
+
+    let labels = |name: &str, device: &str, backend: &str| {
+        format!("name=\"{}\",device=\"{}\",backend=\"{}\"", escape(name), escape(device), escape(backend))
+    };
+
+    family(&mut out, "archx_operations", "counter", "Profiled operations completed.");
+    for op in &totals {
+        let _ = writeln!(out, "archx_operations_total{{{}}} {}", labels(&op.name, &op.device, &op.backend), op.count);
+    }
+
+    family(&mut out, "archx_operation_duration_seconds", "histogram", "Duration of profiled operations.");
+    let _ = writeln!(out, "# UNIT archx_operation_duration_seconds seconds");
+    for op in &totals {
+        let labels = labels(&op.name, &op.device, &op.backend);
+        for (&le, count) in DURATION_BUCKETS.iter().zip(&op.buckets) {
+            let _ = writeln!(out, "archx_operation_duration_seconds_bucket{{{},le=\"{}\"}} {}", labels, le, count);
+        }
+        let _ = writeln!(out, "archx_operation_duration_seconds_bucket{{{},le=\"+Inf\"}} {}", labels, op.count);
+        let _ = writeln!(out, "archx_operation_duration_seconds_count{{{}}} {}", labels, op.count);
+        let _ = writeln!(out, "archx_operation_duration_seconds_sum{{{}}} {}", labels, op.sum.as_secs_f64());
+    }
+
+    family(&mut out, "archx_profiler_dropped_spans", "counter", "Spans overwritten before they were read.");
+    let _ = writeln!(out, "archx_profiler_dropped_spans_total {}", profiler.dropped());
+
+    family(&mut out, "archx_strategy_chosen", "counter", "Strategies chosen by the adaptive engine.");
+    for (strategy, count) in AdaptiveEngine::strategy_counts() {
+        let _ = writeln!(out, "archx_strategy_chosen_total{{strategy=\"{}\"}} {}", strategy, count);
+    }
+
+    let usage = CoreEngine::global().resources().get_current_usage();
+    family(&mut out, "archx_active_threads", "gauge", "Threads currently reserved through the resource manager.");
+    let _ = writeln!(out, "archx_active_threads {}", usage.active_threads);
+    family(&mut out, "archx_active_tasks", "gauge", "Tasks currently tracked by the resource manager.");
+    let _ = writeln!(out, "archx_active_tasks {}", usage.active_tasks);
+    family(&mut out, "archx_reserved_memory_bytes", "gauge", "Host memory currently reserved through the resource manager.");
+    let _ = writeln!(out, "# UNIT archx_reserved_memory_bytes bytes");
+    let _ = writeln!(out, "archx_reserved_memory_bytes {}", usage.reserved_memory_bytes);
+
+    family(&mut out, "archx_gpu_fallbacks", "counter", "GPU partitions recomputed on the CPU after a GPU failure.");
+    let _ = writeln!(out, "archx_gpu_fallbacks_total {}", crate::gpu::fallback_count());
+    family(&mut out, "archx_gpu_backend_quarantined", "gauge", "1 if the GPU backend is quarantined after repeated faults.");
+    let mut seen = Vec::new();
+    for backend in crate::gpu::backend_status() {
+        if !seen.contains(&backend.name) {
+            seen.push(backend.name);
+            let _ = writeln!(out, "archx_gpu_backend_quarantined{{backend=\"{}\"}} {}", escape(backend.name), u8::from(backend.quarantined));
+        }
+    }
+
+    let throttle = Governor::state();
+    family(&mut out, "archx_throttled", "gauge", "1 while the governor holds work back, by reason.");
+    let _ = writeln!(out, "archx_throttled{{reason=\"high_load\"}} {}", u8::from(throttle.high_load));
+    let _ = writeln!(out, "archx_throttled{{reason=\"low_power\"}} {}", u8::from(throttle.low_power));
+
+    out.push_str("# EOF\n");
+    out
+}
+
+/// A background HTTP listener serving [`render_openmetrics`] at `/metrics`.
+///
+/// Stops when dropped.
+pub struct MetricsServer {
+    addr: SocketAddr,
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+/// Serves metrics on `127.0.0.1:port`; port 0 picks a free port.
+///
+/// Only loopback is bound, so the endpoint is not reachable from other
+/// hosts. Requests are answered one at a time.
+pub fn serve_metrics(port: u16) -> io::Result<MetricsServer> {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))?;
+    let addr = listener.local_addr()?;
+    let stop = Arc::new(AtomicBool::new(false));
+
+    let handle = {
+        let stop = stop.clone();
+        std::thread::Builder::new().name("archx-metrics".into()).spawn(move || {
+            for stream in listener.incoming() {
+                if stop.load(Ordering::Acquire) {
+                    break;
+                }
+                if let Ok(stream) = stream {
+                    let _ = respond(stream);
+                }
+            }
+        })?
+    };
+
+    Ok(MetricsServer { addr, stop, handle: Some(handle) })
+}
+
+fn respond(mut stream: TcpStream) -> io::Result<()> {
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+    let mut request_line = String::new();
+    let mut reader = BufReader::new(stream.try_clone()?);
+    reader.read_line(&mut request_line)?;
+    // Drain the headers so the client sees a clean response.
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 2 {
+        header.clear();
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let (method, path) = (parts.next().unwrap_or(""), parts.next().unwrap_or(""));
+    let (status, content_type, body) = match (method, path.split('?').next().unwrap_or("")) {
+        ("GET", "/metrics") => ("200 OK", OPENMETRICS_CONTENT_TYPE, render_openmetrics()),
+        ("GET", _) => ("404 Not Found", "text/plain", "Not Found\n".to_string()),
+        _ => ("405 Method Not Allowed", "text/plain", "Method Not Allowed\n".to_string()),
+    };
+    write!(stream, "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status, content_type, body.len(), body)?;
+    stream.flush()
+}
+
+impl MetricsServer {
+    pub fn local_addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Stops the listener and waits for its thread.
+    pub fn shutdown(mut self) {
+        self.stop_listener();
+    }
+
+    fn stop_listener(&mut self) {
+        self.stop.store(true, Ordering::Release);
+        // Wake the blocking accept.
+        let _ = TcpStream::connect(self.addr);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for MetricsServer {
+    fn drop(&mut self) {
+        self.stop_listener();
+    }
+}
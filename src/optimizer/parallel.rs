@@ -1,50 +1,80 @@
 use std::thread;
 use crate::dispatch::select::Selector;
+use crate::error::ArchXResult;
 use crate::optimizer::scheduler::{Scheduler, WorkloadHints};
+use crate::runtime::control::ExecControl;
 
 /// Parallel implementation of the add operation with advanced scheduling.
-/// 
-/// WHY: v0.5 introduces cache-aware chunking and user-provided hints 
+///
+/// WHY: v0.5 introduces cache-aware chunking and user-provided hints
 /// to maximize throughput on high-core-count and AVX-512 systems.
 pub fn add_parallel_impl(a: &[f32], b: &[f32], out: &mut [f32], hints: &WorkloadHints) {
-    let _scope = crate::profiler::ProfileScope::new("Parallel Add", "CPU", "SIMD");
+    let _ = add_parallel_impl_with(a, b, out, hints, &ExecControl::none());
+}
+
+/// Controlled variant of [`add_parallel_impl`].
+///
+/// When `ctl` is active, each thread's range is processed in blocks of at most
+/// `CONTROLLED_CHUNK_MAX` elements and cancellation/deadlines are checked between
+/// blocks. Progress is reported once per completed block.
+pub fn add_parallel_impl_with(a: &[f32], b: &[f32], out: &mut [f32], hints: &WorkloadHints, ctl: &ExecControl) -> ArchXResult<()> {
     let len = a.len().min(b.len()).min(out.len());
-    
+    // Two inputs read and one output written per element.
+    let _scope = crate::profiler::ProfileScope::new("Parallel Add", "CPU", "SIMD").with_size(len, 3 * len * size_of::<f32>());
+
     // Determine the number of threads.
-    let num_threads = hints.thread_count.unwrap_or_else(|| {
-        thread::available_parallelism().map(|n| n.get()).unwrap_or(1)
-    });
-    
-    if num_threads <= 1 {
+    let num_threads = hints.thread_count.unwrap_or_else(crate::detect::effective_threads);
+
+    if num_threads <= 1 && !ctl.is_active() {
         Selector::dispatch_add(a, b, out);
-        return;
+        return Ok(());
     }
 
-    // Use the cache-aware scheduler to calculate optimal chunk size.
-    let chunk_size = Scheduler::calculate_chunk_size(len, num_threads, hints);
+    // Use the cache- and core-aware scheduler to split the work between threads.
+    let statics = crate::detect::HardwareCache::global().static_facts();
+    let plans = Scheduler::plan_chunks(len, num_threads.max(1), hints, &statics.caches, &statics.cores);
+    let chunk_size = plans.iter().map(|p| p.len).max().unwrap_or(1).max(1);
+    let block_size = ctl.chunk_size(chunk_size);
     let add_fn = Selector::get_add_fn();
 
+    // Total number of control blocks across all threads, for progress reporting.
+    let total_blocks = plans.iter().map(|p| p.len.div_ceil(block_size)).sum();
+    let tracker = ctl.tracker(total_blocks);
+
     // v1.1: Pre-calculate pointers to minimize loop overhead
     let a_ptr = a.as_ptr();
     let b_ptr = b.as_ptr();
 
+    let span = crate::profiler::SpanContext::current();
     thread::scope(|s| {
-        for i in 0..num_threads {
-            let offset = i * chunk_size;
-            if offset >= len { break; }
-            let count = (len - offset).min(chunk_size);
-            
+        let tracker = &tracker;
+        let mut handles = Vec::with_capacity(plans.len());
+        for plan in &plans {
+            let (offset, count, cpu) = (plan.offset, plan.len, plan.cpu);
+
             unsafe {
                 let ca = std::slice::from_raw_parts(a_ptr.add(offset), count);
                 let cb = std::slice::from_raw_parts(b_ptr.add(offset), count);
                 let out_ptr = out.as_mut_ptr().add(offset);
                 let co = std::slice::from_raw_parts_mut(out_ptr, count);
-                
-                s.spawn(move || {
-                    let _thread_scope = crate::profiler::ProfileScope::new("Parallel Chunk", "CPU", "SIMD");
-                    add_fn(ca, cb, co);
-                });
+
+                handles.push(s.spawn(move || -> ArchXResult<()> {
+                    if let Some(cpu) = cpu {
+                        crate::runtime::affinity::pin_current_thread(&[cpu]);
+                    }
+                    let _entered = span.enter();
+                    let _thread_scope = crate::profiler::ProfileScope::new("Parallel Chunk", "CPU", "SIMD").with_size(count, 3 * count * size_of::<f32>());
+                    for ((ba, bb), bo) in ca.chunks(block_size).zip(cb.chunks(block_size)).zip(co.chunks_mut(block_size)) {
+                        tracker.check()?;
+                        add_fn(ba, bb, bo);
+                        tracker.complete();
+                    }
+                    Ok(())
+                }));
             }
         }
-    });
+
+        handles.into_iter()
+            .try_for_each(|h| h.join().unwrap_or_else(|panic| std::panic::resume_unwind(panic)))
+    })
 }
@@ -1,3 +1,6 @@
+use crate::decision::Policy;
+use crate::detect::{CacheTopology, CoreTopology, SystemContext};
+
 #[derive(Debug, Clone, Copy, Default, serde::Serialize)]
 pub enum PowerMode {
     #[default]
@@ -6,6 +9,16 @@ pub enum PowerMode {
     PowerSaving,
 }
 
+impl PowerMode {
+    /// `Balanced` becomes `PowerSaving` while `context` is in low-power mode.
+    pub fn resolve(self, context: &SystemContext) -> PowerMode {
+        match self {
+            PowerMode::Balanced if context.is_low_power_mode => PowerMode::PowerSaving,
+            other => other,
+        }
+    }
+}
+
 /// Advanced performance tuning hints for ArchX operations.
 #[derive(Debug, Clone, Copy, serde::Serialize)]
 pub struct WorkloadHints {
@@ -19,6 +32,8 @@ pub struct WorkloadHints {
     pub prefer_hybrid: bool,
     pub enable_gpu: bool,
     pub policy: crate::decision::Policy,
+    /// Pin each worker thread to one CPU and size its chunk by that CPU's capacity.
+    pub pin_threads: bool,
 }
 
 impl Default for WorkloadHints {
@@ -32,10 +47,20 @@ impl Default for WorkloadHints {
             prefer_hybrid: false,
             enable_gpu: true,
             policy: crate::decision::Policy::Balanced,
+            pin_threads: false,
         }
     }
 }
 
+/// One worker thread's share of a parallel operation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChunkPlan {
+    pub offset: usize,
+    pub len: usize,
+    /// CPU the worker should be pinned to, if any.
+    pub cpu: Option<usize>,
+}
+
 /// Cache-aware scheduler for partitioning workloads.
 pub struct Scheduler;
 
@@ -47,23 +72,70 @@ impl Scheduler {
     /// - For large workloads, partition based on available parallelism 
     ///   and cache boundaries.
     pub fn calculate_chunk_size(len: usize, num_threads: usize, hints: &WorkloadHints) -> usize {
-        if len < 8192 && hints.thread_count.is_none() {
+        let statics = crate::detect::HardwareCache::global().static_facts();
+        Self::calculate_chunk_size_with(len, num_threads, hints, &statics.caches)
+    }
+
+    /// Same as [`Scheduler::calculate_chunk_size`] for an explicit cache topology.
+    ///
+    /// The floor is one L1-sized tile of the three `f32` streams of a binary
+    /// operation, and chunks are aligned to whole cache lines.
+    pub fn calculate_chunk_size_with(len: usize, num_threads: usize, hints: &WorkloadHints, topology: &CacheTopology) -> usize {
+        let line_elems = (topology.line_size() / std::mem::size_of::<f32>()).max(1);
+        let align = |n: usize| n.div_ceil(line_elems) * line_elems;
+        let floor = topology.tile_elements(1, std::mem::size_of::<f32>(), 3);
+
+        if len < floor && hints.thread_count.is_none() {
             return len; // Too small for threading overhead
         }
 
         if let Some(min) = hints.min_chunk_size {
-            return (min + 15) & !15;
+            return align(min);
+        }
+
+        let base_chunk = len.div_ceil(num_threads.max(1));
+        align(base_chunk.max(floor))
+    }
+
+    /// Splits `0..len` across `num_threads` workers.
+    ///
+    /// Without pinning, this is the equal split of [`Scheduler::calculate_chunk_size`].
+    /// On hybrid CPUs under `Policy::Performance`, workers are pinned to P-cores only.
+    /// With `hints.pin_threads`, workers are pinned fastest CPU first and each chunk
+    /// is proportional to its CPU's capacity, so E-cores finish with everyone else.
+    pub fn plan_chunks(len: usize, num_threads: usize, hints: &WorkloadHints, caches: &CacheTopology, cores: &CoreTopology) -> Vec<ChunkPlan> {
+        let prefer_performance = hints.policy == Policy::Performance && cores.is_hybrid();
+        let cpus = if prefer_performance { cores.performance_cpus() } else { cores.by_capacity() };
+
+        if !(prefer_performance || hints.pin_threads) || cpus.is_empty() {
+            let chunk = Self::calculate_chunk_size_with(len, num_threads, hints, caches).max(1);
+            return (0..num_threads.max(1))
+                .map(|i| i * chunk)
+                .take_while(|&offset| offset < len)
+                .map(|offset| ChunkPlan { offset, len: (len - offset).min(chunk), cpu: None })
+                .collect();
         }
 
-        // Adaptive heuristic: If system load is high (simulated), increase chunk size 
-        // to reduce task spawning frequency.
-        let base_chunk = len.div_ceil(num_threads);
-        
-        // Ensure floor (e.g., 8192 elements) to satisfy cache-line utilization
-        let floor = 8192;
-        let chunk_size = base_chunk.max(floor);
-        
-        // Align to 16 elements (64 bytes)
-        (chunk_size + 15) & !15
+        let threads = if prefer_performance { num_threads.min(cpus.len()) } else { num_threads }.max(1);
+        let equal = Self::calculate_chunk_size_with(len, threads, hints, caches).max(1);
+        let threads = threads.min(len.div_ceil(equal)).max(1);
+        let assigned: Vec<usize> = (0..threads).map(|i| cpus[i % cpus.len()]).collect();
+        let weights: Vec<u64> = assigned.iter().map(|&cpu| cores.capacity_of(cpu).max(1) as u64).collect();
+        let total: u64 = weights.iter().sum();
+
+        let line_elems = (caches.line_size() / std::mem::size_of::<f32>()).max(1);
+        let mut offset = 0;
+        let mut weight = 0;
+        assigned.iter().zip(&weights).enumerate().map(|(i, (&cpu, &w))| {
+            weight += w;
+            let end = if i + 1 == threads {
+                len
+            } else {
+                ((len as u64 * weight / total) as usize / line_elems * line_elems).clamp(offset, len)
+            };
+            let plan = ChunkPlan { offset, len: end - offset, cpu: Some(cpu) };
+            offset = end;
+            plan
+        }).filter(|plan| plan.len > 0).collect()
     }
 }
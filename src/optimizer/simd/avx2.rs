@@ -83,6 +83,54 @@ pub fn dot_avx2_impl(a: &[f32], b: &[f32]) -> f32 {
     result
 }
 
+/// AVX2 + FMA implementation of the dot product.
+///
+/// Uses four independent accumulators so consecutive FMAs do not stall on
+/// each other. Must only be selected when both `avx2` and `fma` are detected.
+pub fn dot_avx2_fma_impl(a: &[f32], b: &[f32]) -> f32 {
+    #[cfg(target_arch = "x86_64")]
+    {
+        // SAFETY: the dispatcher only installs this kernel when AVX2 and FMA are present.
+        unsafe { dot_avx2_fma_inner(a, b) }
+    }
+    #[cfg(not(target_arch = "x86_64"))]
+    {
+        dot_avx2_impl(a, b)
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2,fma")]
+unsafe fn dot_avx2_fma_inner(a: &[f32], b: &[f32]) -> f32 {
+    let len = a.len().min(b.len());
+    let unroll_factor = 32;
+    let main_loop_len = (len / unroll_factor) * unroll_factor;
+    let mut result = 0.0;
+
+    if main_loop_len > 0 {
+        let mut acc0 = _mm256_setzero_ps();
+        let mut acc1 = _mm256_setzero_ps();
+        let mut acc2 = _mm256_setzero_ps();
+        let mut acc3 = _mm256_setzero_ps();
+        for i in (0..main_loop_len).step_by(unroll_factor) {
+            let a_p = a.as_ptr().add(i);
+            let b_p = b.as_ptr().add(i);
+
+            acc0 = _mm256_fmadd_ps(_mm256_loadu_ps(a_p), _mm256_loadu_ps(b_p), acc0);
+            acc1 = _mm256_fmadd_ps(_mm256_loadu_ps(a_p.add(8)), _mm256_loadu_ps(b_p.add(8)), acc1);
+            acc2 = _mm256_fmadd_ps(_mm256_loadu_ps(a_p.add(16)), _mm256_loadu_ps(b_p.add(16)), acc2);
+            acc3 = _mm256_fmadd_ps(_mm256_loadu_ps(a_p.add(24)), _mm256_loadu_ps(b_p.add(24)), acc3);
+        }
+        let acc = _mm256_add_ps(_mm256_add_ps(acc0, acc1), _mm256_add_ps(acc2, acc3));
+        result = hsum_avx(acc);
+    }
+
+    for i in main_loop_len..len {
+        result += a[i] * b[i];
+    }
+    result
+}
+
 /// AVX2 implementation of the sum reduction.
 pub fn sum_avx2_impl(a: &[f32]) -> f32 {
     let len = a.len();
@@ -105,8 +153,8 @@ pub fn sum_avx2_impl(a: &[f32]) -> f32 {
         }
     }
 
-    for i in main_loop_len..len {
-        result += a[i];
+    for x in &a[main_loop_len..len] {
+        result += x;
     }
     result
 }
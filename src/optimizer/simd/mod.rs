@@ -17,6 +17,18 @@ pub struct SimdDispatcher {
     pub mul: BinaryOpFn,
     pub dot: DotFn,
     pub sum: ReductionFn,
+    /// Which implementation each operation was bound to.
+    pub kernels: KernelNames,
+}
+
+/// Names of the kernels selected per operation, e.g. `avx2` or `avx2+fma`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub struct KernelNames {
+    pub add: &'static str,
+    pub sub: &'static str,
+    pub mul: &'static str,
+    pub dot: &'static str,
+    pub sum: &'static str,
 }
 
 static DISPATCHER: OnceLock<SimdDispatcher> = OnceLock::new();
@@ -36,6 +48,7 @@ impl SimdDispatcher {
             mul: scalar_mul,
             dot: scalar_dot,
             sum: scalar_sum,
+            kernels: KernelNames { add: "scalar", sub: "scalar", mul: "scalar", dot: "scalar", sum: "scalar" },
         };
 
         #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
@@ -44,14 +57,26 @@ impl SimdDispatcher {
                 dispatcher.add = avx2::add_avx2_impl;
                 dispatcher.sub = avx2::sub_avx2_impl;
                 dispatcher.mul = avx2::mul_avx2_impl;
-                dispatcher.dot = avx2::dot_avx2_impl;
+                dispatcher.dot = if features.has_fma_avx2() {
+                    avx2::dot_avx2_fma_impl
+                } else {
+                    avx2::dot_avx2_impl
+                };
                 dispatcher.sum = avx2::sum_avx2_impl;
+                dispatcher.kernels = KernelNames {
+                    add: "avx2",
+                    sub: "avx2",
+                    mul: "avx2",
+                    dot: if features.has_fma_avx2() { "avx2+fma" } else { "avx2" },
+                    sum: "avx2",
+                };
             } else if features.sse2 {
                 dispatcher.add = sse2::add_sse2_impl;
                 dispatcher.sub = sse2::sub_sse2_impl;
                 dispatcher.mul = sse2::mul_sse2_impl;
                 dispatcher.dot = sse2::dot_sse2_impl;
                 dispatcher.sum = sse2::sum_sse2_impl;
+                dispatcher.kernels = KernelNames { add: "sse2", sub: "sse2", mul: "sse2", dot: "sse2", sum: "sse2" };
             }
         }
 
@@ -59,6 +84,7 @@ impl SimdDispatcher {
         {
             if features.neon {
                 dispatcher.add = neon::add_neon_impl;
+                dispatcher.kernels.add = "neon";
                 // Add more neon impls as they are developed
             }
         }
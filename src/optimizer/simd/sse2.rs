@@ -105,8 +105,8 @@ pub fn sum_sse2_impl(a: &[f32]) -> f32 {
         }
     }
 
-    for i in main_loop_len..len {
-        result += a[i];
+    for x in &a[main_loop_len..len] {
+        result += x;
     }
     result
 }
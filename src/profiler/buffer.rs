@@ -0,0 +1,158 @@
+//! Per-thread span storage for the profiler.
+//!
+//! Each thread writes its spans into its own fixed-size ring without taking a
+//! lock. Slots are guarded by a sequence number (a per-slot seqlock), so a
+//! snapshot taken from another thread copies every complete slot and skips
+//! the ones being overwritten at that moment.
+
+use crate::profiler::core::Metric;
+use std::collections::VecDeque;
+use std::sync::atomic::{fence, AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
+
+/// Spans kept per thread; older spans are overwritten.
+pub const THREAD_BUFFER_CAPACITY: usize = 4096;
+
+/// A completed span with interned names and timestamps relative to the profiler epoch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct RawSpan {
+    pub site: u32,
+    pub thread: u32,
+    pub id: u64,
+    /// Zero for root spans.
+    pub parent: u64,
+    pub start_nanos: u64,
+    pub duration_nanos: u64,
+    /// Size of the operation, zero if unknown.
+    pub elements: u64,
+    pub bytes: u64,
+}
+
+#[derive(Default)]
+struct Slot {
+    /// `2 * index + 1` while span `index` is written, `2 * index + 2` once complete.
+    seq: AtomicU64,
+    site_thread: AtomicU64,
+    id: AtomicU64,
+    parent: AtomicU64,
+    start_nanos: AtomicU64,
+    duration_nanos: AtomicU64,
+    elements: AtomicU64,
+    bytes: AtomicU64,
+}
+
+pub(crate) struct ThreadBuffer {
+    slots: Box<[Slot]>,
+    /// Number of spans ever written. Only the owning thread stores it.
+    head: AtomicU64,
+    /// Spans below this index were cleared.
+    tail: AtomicU64,
+    /// True while a live thread writes to this buffer. Buffers of exited
+    /// threads are handed to new threads, keeping their recorded spans.
+    owned: AtomicBool,
+    /// Metrics recorded with `Profiler::record`, which carry owned strings and metadata.
+    events: Mutex<VecDeque<Metric>>,
+}
+
+impl ThreadBuffer {
+    pub fn new() -> Self {
+        Self {
+            slots: (0..THREAD_BUFFER_CAPACITY).map(|_| Slot::default()).collect(),
+            head: AtomicU64::new(0),
+            tail: AtomicU64::new(0),
+            owned: AtomicBool::new(true),
+            events: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Claims a buffer whose thread has exited.
+    pub fn try_claim(&self) -> bool {
+        self.owned.compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed).is_ok()
+    }
+
+    pub fn release(&self) {
+        self.owned.store(false, Ordering::Release);
+    }
+
+    /// Appends a span. Must only be called by the owning thread.
+    pub fn push(&self, span: RawSpan) {
+        let index = self.head.load(Ordering::Relaxed);
+        let slot = &self.slots[(index % THREAD_BUFFER_CAPACITY as u64) as usize];
+
+        slot.seq.store(2 * index + 1, Ordering::Relaxed);
+        fence(Ordering::Release);
+        slot.site_thread.store(((span.site as u64) << 32) | span.thread as u64, Ordering::Relaxed);
+        slot.id.store(span.id, Ordering::Relaxed);
+        slot.parent.store(span.parent, Ordering::Relaxed);
+        slot.start_nanos.store(span.start_nanos, Ordering::Relaxed);
+        slot.duration_nanos.store(span.duration_nanos, Ordering::Relaxed);
+        slot.elements.store(span.elements, Ordering::Relaxed);
+        slot.bytes.store(span.bytes, Ordering::Relaxed);
+        slot.seq.store(2 * index + 2, Ordering::Release);
+        self.head.store(index + 1, Ordering::Release);
+    }
+
+    pub fn push_event(&self, metric: Metric) {
+        if let Ok(mut events) = self.events.lock() {
+            if events.len() == THREAD_BUFFER_CAPACITY {
+                events.pop_front();
+            }
+            events.push_back(metric);
+        }
+    }
+
+    /// Copies the retained spans into `out`.
+    pub fn read_spans(&self, out: &mut Vec<RawSpan>) {
+        let head = self.head.load(Ordering::Acquire);
+        let first = self.tail.load(Ordering::Acquire).max(head.saturating_sub(THREAD_BUFFER_CAPACITY as u64));
+
+        for index in first..head {
+            let slot = &self.slots[(index % THREAD_BUFFER_CAPACITY as u64) as usize];
+            let seq = slot.seq.load(Ordering::Acquire);
+            if seq != 2 * index + 2 {
+                continue;
+            }
+            let site_thread = slot.site_thread.load(Ordering::Relaxed);
+            let id = slot.id.load(Ordering::Relaxed);
+            let parent = slot.parent.load(Ordering::Relaxed);
+            let start_nanos = slot.start_nanos.load(Ordering::Relaxed);
+            let duration_nanos = slot.duration_nanos.load(Ordering::Relaxed);
+            let elements = slot.elements.load(Ordering::Relaxed);
+            let bytes = slot.bytes.load(Ordering::Relaxed);
+            fence(Ordering::Acquire);
+            if slot.seq.load(Ordering::Relaxed) != seq {
+                continue;
+            }
+            out.push(RawSpan {
+                site: (site_thread >> 32) as u32,
+                thread: site_thread as u32,
+                id,
+                parent,
+                start_nanos,
+                duration_nanos,
+                elements,
+                bytes,
+            });
+        }
+    }
+
+    pub fn read_events(&self, out: &mut Vec<Metric>) {
+        if let Ok(events) = self.events.lock() {
+            out.extend(events.iter().cloned());
+        }
+    }
+
+    /// Spans overwritten before they could be read.
+    pub fn dropped(&self) -> u64 {
+        // A concurrent `clear` can move `tail` past the `head` read here.
+        let retained = self.head.load(Ordering::Acquire).saturating_sub(self.tail.load(Ordering::Acquire));
+        retained.saturating_sub(THREAD_BUFFER_CAPACITY as u64)
+    }
+
+    pub fn clear(&self) {
+        self.tail.store(self.head.load(Ordering::Acquire), Ordering::Release);
+        if let Ok(mut events) = self.events.lock() {
+            events.clear();
+        }
+    }
+}
@@ -1,60 +1,349 @@
-use std::sync::{Mutex, OnceLock};
+use crate::profiler::buffer::{RawSpan, ThreadBuffer};
+use crate::profiler::counters::{Counts, OperationTotals, SiteCounter};
+use crate::profiler::energy::{self, EnergySource};
+use crate::profiler::export::HardwareFingerprint;
+use crate::profiler::reports::{ChromeTraceExporter, CsvExporter, HtmlReportExporter};
+use crate::profiler::sampler::system_sampler;
+use crate::profiler::stats::{operation_stats, OperationStats, BYTES_KEY, ELEMENTS_KEY};
+use crate::profiler::tree::summarize;
+use std::cell::{Cell, RefCell};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
 use std::time::{Duration, Instant};
-use std::collections::HashMap;
 
 /// Represents a single execution metric in v3.0.0.
-#[derive(Debug, Clone, serde::Serialize)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct Metric {
     pub name: String,
     pub backend: String,   // "SIMD", "Vulkan", "OpenCL"
     pub device: String,    // "CPU", "iGPU", "dGPU"
+    /// Offset from the profiler epoch, see [`Profiler::now`].
+    #[serde(default)]
+    pub start: Duration,
     pub duration: Duration,
-    pub energy_estimate: f32, // estimated power draw
+    /// Energy in joules, measured or estimated as told by `energy_source`.
+    pub energy_estimate: f32,
+    #[serde(default)]
+    pub energy_source: EnergySource,
+    /// OS thread id of the recording thread.
     pub thread_id: Option<usize>,
+    /// Unique within a profiler run; 0 until recorded.
+    #[serde(default)]
+    pub id: u64,
+    /// The span that was open on the recording thread, or the one entered
+    /// through [`SpanContext::enter`].
+    #[serde(default)]
+    pub parent_id: Option<u64>,
+    #[serde(default)]
     pub metadata: HashMap<String, String>,
 }
 
+impl Metric {
+    /// A metric that ended now. Thread, id and parent are filled in by [`Profiler::record`].
+    pub fn new(name: impl Into<String>, backend: impl Into<String>, device: impl Into<String>, duration: Duration) -> Self {
+        Self {
+            name: name.into(),
+            backend: backend.into(),
+            device: device.into(),
+            start: get_profiler().now().saturating_sub(duration),
+            duration,
+            energy_estimate: 0.0,
+            energy_source: EnergySource::Estimate,
+            thread_id: None,
+            id: 0,
+            parent_id: None,
+            metadata: HashMap::new(),
+        }
+    }
+}
+
+fn size_metadata(elements: u64, bytes: u64) -> HashMap<String, String> {
+    let mut metadata = HashMap::new();
+    if elements > 0 {
+        metadata.insert(ELEMENTS_KEY.to_string(), elements.to_string());
+    }
+    if bytes > 0 {
+        metadata.insert(BYTES_KEY.to_string(), bytes.to_string());
+    }
+    metadata
+}
+
+/// Fallback when RAPL counters are not sampled (see [`energy`]):
+/// Energy = Duration * BasePower (CPU ~45W, iGPU ~15W)
+fn estimate_energy(device: &str, duration: Duration) -> f32 {
+    let energy_multiplier = if device.contains("GPU") { 15.0 } else { 45.0 };
+    duration.as_secs_f32() * energy_multiplier
+}
+
+/// The static names of one `ProfileScope` call site.
+#[derive(Debug, Clone, Copy)]
+struct Site {
+    name: &'static str,
+    device: &'static str,
+    backend: &'static str,
+}
+
+/// Identity of a site: address and length of each name.
+type SiteKey = [usize; 6];
+
+impl Site {
+    fn key(&self) -> SiteKey {
+        [
+            self.name.as_ptr() as usize, self.name.len(),
+            self.device.as_ptr() as usize, self.device.len(),
+            self.backend.as_ptr() as usize, self.backend.len(),
+        ]
+    }
+}
+
+#[derive(Default)]
+struct SiteTable {
+    sites: Vec<Site>,
+    ids: HashMap<SiteKey, u32>,
+}
+
+type OperationKey = (String, String, String);
+
+/// Cumulative counts behind [`Profiler::operation_totals`].
+#[derive(Default)]
+struct CounterRegistry {
+    /// Per-site counters of live threads.
+    live: Vec<(u32, Arc<SiteCounter>)>,
+    /// Counts of exited threads, by site.
+    retired: HashMap<u32, Counts>,
+    /// Metrics passed to `record`, by (name, device, backend).
+    events: HashMap<OperationKey, Counts>,
+}
+
 /// Global registry for execution profiling.
+///
+/// Recording is gated by an atomic flag, so a disabled profiler costs one
+/// branch per scope. Enabled scopes write into a per-thread ring buffer
+/// without locking; [`Profiler::get_snapshot`] merges all buffers.
 pub struct Profiler {
-    metrics: Mutex<Vec<Metric>>,
-    is_enabled: Mutex<bool>,
+    enabled: AtomicBool,
+    epoch: Instant,
+    buffers: Mutex<Vec<Arc<ThreadBuffer>>>,
+    sites: Mutex<SiteTable>,
+    counters: Mutex<CounterRegistry>,
+}
+
+/// The calling thread's buffer and its cache of interned sites.
+///
+/// Programs have a handful of scope sites, so the cache is a list scanned by
+/// address, which is much cheaper than hashing.
+struct Local {
+    buffer: Arc<ThreadBuffer>,
+    /// OS thread id.
+    thread: u32,
+    /// Span ids of this thread are `id_base | n`, so ids never collide
+    /// across threads without a shared counter.
+    id_base: u64,
+    next_id: Cell<u64>,
+    /// Innermost open span, 0 if none.
+    current: Cell<u64>,
+    sites: RefCell<Vec<(SiteKey, u32, Arc<SiteCounter>)>>,
+}
+
+impl Local {
+    fn register() -> Self {
+        static NEXT_THREAD: AtomicU32 = AtomicU32::new(0);
+        let profiler = get_profiler();
+        let buffer = {
+            let mut buffers = profiler.buffers.lock().unwrap_or_else(|e| e.into_inner());
+            match buffers.iter().find(|b| b.try_claim()) {
+                Some(free) => free.clone(),
+                None => {
+                    let buffer = Arc::new(ThreadBuffer::new());
+                    buffers.push(buffer.clone());
+                    buffer
+                }
+            }
+        };
+        let index = NEXT_THREAD.fetch_add(1, Ordering::Relaxed);
+        Self {
+            buffer,
+            thread: os_thread_id().unwrap_or(index),
+            id_base: (index as u64 + 1) << 40,
+            next_id: Cell::new(0),
+            current: Cell::new(0),
+            sites: RefCell::new(Vec::new()),
+        }
+    }
+
+    fn next_id(&self) -> u64 {
+        let n = self.next_id.get() + 1;
+        self.next_id.set(n);
+        self.id_base | n
+    }
+
+    /// Interns `site` and counts a span of `nanos` for it.
+    fn observe(&self, site: Site, nanos: u64) -> u32 {
+        let key = site.key();
+        if let Some((_, id, counter)) = self.sites.borrow().iter().find(|(k, _, _)| *k == key) {
+            counter.observe(nanos);
+            return *id;
+        }
+        let profiler = get_profiler();
+        let id = profiler.intern(site, key);
+        let counter = Arc::new(SiteCounter::default());
+        counter.observe(nanos);
+        profiler.counters.lock().unwrap_or_else(|e| e.into_inner()).live.push((id, counter.clone()));
+        self.sites.borrow_mut().push((key, id, counter));
+        id
+    }
+}
+
+impl Drop for Local {
+    fn drop(&mut self) {
+        self.buffer.release();
+        // Keep the thread's counts after its counters go away.
+        let mut counters = get_profiler().counters.lock().unwrap_or_else(|e| e.into_inner());
+        for (_, id, counter) in self.sites.borrow().iter() {
+            counters.live.retain(|(_, c)| !Arc::ptr_eq(c, counter));
+            counters.retired.entry(*id).or_default().merge(&counter.counts());
+        }
+    }
+}
+
+thread_local! {
+    static LOCAL: Local = Local::register();
+}
+
+#[cfg(target_os = "linux")]
+fn os_thread_id() -> Option<u32> {
+    // SAFETY: gettid takes no arguments and cannot fail.
+    Some(unsafe { libc::syscall(libc::SYS_gettid) } as u32)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn os_thread_id() -> Option<u32> {
+    None
 }
 
 impl Profiler {
     fn new() -> Self {
         Self {
-            metrics: Mutex::new(Vec::new()),
-            is_enabled: Mutex::new(false),
+            enabled: AtomicBool::new(false),
+            epoch: Instant::now(),
+            buffers: Mutex::new(Vec::new()),
+            sites: Mutex::new(SiteTable::default()),
+            counters: Mutex::new(CounterRegistry::default()),
         }
     }
 
     pub fn set_enabled(&self, enabled: bool) {
-        if let Ok(mut lock) = self.is_enabled.lock() {
-            *lock = enabled;
-        }
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    /// Time since the profiler was created; every `Metric::start` is relative to it.
+    pub fn now(&self) -> Duration {
+        self.epoch.elapsed()
     }
 
     pub fn record(&self, metric: Metric) {
-        if let Ok(enabled) = self.is_enabled.lock() {
-            if !*enabled { return; }
+        if !self.is_enabled() {
+            return;
         }
-        if let Ok(mut lock) = self.metrics.lock() {
-            lock.push(metric);
+        if let Ok(mut counters) = self.counters.lock() {
+            let key = (metric.name.clone(), metric.device.clone(), metric.backend.clone());
+            counters.events.entry(key).or_default().observe(metric.duration.as_nanos() as u64);
         }
+        let _ = LOCAL.try_with(|local| {
+            let mut metric = metric;
+            metric.thread_id.get_or_insert(local.thread as usize);
+            if metric.id == 0 {
+                metric.id = local.next_id();
+            }
+            if metric.parent_id.is_none() && local.current.get() != 0 {
+                metric.parent_id = Some(local.current.get());
+            }
+            local.buffer.push_event(metric);
+        });
     }
 
     pub fn clear(&self) {
-        if let Ok(mut lock) = self.metrics.lock() {
-            lock.clear();
+        for buffer in self.buffers() {
+            buffer.clear();
         }
     }
 
+    /// All retained metrics of every thread, ordered by start time.
     pub fn get_snapshot(&self) -> Vec<Metric> {
-        if let Ok(lock) = self.metrics.lock() {
-            lock.clone()
-        } else {
-            Vec::new()
+        let buffers = self.buffers();
+        let mut spans = Vec::new();
+        let mut metrics = Vec::new();
+        for buffer in &buffers {
+            buffer.read_spans(&mut spans);
+            buffer.read_events(&mut metrics);
         }
+
+        let sites = self.sites.lock().map(|t| t.sites.clone()).unwrap_or_default();
+        metrics.extend(spans.into_iter().filter_map(|span| {
+            let site = sites.get(span.site as usize)?;
+            let duration = Duration::from_nanos(span.duration_nanos);
+            Some(Metric {
+                name: site.name.to_string(),
+                backend: site.backend.to_string(),
+                device: site.device.to_string(),
+                start: Duration::from_nanos(span.start_nanos),
+                duration,
+                energy_estimate: estimate_energy(site.device, duration),
+                energy_source: EnergySource::Estimate,
+                thread_id: Some(span.thread as usize),
+                id: span.id,
+                parent_id: (span.parent != 0).then_some(span.parent),
+                metadata: size_metadata(span.elements, span.bytes),
+            })
+        }));
+        energy::attribute(&mut metrics);
+        metrics.sort_by_key(|m| m.start);
+        metrics
+    }
+
+    /// Spans lost because a thread recorded more than
+    /// [`THREAD_BUFFER_CAPACITY`](crate::profiler::buffer::THREAD_BUFFER_CAPACITY)
+    /// of them since the last `clear`.
+    pub fn dropped(&self) -> u64 {
+        self.buffers().iter().map(|b| b.dropped()).sum()
+    }
+
+    /// Span counts and duration buckets per operation since the process
+    /// started, ordered by name. Unlike the snapshot these only grow:
+    /// `clear()` and ring buffer wraparound do not reset them.
+    pub fn operation_totals(&self) -> Vec<OperationTotals> {
+        let sites = self.sites.lock().map(|t| t.sites.clone()).unwrap_or_default();
+        let counters = self.counters.lock().unwrap_or_else(|e| e.into_inner());
+        let mut totals: BTreeMap<OperationKey, Counts> = counters.events.iter().map(|(k, c)| (k.clone(), *c)).collect();
+        let by_site = counters.live.iter().map(|(id, c)| (*id, c.counts()))
+            .chain(counters.retired.iter().map(|(id, c)| (*id, *c)));
+        for (id, counts) in by_site {
+            let Some(site) = sites.get(id as usize) else { continue };
+            let key = (site.name.to_string(), site.device.to_string(), site.backend.to_string());
+            totals.entry(key).or_default().merge(&counts);
+        }
+        totals.into_iter().map(|((name, device, backend), counts)| OperationTotals::new(name, device, backend, &counts)).collect()
+    }
+
+    fn buffers(&self) -> Vec<Arc<ThreadBuffer>> {
+        self.buffers.lock().map(|b| b.clone()).unwrap_or_default()
+    }
+
+    fn intern(&self, site: Site, key: SiteKey) -> u32 {
+        let mut table = self.sites.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(&id) = table.ids.get(&key) {
+            return id;
+        }
+        let id = table.sites.len() as u32;
+        table.sites.push(site);
+        table.ids.insert(key, id);
+        id
     }
 
     /// Exports metrics as a JSON string.
@@ -62,37 +351,72 @@ impl Profiler {
         serde_json::to_string_pretty(&self.get_snapshot()).unwrap_or_else(|_| "[]".into())
     }
 
-    /// Exports metrics as a professional CSV string with device info.
+    /// Latency percentiles and throughput per (name, device, backend).
+    pub fn stats(&self) -> Vec<OperationStats> {
+        operation_stats(&self.get_snapshot())
+    }
+
+    /// Exports metrics as a Chrome trace (JSON) for Perfetto or `chrome://tracing`,
+    /// with counter tracks from the running [`system_sampler`], if any.
+    pub fn to_chrome_trace(&self) -> String {
+        let counters = system_sampler().map(|s| s.counters()).unwrap_or_default();
+        ChromeTraceExporter::new().with_counters(counters).trace(&self.get_snapshot()).to_string()
+    }
+
+    /// Renders a self-contained HTML report of the snapshot and this
+    /// machine's hardware, see [`HtmlReportExporter`].
+    pub fn to_html(&self) -> String {
+        HtmlReportExporter::new().with_hardware(HardwareFingerprint::current()).render(&self.get_snapshot())
+    }
+
+    /// Exports metrics as an RFC 4180 CSV string, see [`CsvExporter`].
     pub fn to_csv(&self) -> String {
-        let snapshot = self.get_snapshot();
-        let mut csv = String::from("p_name,backend,device,duration_ms,energy_est,thread_id\n");
-        for m in snapshot {
-            csv.push_str(&format!("{},{},{},{:.4},{:.2},{:?}\n", 
-                m.name, m.backend, m.device, m.duration.as_secs_f64() * 1000.0, m.energy_estimate, m.thread_id));
-        }
-        csv
+        CsvExporter::render(&self.get_snapshot()).unwrap_or_default()
     }
 
-    /// Prints a professional, human-readable performance summary.
+    /// Prints a professional, human-readable performance summary: the call
+    /// tree with total and self time per row, then latency percentiles and
+    /// throughput per operation.
     pub fn print_summary(&self) {
-        let snapshot = self.get_snapshot();
-        if snapshot.is_empty() {
+        let rows = summarize(&self.get_snapshot());
+        if rows.is_empty() {
             println!("\x1b[33m[ArchX Profiler]\x1b[0m No metrics collected.");
             return;
         }
 
-        println!("\n\x1b[1;36m┌── ArchX Sovereign v3.0 Profile ───────────────────────────────┐\x1b[0m");
-        println!("│ \x1b[1m{:<18}\x1b[0m │ \x1b[1m{:<10}\x1b[0m │ \x1b[1m{:<10}\x1b[0m │ \x1b[1m{:<12}\x1b[0m │", "Task", "Device", "Backend", "Time (ms)");
-        println!("├────────────────────┼────────────┼───────────┼────────────────┤");
-        for m in snapshot {
-            println!("│ {:<18} │ {:<10} │ {:<9} │ {:>10.4} ms │", 
-                m.name, 
-                m.device,
-                m.backend,
-                m.duration.as_secs_f64() * 1000.0
+        println!("\n\x1b[1;36m┌── ArchX Sovereign v3.0 Profile ────────────────────────────────────────────────────────────┐\x1b[0m");
+        println!("│ \x1b[1m{:<28}\x1b[0m │ \x1b[1m{:<6}\x1b[0m │ \x1b[1m{:<9}\x1b[0m │ \x1b[1m{:>6}\x1b[0m │ \x1b[1m{:>13}\x1b[0m │ \x1b[1m{:>13}\x1b[0m │",
+            "Task", "Device", "Backend", "Calls", "Total (ms)", "Self (ms)");
+        println!("├──────────────────────────────┼────────┼───────────┼────────┼───────────────┼───────────────┤");
+        for row in rows {
+            let task = format!("{}{}", "  ".repeat(row.depth), row.name);
+            println!("│ {:<28} │ {:<6} │ {:<9} │ {:>6} │ {:>13.4} │ {:>13.4} │",
+                task,
+                row.device,
+                row.backend,
+                row.calls,
+                row.total.as_secs_f64() * 1000.0,
+                row.self_time.as_secs_f64() * 1000.0
             );
         }
-        println!("\x1b[1;36m└────────────────────┴────────────┴───────────┴────────────────┘\x1b[0m\n");
+        println!("\x1b[1;36m└──────────────────────────────┴────────┴───────────┴────────┴───────────────┴───────────────┘\x1b[0m");
+
+        let ms = |d: Duration| d.as_secs_f64() * 1000.0;
+        println!("\n\x1b[1m{:<28} {:>8} {:>11} {:>11} {:>11} {:>11} {:>11} {:>12} {:>9}\x1b[0m",
+            "Operation", "Calls", "Mean (ms)", "p50 (ms)", "p99 (ms)", "p99.9 (ms)", "Max (ms)", "Elem/s", "GB/s");
+        for op in self.stats() {
+            println!("{:<28} {:>8} {:>11.4} {:>11.4} {:>11.4} {:>11.4} {:>11.4} {:>12} {:>9}",
+                format!("{} [{}]", op.name, op.device),
+                op.count,
+                ms(op.mean),
+                ms(op.p50),
+                ms(op.p99),
+                ms(op.p999),
+                ms(op.max),
+                op.elements_per_sec.map(|r| format!("{:.3e}", r)).unwrap_or_else(|| "-".to_string()),
+                op.gb_per_sec.map(|r| format!("{:.2}", r)).unwrap_or_else(|| "-".to_string()));
+        }
+        println!();
     }
 }
 
@@ -102,43 +426,122 @@ pub fn get_profiler() -> &'static Profiler {
     GLOBAL_PROFILER.get_or_init(Profiler::new)
 }
 
+/// The innermost open span of a thread.
+///
+/// Spans opened on another thread start without a parent. To keep the tree
+/// intact across `thread::scope` or rayon workers, capture the context before
+/// spawning and enter it inside the worker:
+///
+/// ```
+/// use archx::profiler::{ProfileScope, SpanContext};
+///
+/// let _scope = ProfileScope::new("Batch", "CPU", "SIMD");
+/// let context = SpanContext::current();
+/// std::thread::scope(|s| {
+///     s.spawn(|| {
+///         let _entered = context.enter();
+///         let _chunk = ProfileScope::new("Chunk", "CPU", "SIMD");
+///     });
+/// });
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SpanContext(u64);
+
+impl SpanContext {
+    /// The calling thread's open span; empty while the profiler is disabled.
+    pub fn current() -> Self {
+        if !get_profiler().is_enabled() {
+            return Self(0);
+        }
+        Self(LOCAL.try_with(|local| local.current.get()).unwrap_or(0))
+    }
+
+    pub fn id(&self) -> Option<u64> {
+        (self.0 != 0).then_some(self.0)
+    }
+
+    /// Makes this span the parent of spans opened on the calling thread until
+    /// the guard is dropped.
+    pub fn enter(self) -> SpanGuard {
+        if self.0 == 0 {
+            return SpanGuard { previous: None };
+        }
+        let previous = LOCAL.try_with(|local| local.current.replace(self.0)).ok();
+        SpanGuard { previous }
+    }
+}
+
+/// Restores the previous parent span when dropped; see [`SpanContext::enter`].
+pub struct SpanGuard {
+    previous: Option<u64>,
+}
+
+impl Drop for SpanGuard {
+    fn drop(&mut self) {
+        if let Some(previous) = self.previous {
+            let _ = LOCAL.try_with(|local| local.current.set(previous));
+        }
+    }
+}
+
 /// A scope-based timer for profiling.
+///
+/// The scope becomes the parent of every span opened on the same thread
+/// while it is alive.
 pub struct ProfileScope {
-    name: String,
-    backend: String,
-    device: String,
-    start: Instant,
-    thread_id: Option<usize>,
+    site: Site,
+    id: u64,
+    parent: u64,
+    elements: u64,
+    bytes: u64,
+    start: Option<Instant>,
 }
 
 impl ProfileScope {
-    pub fn new(name: &str, device: &str, backend: &str) -> Self {
-        Self {
-            name: name.to_string(),
-            device: device.to_string(),
-            backend: backend.to_string(),
-            start: Instant::now(),
-            thread_id: None,
+    #[inline]
+    pub fn new(name: &'static str, device: &'static str, backend: &'static str) -> Self {
+        let site = Site { name, device, backend };
+        if !get_profiler().is_enabled() {
+            return Self { site, id: 0, parent: 0, elements: 0, bytes: 0, start: None };
+        }
+        match LOCAL.try_with(|local| {
+            let id = local.next_id();
+            (id, local.current.replace(id))
+        }) {
+            Ok((id, parent)) => Self { site, id, parent, elements: 0, bytes: 0, start: Some(Instant::now()) },
+            Err(_) => Self { site, id: 0, parent: 0, elements: 0, bytes: 0, start: None },
         }
     }
+
+    /// Records how many elements and bytes the scope processes, for the
+    /// throughput columns of [`Profiler::stats`].
+    #[inline]
+    pub fn with_size(mut self, elements: usize, bytes: usize) -> Self {
+        self.elements = elements as u64;
+        self.bytes = bytes as u64;
+        self
+    }
 }
 
 impl Drop for ProfileScope {
+    #[inline]
     fn drop(&mut self) {
-        let duration = self.start.elapsed();
-        // Estimated energy profile (placeholder logic)
-        // Energy = Duration * BasePower (CPU ~45W, iGPU ~15W)
-        let energy_multiplier = if self.device.contains("GPU") { 15.0 } else { 45.0 };
-        let energy_estimate = duration.as_secs_f32() * energy_multiplier;
-
-        get_profiler().record(Metric {
-            name: self.name.clone(),
-            backend: self.backend.clone(),
-            device: self.device.clone(),
-            duration,
-            energy_estimate,
-            thread_id: self.thread_id,
-            metadata: HashMap::new(),
+        let Some(start) = self.start else { return };
+        let end = Instant::now();
+        let epoch = get_profiler().epoch;
+        let duration_nanos = end.saturating_duration_since(start).as_nanos() as u64;
+        let _ = LOCAL.try_with(|local| {
+            local.current.set(self.parent);
+            local.buffer.push(RawSpan {
+                site: local.observe(self.site, duration_nanos),
+                thread: local.thread,
+                id: self.id,
+                parent: self.parent,
+                start_nanos: start.saturating_duration_since(epoch).as_nanos() as u64,
+                duration_nanos,
+                elements: self.elements,
+                bytes: self.bytes,
+            });
         });
     }
 }
@@ -0,0 +1,105 @@
+//! Cumulative per-operation counts for monitoring.
+//!
+//! The snapshot ring buffers forget old spans and are emptied by `clear()`,
+//! so counters and histograms exported to Prometheus would go backwards.
+//! These totals are updated when a span is recorded and only ever grow.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Upper bounds (seconds) of the operation duration histogram buckets.
+pub const DURATION_BUCKETS: [f64; 8] = [1e-6, 1e-5, 1e-4, 1e-3, 1e-2, 0.1, 1.0, 10.0];
+
+const BUCKET_NANOS: [u64; 8] = {
+    let mut nanos = [0u64; 8];
+    let mut i = 0;
+    while i < nanos.len() {
+        nanos[i] = (DURATION_BUCKETS[i] * 1e9) as u64;
+        i += 1;
+    }
+    nanos
+};
+
+/// Index of the first bucket holding `nanos`, `len()` if above all of them.
+fn bucket(nanos: u64) -> usize {
+    BUCKET_NANOS.iter().position(|&bound| nanos <= bound).unwrap_or(BUCKET_NANOS.len())
+}
+
+/// Plain counts of one operation, merged from several sources.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) struct Counts {
+    count: u64,
+    sum_nanos: u64,
+    /// Calls per bucket (not cumulative); the last slot is above all bounds.
+    buckets: [u64; 9],
+}
+
+impl Counts {
+    pub(crate) fn observe(&mut self, nanos: u64) {
+        self.count += 1;
+        self.sum_nanos = self.sum_nanos.saturating_add(nanos);
+        self.buckets[bucket(nanos)] += 1;
+    }
+
+    pub(crate) fn merge(&mut self, other: &Counts) {
+        self.count += other.count;
+        self.sum_nanos = self.sum_nanos.saturating_add(other.sum_nanos);
+        for (a, b) in self.buckets.iter_mut().zip(&other.buckets) {
+            *a += b;
+        }
+    }
+}
+
+/// Counts of one site on one thread.
+///
+/// Only the owning thread writes, so updates are a load and a store rather
+/// than a locked read-modify-write; readers on other threads see a value
+/// that is at most one span old.
+#[derive(Debug, Default)]
+pub(crate) struct SiteCounter {
+    sum_nanos: AtomicU64,
+    buckets: [AtomicU64; 9],
+}
+
+impl SiteCounter {
+    #[inline]
+    pub(crate) fn observe(&self, nanos: u64) {
+        let bump = |cell: &AtomicU64, by: u64| cell.store(cell.load(Ordering::Relaxed).wrapping_add(by), Ordering::Relaxed);
+        bump(&self.buckets[bucket(nanos)], 1);
+        bump(&self.sum_nanos, nanos);
+    }
+
+    pub(crate) fn counts(&self) -> Counts {
+        let mut counts = Counts { count: 0, sum_nanos: self.sum_nanos.load(Ordering::Relaxed), buckets: [0; 9] };
+        for (slot, cell) in counts.buckets.iter_mut().zip(&self.buckets) {
+            *slot = cell.load(Ordering::Relaxed);
+        }
+        // Derived from the buckets so the +Inf bucket always equals the count.
+        counts.count = counts.buckets.iter().sum();
+        counts
+    }
+}
+
+/// Everything recorded for one operation since the process started.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct OperationTotals {
+    pub name: String,
+    pub device: String,
+    pub backend: String,
+    pub count: u64,
+    pub sum: Duration,
+    /// Calls at most as long as each of [`DURATION_BUCKETS`] (cumulative).
+    pub buckets: Vec<u64>,
+}
+
+impl OperationTotals {
+    pub(crate) fn new(name: String, device: String, backend: String, counts: &Counts) -> Self {
+        let buckets = counts.buckets[..DURATION_BUCKETS.len()].iter()
+            .scan(0, |total, &n| {
+                *total += n;
+                Some(*total)
+            })
+            .collect();
+        Self { name, device, backend, count: counts.count, sum: Duration::from_nanos(counts.sum_nanos), buckets }
+    }
+}
@@ -0,0 +1,229 @@
+//! Comparison of two profiler exports to catch performance regressions.
+
+use crate::profiler::core::Metric;
+use crate::profiler::export::ProfileExport;
+use crate::profiler::stats::{operation_stats, OperationStats};
+use serde::Serialize;
+use std::fmt;
+use std::time::Duration;
+
+/// When a change of an operation counts as a regression.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct DiffThresholds {
+    /// Mean slowdown (percent) above which an operation regresses.
+    pub max_regression_pct: f64,
+    /// Significance level of the Mann-Whitney U test.
+    pub alpha: f64,
+    /// Calls needed in both runs to test significance.
+    pub min_samples: usize,
+}
+
+impl Default for DiffThresholds {
+    fn default() -> Self {
+        Self { max_regression_pct: 10.0, alpha: 0.05, min_samples: 5 }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum DiffStatus {
+    /// Significantly slower by more than the threshold.
+    Regressed,
+    /// Significantly faster by more than the threshold.
+    Improved,
+    Unchanged,
+    /// Changed by more than the threshold, but with too few calls to tell.
+    Inconclusive,
+    /// Only in the candidate.
+    Added,
+    /// Only in the baseline.
+    Removed,
+}
+
+/// Summary of one operation in one run.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RunSummary {
+    pub count: u64,
+    pub mean: Duration,
+    pub p50: Duration,
+    pub p99: Duration,
+}
+
+impl From<&OperationStats> for RunSummary {
+    fn from(stats: &OperationStats) -> Self {
+        Self { count: stats.count, mean: stats.mean, p50: stats.p50, p99: stats.p99 }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OperationDiff {
+    pub name: String,
+    pub device: String,
+    pub backend: String,
+    pub baseline: Option<RunSummary>,
+    pub candidate: Option<RunSummary>,
+    /// Change of the mean duration in percent; positive is slower.
+    pub delta_pct: Option<f64>,
+    /// Two-sided p-value of the Mann-Whitney U test on the durations.
+    pub p_value: Option<f64>,
+    pub status: DiffStatus,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProfileDiff {
+    pub thresholds: DiffThresholds,
+    /// Why the runs may not be comparable, e.g. different CPUs.
+    pub hardware_warnings: Vec<String>,
+    pub operations: Vec<OperationDiff>,
+}
+
+fn durations(metrics: &[Metric], stats: &OperationStats) -> Vec<f64> {
+    metrics.iter()
+        .filter(|m| m.name == stats.name && m.device == stats.device && m.backend == stats.backend)
+        .map(|m| m.duration.as_secs_f64())
+        .collect()
+}
+
+impl ProfileDiff {
+    pub fn compare(baseline: &ProfileExport, candidate: &ProfileExport, thresholds: &DiffThresholds) -> Self {
+        let hardware_warnings = match (&baseline.fingerprint, &candidate.fingerprint) {
+            (Some(a), Some(b)) => a.differences(b).into_iter().map(|d| format!("different hardware: {}", d)).collect(),
+            (None, _) => vec!["baseline has no hardware fingerprint".to_string()],
+            (_, None) => vec!["candidate has no hardware fingerprint".to_string()],
+        };
+
+        let base_stats = operation_stats(&baseline.metrics);
+        let cand_stats = operation_stats(&candidate.metrics);
+        let same = |a: &OperationStats, b: &OperationStats| a.name == b.name && a.device == b.device && a.backend == b.backend;
+
+        let mut operations: Vec<OperationDiff> = base_stats.iter().map(|base| {
+            let cand = cand_stats.iter().find(|c| same(base, c));
+            let mut diff = OperationDiff {
+                name: base.name.clone(),
+                device: base.device.clone(),
+                backend: base.backend.clone(),
+                baseline: Some(base.into()),
+                candidate: cand.map(RunSummary::from),
+                delta_pct: None,
+                p_value: None,
+                status: DiffStatus::Removed,
+            };
+            if let Some(cand) = cand {
+                let base_mean = base.mean.as_secs_f64();
+                let delta = if base_mean > 0.0 { (cand.mean.as_secs_f64() - base_mean) / base_mean * 100.0 } else { 0.0 };
+                let p_value = (base.count.min(cand.count) as usize >= thresholds.min_samples)
+                    .then(|| mann_whitney_p(&durations(&baseline.metrics, base), &durations(&candidate.metrics, cand)));
+                let exceeds = delta.abs() > thresholds.max_regression_pct;
+                diff.status = match p_value {
+                    None if exceeds => DiffStatus::Inconclusive,
+                    Some(p) if exceeds && p < thresholds.alpha => {
+                        if delta > 0.0 { DiffStatus::Regressed } else { DiffStatus::Improved }
+                    }
+                    _ => DiffStatus::Unchanged,
+                };
+                diff.delta_pct = Some(delta);
+                diff.p_value = p_value;
+            }
+            diff
+        }).collect();
+
+        operations.extend(cand_stats.iter().filter(|c| !base_stats.iter().any(|b| same(b, c))).map(|cand| OperationDiff {
+            name: cand.name.clone(),
+            device: cand.device.clone(),
+            backend: cand.backend.clone(),
+            baseline: None,
+            candidate: Some(cand.into()),
+            delta_pct: None,
+            p_value: None,
+            status: DiffStatus::Added,
+        }));
+
+        Self { thresholds: *thresholds, hardware_warnings, operations }
+    }
+
+    pub fn regressions(&self) -> impl Iterator<Item = &OperationDiff> {
+        self.operations.iter().filter(|op| op.status == DiffStatus::Regressed)
+    }
+
+    pub fn has_regressions(&self) -> bool {
+        self.regressions().next().is_some()
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_else(|_| "{}".to_string())
+    }
+}
+
+/// Complementary error function (Numerical Recipes `erfcc`, error below 1.2e-7).
+fn erfc(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1.0 / (1.0 + 0.5 * z);
+    let poly = -z * z - 1.26551223 + t * (1.00002368 + t * (0.37409196 + t * (0.09678418
+        + t * (-0.18628806 + t * (0.27886807 + t * (-1.13520398 + t * (1.48851587
+        + t * (-0.82215223 + t * 0.17087277))))))));
+    let result = t * poly.exp();
+    if x >= 0.0 { result } else { 2.0 - result }
+}
+
+/// Two-sided p-value of the Mann-Whitney U test (normal approximation with
+/// tie correction). It compares the rank order of the samples, so a few
+/// outliers do not dominate the result the way they would a t-test.
+pub fn mann_whitney_p(a: &[f64], b: &[f64]) -> f64 {
+    let (n1, n2) = (a.len() as f64, b.len() as f64);
+    if a.is_empty() || b.is_empty() {
+        return 1.0;
+    }
+    let mut all: Vec<(f64, bool)> = a.iter().map(|&v| (v, true)).chain(b.iter().map(|&v| (v, false))).collect();
+    all.sort_by(|x, y| x.0.total_cmp(&y.0));
+
+    let n = all.len();
+    let (mut rank_sum_a, mut tie_term) = (0.0, 0.0);
+    let mut i = 0;
+    while i < n {
+        let mut j = i;
+        while j + 1 < n && all[j + 1].0 == all[i].0 {
+            j += 1;
+        }
+        // Tied values share the average of their ranks (1-based).
+        let rank = (i + j) as f64 / 2.0 + 1.0;
+        rank_sum_a += all[i..=j].iter().filter(|(_, from_a)| *from_a).count() as f64 * rank;
+        let t = (j - i + 1) as f64;
+        tie_term += t * t * t - t;
+        i = j + 1;
+    }
+
+    let u = rank_sum_a - n1 * (n1 + 1.0) / 2.0;
+    let total = n1 + n2;
+    let variance = n1 * n2 / 12.0 * ((total + 1.0) - tie_term / (total * (total - 1.0)));
+    if variance <= 0.0 {
+        return 1.0;
+    }
+    let z = (u - n1 * n2 / 2.0).abs() / variance.sqrt();
+    erfc(z / std::f64::consts::SQRT_2).min(1.0)
+}
+
+fn ms(summary: &Option<RunSummary>) -> String {
+    summary.as_ref().map(|s| format!("{:.4}", s.mean.as_secs_f64() * 1000.0)).unwrap_or_else(|| "-".to_string())
+}
+
+impl fmt::Display for ProfileDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for warning in &self.hardware_warnings {
+            writeln!(f, "warning: {}", warning)?;
+        }
+        writeln!(f, "{:<32} {:>7} {:>7} {:>12} {:>12} {:>9} {:>8}  Status",
+            "Operation", "Base n", "New n", "Base (ms)", "New (ms)", "Delta", "p")?;
+        for op in &self.operations {
+            writeln!(f, "{:<32} {:>7} {:>7} {:>12} {:>12} {:>9} {:>8}  {:?}",
+                format!("{} [{}/{}]", op.name, op.device, op.backend),
+                op.baseline.as_ref().map(|s| s.count.to_string()).unwrap_or_else(|| "-".to_string()),
+                op.candidate.as_ref().map(|s| s.count.to_string()).unwrap_or_else(|| "-".to_string()),
+                ms(&op.baseline),
+                ms(&op.candidate),
+                op.delta_pct.map(|d| format!("{:+.1}%", d)).unwrap_or_else(|| "-".to_string()),
+                op.p_value.map(|p| format!("{:.3}", p)).unwrap_or_else(|| "-".to_string()),
+                op.status)?;
+        }
+        let regressions = self.regressions().count();
+        writeln!(f, "\n{} regression(s) above {:.1}% at alpha {}", regressions, self.thresholds.max_regression_pct, self.thresholds.alpha)
+    }
+}
@@ -0,0 +1,285 @@
+//! Measured energy for profiler metrics from Linux RAPL counters.
+//!
+//! Reading sysfs costs microseconds, far more than a span, so counters are
+//! not read inside `ProfileScope`. A sampler reads them periodically and the
+//! energy of a span is interpolated between the samples around its start and
+//! end. The package energy of an interval is shared among the top-level spans
+//! running in it, so concurrent spans do not each claim the whole package.
+
+use crate::detect::rapl::{read_rapl_zones, RaplDomain, RaplZone};
+use crate::detect::sysfs::SYSFS_ROOT;
+use crate::profiler::core::{get_profiler, Metric};
+use crate::runtime::periodic::PeriodicThread;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Interval of the background sampler started by [`start_energy_sampling`].
+pub const DEFAULT_ENERGY_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Samples kept by an [`EnergySampler`]; about 16 minutes at the default interval.
+pub const MAX_ENERGY_SAMPLES: usize = 100_000;
+
+/// Where a metric's `energy_estimate` comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum EnergySource {
+    /// Measured with RAPL counters.
+    Rapl,
+    /// Duration times a fixed wattage per device.
+    #[default]
+    Estimate,
+}
+
+/// Energy in joules per RAPL domain, summed over sockets.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize)]
+pub struct EnergyBreakdown {
+    pub package_j: f64,
+    pub core_j: f64,
+    pub dram_j: f64,
+}
+
+impl EnergyBreakdown {
+    fn lerp(&self, other: &Self, t: f64) -> Self {
+        let mix = |a: f64, b: f64| a + (b - a) * t;
+        Self {
+            package_j: mix(self.package_j, other.package_j),
+            core_j: mix(self.core_j, other.core_j),
+            dram_j: mix(self.dram_j, other.dram_j),
+        }
+    }
+
+    fn add_share(&mut self, other: &Self, fraction: f64) {
+        self.package_j += other.package_j * fraction;
+        self.core_j += other.core_j * fraction;
+        self.dram_j += other.dram_j * fraction;
+    }
+
+    fn since(&self, earlier: &Self) -> Self {
+        Self {
+            package_j: self.package_j - earlier.package_j,
+            core_j: self.core_j - earlier.core_j,
+            dram_j: self.dram_j - earlier.dram_j,
+        }
+    }
+}
+
+struct SamplerState {
+    /// Last raw reading per zone, to detect wraparound.
+    last_uj: Vec<Option<u64>>,
+    /// Microjoules consumed per zone since the sampler was created.
+    consumed_uj: Vec<u64>,
+    /// Cumulative energy at points in time (profiler offsets).
+    samples: VecDeque<(Duration, EnergyBreakdown)>,
+}
+
+/// Accumulates RAPL counters into a timeline of consumed energy.
+pub struct EnergySampler {
+    zones: Vec<RaplZone>,
+    state: Mutex<SamplerState>,
+}
+
+impl EnergySampler {
+    /// A sampler over `zones`, or `None` without any zone.
+    pub fn new(zones: Vec<RaplZone>) -> Option<Self> {
+        if zones.is_empty() {
+            return None;
+        }
+        let state = SamplerState {
+            last_uj: vec![None; zones.len()],
+            consumed_uj: vec![0; zones.len()],
+            samples: VecDeque::new(),
+        };
+        Some(Self { zones, state: Mutex::new(state) })
+    }
+
+    /// Discovers the zones under `<root>/class/powercap`.
+    pub fn discover(root: impl AsRef<Path>) -> Option<Self> {
+        Self::new(read_rapl_zones(root))
+    }
+
+    pub fn zones(&self) -> &[RaplZone] {
+        &self.zones
+    }
+
+    /// Reads every counter and appends a sample at the current profiler time.
+    pub fn sample(&self) {
+        let readings: Vec<Option<u64>> = self.zones.iter().map(RaplZone::read_energy_uj).collect();
+        let at = get_profiler().now();
+        let Ok(mut state) = self.state.lock() else { return };
+
+        for (i, reading) in readings.into_iter().enumerate() {
+            let Some(now) = reading else { continue };
+            if let Some(last) = state.last_uj[i] {
+                let range = self.zones[i].max_energy_range_uj;
+                let delta = if now >= last {
+                    now - last
+                } else if range != u64::MAX {
+                    // The counter wrapped at `max_energy_range_uj`.
+                    range.saturating_sub(last).saturating_add(now)
+                } else {
+                    // Without a known range a backwards step is a reset (e.g. after
+                    // suspend): take `now` as the new baseline.
+                    0
+                };
+                state.consumed_uj[i] = state.consumed_uj[i].saturating_add(delta);
+            }
+            state.last_uj[i] = Some(now);
+        }
+
+        let mut totals = EnergyBreakdown::default();
+        for (zone, &uj) in self.zones.iter().zip(&state.consumed_uj) {
+            let joules = uj as f64 / 1e6;
+            match zone.domain {
+                RaplDomain::Package => totals.package_j += joules,
+                RaplDomain::Core => totals.core_j += joules,
+                RaplDomain::Dram => totals.dram_j += joules,
+                _ => {}
+            }
+        }
+        if state.samples.len() == MAX_ENERGY_SAMPLES {
+            state.samples.pop_front();
+        }
+        state.samples.push_back((at, totals));
+    }
+
+    /// Energy consumed between the first and the latest sample.
+    pub fn totals(&self) -> EnergyBreakdown {
+        self.state.lock().ok()
+            .and_then(|s| Some(s.samples.back()?.1.since(&s.samples.front()?.1)))
+            .unwrap_or_default()
+    }
+
+    /// Energy consumed between two profiler offsets, or `None` if the
+    /// samples do not cover the interval.
+    pub fn energy_between(&self, start: Duration, end: Duration) -> Option<EnergyBreakdown> {
+        let state = self.state.lock().ok()?;
+        let at = |t: Duration| -> Option<EnergyBreakdown> {
+            let after = state.samples.partition_point(|(at, _)| *at < t);
+            let (t1, e1) = state.samples.get(after)?;
+            if *t1 == t {
+                return Some(*e1);
+            }
+            let (t0, e0) = state.samples.get(after.checked_sub(1)?)?;
+            let span = (*t1 - *t0).as_secs_f64();
+            Some(e0.lerp(e1, (t - *t0).as_secs_f64() / span))
+        };
+        Some(at(end)?.since(&at(start)?))
+    }
+}
+
+struct Running {
+    sampler: Arc<EnergySampler>,
+    thread: PeriodicThread,
+}
+
+static RUNNING: Mutex<Option<Running>> = Mutex::new(None);
+
+/// Starts sampling the live RAPL counters every `interval`.
+///
+/// Returns `false` if no readable RAPL zone exists; metrics then keep the
+/// fixed-wattage estimate.
+pub fn start_energy_sampling(interval: Duration) -> bool {
+    start_energy_sampling_from(SYSFS_ROOT, interval)
+}
+
+/// Like [`start_energy_sampling`], reading `<root>/class/powercap`.
+pub fn start_energy_sampling_from(root: impl AsRef<Path>, interval: Duration) -> bool {
+    let Some(sampler) = EnergySampler::discover(root) else { return false };
+    let sampler = Arc::new(sampler);
+    sampler.sample();
+
+    stop_energy_sampling();
+    let recorder = sampler.clone();
+    let Some(thread) = PeriodicThread::spawn("archx-energy-sampler", interval, move || recorder.sample()) else {
+        return false;
+    };
+    if let Ok(mut running) = RUNNING.lock() {
+        *running = Some(Running { sampler, thread });
+    }
+    true
+}
+
+/// Stops the background sampler. Later snapshots fall back to estimates.
+pub fn stop_energy_sampling() {
+    let running = RUNNING.lock().ok().and_then(|mut r| r.take());
+    if let Some(running) = running {
+        running.thread.stop();
+    }
+}
+
+/// The running sampler, if any.
+pub fn energy_sampler() -> Option<Arc<EnergySampler>> {
+    RUNNING.lock().ok()?.as_ref().map(|r| r.sampler.clone())
+}
+
+/// Replaces estimates of top-level CPU metrics with measured package energy
+/// where the running sampler covers them.
+///
+/// The package counter covers the whole socket, so energy is split rather
+/// than copied: between consecutive span boundaries it is divided evenly among
+/// the top-level spans running at that time, which weights each span by its
+/// overlap. Nested spans are part of their parent's energy and keep their
+/// estimate, as do GPU metrics, since RAPL does not see discrete GPUs.
+pub(crate) fn attribute(metrics: &mut [Metric]) {
+    let Some(sampler) = energy_sampler() else { return };
+    // Cover metrics that ended after the last periodic sample.
+    sampler.sample();
+
+    let ids: HashSet<u64> = metrics.iter().map(|m| m.id).collect();
+    let mut roots: Vec<usize> = (0..metrics.len())
+        .filter(|&i| {
+            let m = &metrics[i];
+            !m.device.contains("GPU") && m.parent_id.is_none_or(|parent| !ids.contains(&parent))
+        })
+        .collect();
+    roots.sort_by_key(|&i| metrics[i].start);
+    let end = |i: usize| metrics[i].start + metrics[i].duration;
+
+    let mut bounds: Vec<Duration> = roots.iter().flat_map(|&i| [metrics[i].start, end(i)]).collect();
+    bounds.sort();
+    bounds.dedup();
+
+    // `None` once part of a span falls outside the samples.
+    let mut shares: HashMap<usize, Option<EnergyBreakdown>> = roots.iter()
+        .map(|&i| {
+            let covered = metrics[i].duration > Duration::ZERO || sampler.energy_between(metrics[i].start, end(i)).is_some();
+            (i, covered.then(EnergyBreakdown::default))
+        })
+        .collect();
+    let mut next = 0;
+    let mut running: Vec<usize> = Vec::new();
+    for window in bounds.windows(2) {
+        let (from, to) = (window[0], window[1]);
+        running.retain(|&i| end(i) > from);
+        while next < roots.len() && metrics[roots[next]].start <= from {
+            if end(roots[next]) > from {
+                running.push(roots[next]);
+            }
+            next += 1;
+        }
+        if running.is_empty() {
+            continue;
+        }
+
+        let energy = sampler.energy_between(from, to);
+        let fraction = 1.0 / running.len() as f64;
+        for i in &running {
+            let share = shares.get_mut(i).expect("every running span is a root");
+            match (share.as_mut(), &energy) {
+                (Some(total), Some(energy)) => total.add_share(energy, fraction),
+                _ => *share = None,
+            }
+        }
+    }
+
+    for (i, share) in shares {
+        let Some(energy) = share else { continue };
+        let metric = &mut metrics[i];
+        metric.energy_estimate = energy.package_j as f32;
+        metric.energy_source = EnergySource::Rapl;
+        metric.metadata.insert("energy_core_j".to_string(), energy.core_j.to_string());
+        metric.metadata.insert("energy_dram_j".to_string(), energy.dram_j.to_string());
+    }
+}
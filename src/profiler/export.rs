@@ -0,0 +1,123 @@
+//! Self-describing profiler exports for comparing runs.
+
+use crate::hardware::SystemInfo;
+use crate::profiler::core::{get_profiler, Metric};
+use crate::profiler::sampler::{system_sampler, SystemSample};
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::Path;
+
+/// What a run was measured on. Runs are only comparable on equal fingerprints.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HardwareFingerprint {
+    pub cpu_brand: String,
+    pub arch: String,
+    pub logical_threads: usize,
+    /// Threads usable after cgroup CPU quotas.
+    pub effective_threads: usize,
+    pub features: Vec<String>,
+    pub memory_gib: u64,
+    pub gpus: Vec<String>,
+}
+
+impl HardwareFingerprint {
+    pub fn current() -> Self {
+        let info = SystemInfo::detect();
+        let total_kb = crate::detect::HardwareCache::global().dynamic_facts().memory.total_kb;
+        Self {
+            cpu_brand: info.cpu.brand.clone(),
+            arch: format!("{:?}", info.cpu.arch),
+            logical_threads: info.cpu.logical_processors,
+            effective_threads: info.cpu.effective_threads,
+            features: info.cpu.features.enabled().into_iter().map(String::from).collect(),
+            memory_gib: (total_kb as f64 / (1024.0 * 1024.0)).round() as u64,
+            gpus: info.gpus.iter().map(|g| g.name.clone()).collect(),
+        }
+    }
+
+    /// Human-readable descriptions of the fields that differ, empty if equal.
+    pub fn differences(&self, other: &Self) -> Vec<String> {
+        let mut diffs = Vec::new();
+        let mut check = |field: &str, a: String, b: String| {
+            if a != b {
+                diffs.push(format!("{}: {} vs {}", field, a, b));
+            }
+        };
+        check("cpu", self.cpu_brand.clone(), other.cpu_brand.clone());
+        check("arch", self.arch.clone(), other.arch.clone());
+        check("logical threads", self.logical_threads.to_string(), other.logical_threads.to_string());
+        check("effective threads", self.effective_threads.to_string(), other.effective_threads.to_string());
+        check("features", self.features.join(" "), other.features.join(" "));
+        check("memory", format!("{} GiB", self.memory_gib), format!("{} GiB", other.memory_gib));
+        check("gpus", self.gpus.join(", "), other.gpus.join(", "));
+        diffs
+    }
+}
+
+/// A profiler snapshot together with the hardware it was taken on.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileExport {
+    pub archx_version: String,
+    /// Unix time (seconds) of the capture.
+    pub created: u64,
+    /// `None` for plain metric lists, e.g. from `Profiler::to_json`.
+    pub fingerprint: Option<HardwareFingerprint>,
+    pub metrics: Vec<Metric>,
+    /// Load, memory and frequency during the run, from the system sampler.
+    #[serde(default)]
+    pub system: Vec<SystemSample>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum AnyExport {
+    Export(ProfileExport),
+    Metrics(Vec<Metric>),
+}
+
+impl ProfileExport {
+    /// The global profiler's snapshot, this machine's fingerprint and the
+    /// history of the running system sampler.
+    pub fn capture() -> Self {
+        let mut export = Self::from_metrics(get_profiler().get_snapshot());
+        export.system = system_sampler().map(|s| s.history()).unwrap_or_default();
+        export
+    }
+
+    pub fn from_metrics(metrics: Vec<Metric>) -> Self {
+        Self {
+            archx_version: env!("CARGO_PKG_VERSION").to_string(),
+            created: std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0),
+            fingerprint: Some(HardwareFingerprint::current()),
+            metrics,
+            system: Vec::new(),
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_else(|_| "{}".to_string())
+    }
+
+    /// Parses an export, or a plain list of metrics as written by
+    /// `Profiler::to_json` and `JsonExporter`.
+    pub fn from_json(json: &str) -> io::Result<Self> {
+        match serde_json::from_str(json).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))? {
+            AnyExport::Export(export) => Ok(export),
+            AnyExport::Metrics(metrics) => Ok(Self {
+                archx_version: String::new(),
+                created: 0,
+                fingerprint: None,
+                metrics,
+                system: Vec::new(),
+            }),
+        }
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        std::fs::write(path, self.to_json())
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::from_json(&std::fs::read_to_string(path)?)
+    }
+}
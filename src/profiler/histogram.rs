@@ -0,0 +1,117 @@
+//! Log-linear latency histogram in the style of HdrHistogram.
+//!
+//! Values below 128ns are counted exactly. Above that, every power of two is
+//! split into 128 buckets, so a reported percentile is within 1% of the
+//! recorded value while the histogram stays a few KB in size.
+
+use std::time::Duration;
+
+const SUB_BUCKET_BITS: u32 = 7;
+const SUB_BUCKETS: u64 = 1 << SUB_BUCKET_BITS;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LatencyHistogram {
+    counts: Vec<u64>,
+    count: u64,
+    total_nanos: u128,
+    min_nanos: u64,
+    max_nanos: u64,
+}
+
+fn bucket_index(nanos: u64) -> usize {
+    if nanos < SUB_BUCKETS {
+        return nanos as usize;
+    }
+    let magnitude = 63 - nanos.leading_zeros();
+    let shift = magnitude - SUB_BUCKET_BITS;
+    let sub = (nanos >> shift) - SUB_BUCKETS;
+    ((shift as u64 + 1) * SUB_BUCKETS + sub) as usize
+}
+
+/// Largest value that falls into bucket `index`.
+fn bucket_upper(index: usize) -> u64 {
+    let index = index as u64;
+    if index < SUB_BUCKETS {
+        return index;
+    }
+    let shift = index / SUB_BUCKETS - 1;
+    let sub = index % SUB_BUCKETS + SUB_BUCKETS;
+    u64::try_from(((sub as u128 + 1) << shift) - 1).unwrap_or(u64::MAX)
+}
+
+impl LatencyHistogram {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&mut self, duration: Duration) {
+        let nanos = u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX);
+        let index = bucket_index(nanos);
+        if index >= self.counts.len() {
+            self.counts.resize(index + 1, 0);
+        }
+        self.counts[index] += 1;
+        if self.count == 0 || nanos < self.min_nanos {
+            self.min_nanos = nanos;
+        }
+        self.max_nanos = self.max_nanos.max(nanos);
+        self.count += 1;
+        self.total_nanos += nanos as u128;
+    }
+
+    pub fn merge(&mut self, other: &LatencyHistogram) {
+        if other.count == 0 {
+            return;
+        }
+        if other.counts.len() > self.counts.len() {
+            self.counts.resize(other.counts.len(), 0);
+        }
+        for (mine, theirs) in self.counts.iter_mut().zip(&other.counts) {
+            *mine += theirs;
+        }
+        self.min_nanos = if self.count == 0 { other.min_nanos } else { self.min_nanos.min(other.min_nanos) };
+        self.max_nanos = self.max_nanos.max(other.max_nanos);
+        self.count += other.count;
+        self.total_nanos += other.total_nanos;
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    pub fn total(&self) -> Duration {
+        Duration::from_nanos(u64::try_from(self.total_nanos).unwrap_or(u64::MAX))
+    }
+
+    pub fn min(&self) -> Duration {
+        Duration::from_nanos(self.min_nanos)
+    }
+
+    pub fn max(&self) -> Duration {
+        Duration::from_nanos(self.max_nanos)
+    }
+
+    pub fn mean(&self) -> Duration {
+        if self.count == 0 {
+            return Duration::ZERO;
+        }
+        Duration::from_nanos((self.total_nanos / self.count as u128) as u64)
+    }
+
+    /// The value below which `percentile`% of the recorded values fall,
+    /// e.g. `percentile(99.9)`. Zero for an empty histogram.
+    pub fn percentile(&self, percentile: f64) -> Duration {
+        if self.count == 0 {
+            return Duration::ZERO;
+        }
+        let rank = ((percentile.clamp(0.0, 100.0) / 100.0) * self.count as f64).ceil().max(1.0) as u64;
+        let mut seen = 0;
+        for (index, &count) in self.counts.iter().enumerate() {
+            seen += count;
+            if seen >= rank {
+                return Duration::from_nanos(bucket_upper(index).clamp(self.min_nanos, self.max_nanos));
+            }
+        }
+        self.max()
+    }
+}
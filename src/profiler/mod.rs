@@ -1,10 +1,26 @@
 pub mod core;
+pub mod buffer;
+pub mod counters;
+pub mod tree;
+pub mod histogram;
+pub mod stats;
+pub mod energy;
+pub mod export;
+pub mod diff;
 pub mod sampler;
 pub mod task_metrics;
 pub mod load_monitor;
 pub mod reports;
 
-pub use core::{get_profiler, Metric, Profiler, ProfileScope};
-pub use sampler::Sampler;
+pub use core::{get_profiler, Metric, Profiler, ProfileScope, SpanContext, SpanGuard};
+pub use buffer::THREAD_BUFFER_CAPACITY;
+pub use counters::OperationTotals;
+pub use tree::{summarize, SpanSummary};
+pub use histogram::LatencyHistogram;
+pub use stats::{operation_stats, OperationStats};
+pub use export::{HardwareFingerprint, ProfileExport};
+pub use diff::{DiffStatus, DiffThresholds, OperationDiff, ProfileDiff};
+pub use energy::{start_energy_sampling, stop_energy_sampling, EnergyBreakdown, EnergySampler, EnergySource};
+pub use sampler::{start_system_sampling, stop_system_sampling, system_sampler, Sampler, SystemSample};
 pub use task_metrics::TaskMetrics;
-pub use reports::{JsonExporter, CsvExporter, ReportExporter};
+pub use reports::{JsonExporter, CsvExporter, CsvWriter, ChromeTraceExporter, CounterSample, HtmlReportExporter, ReportExporter};
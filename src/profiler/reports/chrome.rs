@@ -0,0 +1,150 @@
+//! Chrome Trace Event Format export, readable by Perfetto and `chrome://tracing`.
+
+use super::{decode_metrics, is_gpu, micros, ReportExporter};
+use crate::profiler::core::Metric;
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+use std::fs::File;
+use std::time::Duration;
+
+/// Process id of the CPU thread tracks.
+const CPU_PID: u64 = 1;
+/// Process id of the GPU device tracks.
+const GPU_PID: u64 = 2;
+
+/// One value of a counter track, e.g. CPU load at some point of the run.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CounterSample {
+    pub name: String,
+    /// Offset from the profiler epoch, like `Metric::start`.
+    pub at: Duration,
+    pub value: f64,
+}
+
+/// Exporter that writes profiler metrics as a Chrome trace.
+///
+/// CPU spans go to one track per thread and GPU spans to one track per
+/// device and backend. Spans nest by time within a track; a span whose
+/// parent ran on another track is linked to it with a flow arrow.
+#[derive(Debug, Clone, Default)]
+pub struct ChromeTraceExporter {
+    pub counters: Vec<CounterSample>,
+}
+
+impl ChromeTraceExporter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds counter tracks, e.g. from [`Sampler::counters`](crate::profiler::Sampler::counters).
+    pub fn with_counters(mut self, counters: Vec<CounterSample>) -> Self {
+        self.counters.extend(counters);
+        self
+    }
+
+    /// Builds the trace document for `metrics`.
+    pub fn trace(&self, metrics: &[Metric]) -> Value {
+        let mut events = vec![
+            json!({ "name": "process_name", "ph": "M", "pid": CPU_PID, "args": { "name": "CPU" } }),
+            json!({ "name": "process_name", "ph": "M", "pid": GPU_PID, "args": { "name": "GPU" } }),
+        ];
+
+        // GPU tracks are numbered in order of appearance.
+        let mut gpu_tracks: Vec<String> = Vec::new();
+        let mut cpu_tracks: Vec<u64> = Vec::new();
+        let track = |metric: &Metric, gpu_tracks: &mut Vec<String>, cpu_tracks: &mut Vec<u64>| {
+            if is_gpu(metric) {
+                let label = format!("{} ({})", metric.device, metric.backend);
+                let tid = match gpu_tracks.iter().position(|t| *t == label) {
+                    Some(i) => i,
+                    None => {
+                        gpu_tracks.push(label);
+                        gpu_tracks.len() - 1
+                    }
+                };
+                (GPU_PID, tid as u64 + 1)
+            } else {
+                let tid = metric.thread_id.unwrap_or(0) as u64;
+                if !cpu_tracks.contains(&tid) {
+                    cpu_tracks.push(tid);
+                }
+                (CPU_PID, tid)
+            }
+        };
+
+        let tracks: Vec<(u64, u64)> = metrics.iter().map(|m| track(m, &mut gpu_tracks, &mut cpu_tracks)).collect();
+        let by_id: HashMap<u64, usize> = metrics.iter().enumerate()
+            .filter(|(_, m)| m.id != 0)
+            .map(|(i, m)| (m.id, i))
+            .collect();
+
+        for &tid in &cpu_tracks {
+            events.push(json!({ "name": "thread_name", "ph": "M", "pid": CPU_PID, "tid": tid, "args": { "name": format!("Thread {}", tid) } }));
+        }
+        for (i, label) in gpu_tracks.iter().enumerate() {
+            events.push(json!({ "name": "thread_name", "ph": "M", "pid": GPU_PID, "tid": i + 1, "args": { "name": label } }));
+        }
+
+        for (metric, &(pid, tid)) in metrics.iter().zip(&tracks) {
+            // Metadata goes in first so a colliding key cannot shadow the built-in fields.
+            let mut args: Map<String, Value> = metric.metadata.iter()
+                .map(|(key, value)| (key.clone(), json!(value)))
+                .collect();
+            args.insert("id".into(), json!(metric.id));
+            if let Some(parent) = metric.parent_id {
+                args.insert("parent_id".into(), json!(parent));
+            }
+            args.insert("backend".into(), json!(metric.backend));
+            args.insert("device".into(), json!(metric.device));
+            args.insert("energy_estimate".into(), json!(metric.energy_estimate));
+            args.insert("energy_source".into(), json!(metric.energy_source));
+            events.push(json!({
+                "name": metric.name,
+                "cat": metric.backend,
+                "ph": "X",
+                "ts": micros(metric.start),
+                "dur": micros(metric.duration),
+                "pid": pid,
+                "tid": tid,
+                "args": args,
+            }));
+
+            let parent = metric.parent_id.and_then(|p| by_id.get(&p));
+            if let Some(&parent) = parent {
+                let (parent_pid, parent_tid) = tracks[parent];
+                if (parent_pid, parent_tid) != (pid, tid) {
+                    let ts = micros(metric.start);
+                    events.push(json!({ "name": "spawn", "cat": "flow", "ph": "s", "id": metric.id, "ts": ts, "pid": parent_pid, "tid": parent_tid }));
+                    events.push(json!({ "name": "spawn", "cat": "flow", "ph": "f", "bp": "e", "id": metric.id, "ts": ts, "pid": pid, "tid": tid }));
+                }
+            }
+        }
+
+        for counter in &self.counters {
+            events.push(json!({
+                "name": counter.name,
+                "ph": "C",
+                "ts": micros(counter.at),
+                "pid": CPU_PID,
+                "args": { "value": counter.value },
+            }));
+        }
+
+        json!({
+            "traceEvents": events,
+            "displayTimeUnit": "ms",
+            "otherData": { "producer": format!("ArchX {}", env!("CARGO_PKG_VERSION")) },
+        })
+    }
+}
+
+impl ReportExporter for ChromeTraceExporter {
+    /// `data` must serialize as a list of [`Metric`]s, e.g. a profiler snapshot.
+    fn export<T: Serialize>(&self, data: &T, path: &str) -> std::io::Result<()> {
+        let metrics = decode_metrics(data, "Chrome traces")?;
+        let file = File::create(path)?;
+        serde_json::to_writer(file, &self.trace(&metrics))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,418 @@
+//! RFC 4180 CSV output for any sequence of serde records.
+//!
+//! Records are flattened into columns: nested structs and maps become
+//! `parent.child` columns (so `Metric::metadata` gets one column per key),
+//! `Duration`s become milliseconds in a column named `<field>_ms`, and lists
+//! are written as JSON text. Missing values are empty cells.
+//!
+//! Columns follow the order in which a record serializes its fields, so a
+//! struct's columns match its declaration order. Map entries are sorted by
+//! key, since a `HashMap` iterates in a different order in every process.
+
+use serde::ser::{self, Serialize, Serializer};
+use serde_json::Value;
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+/// Streams records into a CSV file or any other writer.
+///
+/// The columns are fixed by the first record (or by the header of the file
+/// being appended to). Later records fill the cells of known columns; fields
+/// without a column are left out.
+pub struct CsvWriter<W: Write> {
+    out: W,
+    columns: Vec<String>,
+    header_written: bool,
+}
+
+impl CsvWriter<BufWriter<File>> {
+    /// Truncates `path` and writes the header with the first record.
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(Self::new(BufWriter::new(File::create(path)?)))
+    }
+
+    /// Appends to `path`, reusing its header. An empty or missing file gets a
+    /// header with the first record, as with [`CsvWriter::create`].
+    pub fn append(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        let mut header = String::new();
+        if let Ok(file) = File::open(path) {
+            BufReader::new(file).read_line(&mut header)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let out = BufWriter::new(file);
+        let header = header.trim_end_matches(['\r', '\n']);
+        if header.is_empty() {
+            Ok(Self::new(out))
+        } else {
+            Ok(Self { out, columns: parse_line(header), header_written: true })
+        }
+    }
+}
+
+impl<W: Write> CsvWriter<W> {
+    pub fn new(out: W) -> Self {
+        Self { out, columns: Vec::new(), header_written: false }
+    }
+
+    /// A writer with a fixed set of columns; the header is written with the first record.
+    pub fn with_columns(out: W, columns: Vec<String>) -> Self {
+        Self { out, columns, header_written: false }
+    }
+
+    pub fn columns(&self) -> &[String] {
+        &self.columns
+    }
+
+    pub fn write_record<T: Serialize>(&mut self, record: &T) -> io::Result<()> {
+        let cells = flatten(record)?;
+        self.write_cells(&cells)
+    }
+
+    fn write_cells(&mut self, cells: &[(String, String)]) -> io::Result<()> {
+        if !self.header_written {
+            if self.columns.is_empty() {
+                self.columns = cells.iter().map(|(column, _)| column.clone()).collect();
+            }
+            write_line(&mut self.out, self.columns.iter().map(String::as_str))?;
+            self.header_written = true;
+        }
+        let row = self.columns.iter().map(|column| {
+            cells.iter().find(|(c, _)| c == column).map(|(_, v)| v.as_str()).unwrap_or("")
+        });
+        write_line(&mut self.out, row)
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.out.flush()
+    }
+
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+}
+
+/// Writes `data` (a sequence of records, or a single record) as CSV.
+///
+/// Unlike [`CsvWriter`], the columns are the union of all records' fields,
+/// so every metadata key gets a column.
+pub(crate) fn write_all<T: Serialize, W: Write>(data: &T, out: W) -> io::Result<W> {
+    let rows = flatten_rows(data, true)?;
+
+    let mut columns: Vec<String> = Vec::new();
+    for (column, _) in rows.iter().flatten() {
+        if !columns.contains(column) {
+            columns.push(column.clone());
+        }
+    }
+
+    let mut writer = CsvWriter::with_columns(out, columns);
+    for row in &rows {
+        writer.write_cells(row)?;
+    }
+    writer.flush()?;
+    Ok(writer.into_inner())
+}
+
+type Row = Vec<(String, String)>;
+
+fn flatten<T: Serialize>(record: &T) -> io::Result<Row> {
+    Ok(flatten_rows(record, false)?.pop().unwrap_or_default())
+}
+
+/// Flattens `data` into rows; with `split`, a top-level sequence gives one row per element.
+fn flatten_rows<T: Serialize>(data: &T, split: bool) -> io::Result<Vec<Row>> {
+    let mut rows = Vec::new();
+    data.serialize(Flatten { rows: &mut rows, prefix: String::new(), split }).map_err(io::Error::from)?;
+    Ok(rows)
+}
+
+/// Serializer writing every leaf of a record as a `(column, cell)` pair of
+/// the last row, in serialization order.
+struct Flatten<'a> {
+    rows: &'a mut Vec<Row>,
+    prefix: String,
+    split: bool,
+}
+
+impl Flatten<'_> {
+    fn nested(&mut self, key: &str) -> Flatten<'_> {
+        let prefix = if self.prefix.is_empty() { key.to_string() } else { format!("{}.{}", self.prefix, key) };
+        Flatten { rows: &mut *self.rows, prefix, split: false }
+    }
+
+    fn push(&mut self, column: String, cell: String) {
+        if self.rows.is_empty() {
+            self.rows.push(Vec::new());
+        }
+        if let Some(row) = self.rows.last_mut() {
+            row.push((column, cell));
+        }
+    }
+
+    fn leaf(mut self, value: Value) -> Result<(), serde_json::Error> {
+        let column = if self.prefix.is_empty() { "value".to_string() } else { self.prefix.clone() };
+        let cell = match value {
+            Value::Null => String::new(),
+            Value::String(s) => s,
+            other => other.to_string(),
+        };
+        self.push(column, cell);
+        Ok(())
+    }
+
+    fn json<T: Serialize + ?Sized>(self, value: &T) -> Result<(), serde_json::Error> {
+        let value = serde_json::to_value(value)?;
+        self.leaf(value)
+    }
+}
+
+/// State of a sequence, map or struct being flattened.
+enum Compound<'a> {
+    /// Top-level sequence: one row per element.
+    Rows(Flatten<'a>),
+    /// Nested sequence, written as one JSON cell.
+    Json(Flatten<'a>, Vec<Value>),
+    /// Map entries become `parent.key` columns, buffered to be written sorted by key.
+    Map(Flatten<'a>, String, Vec<(String, Row)>),
+    /// Struct fields become `parent.field` columns.
+    Fields(Flatten<'a>),
+    /// `Duration` serializes as `{ secs, nanos }` and becomes one `<field>_ms` cell.
+    Duration(Flatten<'a>, u64, u64),
+}
+
+impl Compound<'_> {
+    fn element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), serde_json::Error> {
+        match self {
+            Compound::Rows(f) => {
+                f.rows.push(Vec::new());
+                value.serialize(Flatten { rows: &mut *f.rows, prefix: f.prefix.clone(), split: false })
+            }
+            Compound::Json(_, items) => {
+                items.push(serde_json::to_value(value)?);
+                Ok(())
+            }
+            _ => Err(ser::Error::custom("unexpected sequence element")),
+        }
+    }
+
+    fn field<T: Serialize + ?Sized>(&mut self, key: &str, value: &T) -> Result<(), serde_json::Error> {
+        match self {
+            Compound::Fields(f) => value.serialize(f.nested(key)),
+            Compound::Map(f, _, entries) => {
+                let mut rows = Vec::new();
+                value.serialize(Flatten { rows: &mut rows, prefix: f.nested(key).prefix, split: false })?;
+                entries.push((key.to_string(), rows.pop().unwrap_or_default()));
+                Ok(())
+            }
+            Compound::Duration(_, secs, nanos) => {
+                let n = serde_json::to_value(value)?.as_u64().unwrap_or(0);
+                match key {
+                    "secs" => *secs = n,
+                    _ => *nanos = n,
+                }
+                Ok(())
+            }
+            _ => Err(ser::Error::custom("unexpected struct field")),
+        }
+    }
+
+    fn finish(self) -> Result<(), serde_json::Error> {
+        match self {
+            Compound::Json(f, items) => f.leaf(Value::Array(items)),
+            Compound::Duration(mut f, secs, nanos) => {
+                let ms = secs as f64 * 1000.0 + nanos as f64 / 1_000_000.0;
+                let column = format!("{}_ms", f.prefix);
+                f.push(column, ms.to_string());
+                Ok(())
+            }
+            Compound::Map(mut f, _, mut entries) => {
+                entries.sort_by(|a, b| a.0.cmp(&b.0));
+                for (column, cell) in entries.into_iter().flat_map(|(_, cells)| cells) {
+                    f.push(column, cell);
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl<'a> Serializer for Flatten<'a> {
+    type Ok = ();
+    type Error = serde_json::Error;
+    type SerializeSeq = Compound<'a>;
+    type SerializeTuple = Compound<'a>;
+    type SerializeTupleStruct = Compound<'a>;
+    type SerializeTupleVariant = Compound<'a>;
+    type SerializeMap = Compound<'a>;
+    type SerializeStruct = Compound<'a>;
+    type SerializeStructVariant = Compound<'a>;
+
+    fn serialize_bool(self, v: bool) -> Result<(), Self::Error> { self.json(&v) }
+    fn serialize_i8(self, v: i8) -> Result<(), Self::Error> { self.json(&v) }
+    fn serialize_i16(self, v: i16) -> Result<(), Self::Error> { self.json(&v) }
+    fn serialize_i32(self, v: i32) -> Result<(), Self::Error> { self.json(&v) }
+    fn serialize_i64(self, v: i64) -> Result<(), Self::Error> { self.json(&v) }
+    fn serialize_u8(self, v: u8) -> Result<(), Self::Error> { self.json(&v) }
+    fn serialize_u16(self, v: u16) -> Result<(), Self::Error> { self.json(&v) }
+    fn serialize_u32(self, v: u32) -> Result<(), Self::Error> { self.json(&v) }
+    fn serialize_u64(self, v: u64) -> Result<(), Self::Error> { self.json(&v) }
+    fn serialize_f32(self, v: f32) -> Result<(), Self::Error> { self.json(&v) }
+    fn serialize_f64(self, v: f64) -> Result<(), Self::Error> { self.json(&v) }
+    fn serialize_char(self, v: char) -> Result<(), Self::Error> { self.json(&v) }
+    fn serialize_str(self, v: &str) -> Result<(), Self::Error> { self.json(v) }
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), Self::Error> { self.json(v) }
+    fn serialize_none(self) -> Result<(), Self::Error> { self.leaf(Value::Null) }
+    fn serialize_unit(self) -> Result<(), Self::Error> { self.leaf(Value::Null) }
+    fn serialize_unit_struct(self, _: &'static str) -> Result<(), Self::Error> { self.leaf(Value::Null) }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit_variant(self, _: &'static str, _: u32, variant: &'static str) -> Result<(), Self::Error> {
+        self.json(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _: &'static str, value: &T) -> Result<(), Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(mut self, _: &'static str, _: u32, variant: &'static str, value: &T) -> Result<(), Self::Error> {
+        value.serialize(self.nested(variant))
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<Compound<'a>, Self::Error> {
+        Ok(if self.split { Compound::Rows(self) } else { Compound::Json(self, Vec::new()) })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Compound<'a>, Self::Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(self, _: &'static str, len: usize) -> Result<Compound<'a>, Self::Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(self, _: &'static str, _: u32, variant: &'static str, _: usize) -> Result<Compound<'a>, Self::Error> {
+        let prefix = if self.prefix.is_empty() { variant.to_string() } else { format!("{}.{}", self.prefix, variant) };
+        Ok(Compound::Json(Flatten { prefix, split: false, ..self }, Vec::new()))
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Compound<'a>, Self::Error> {
+        Ok(Compound::Map(self, String::new(), Vec::new()))
+    }
+
+    fn serialize_struct(self, name: &'static str, _: usize) -> Result<Compound<'a>, Self::Error> {
+        Ok(if name == "Duration" { Compound::Duration(self, 0, 0) } else { Compound::Fields(self) })
+    }
+
+    fn serialize_struct_variant(self, _: &'static str, _: u32, variant: &'static str, _: usize) -> Result<Compound<'a>, Self::Error> {
+        let prefix = if self.prefix.is_empty() { variant.to_string() } else { format!("{}.{}", self.prefix, variant) };
+        Ok(Compound::Fields(Flatten { prefix, split: false, ..self }))
+    }
+}
+
+impl ser::SerializeSeq for Compound<'_> {
+    type Ok = ();
+    type Error = serde_json::Error;
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> { self.element(value) }
+    fn end(self) -> Result<(), Self::Error> { self.finish() }
+}
+
+impl ser::SerializeTuple for Compound<'_> {
+    type Ok = ();
+    type Error = serde_json::Error;
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> { self.element(value) }
+    fn end(self) -> Result<(), Self::Error> { self.finish() }
+}
+
+impl ser::SerializeTupleStruct for Compound<'_> {
+    type Ok = ();
+    type Error = serde_json::Error;
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> { self.element(value) }
+    fn end(self) -> Result<(), Self::Error> { self.finish() }
+}
+
+impl ser::SerializeTupleVariant for Compound<'_> {
+    type Ok = ();
+    type Error = serde_json::Error;
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> { self.element(value) }
+    fn end(self) -> Result<(), Self::Error> { self.finish() }
+}
+
+impl ser::SerializeMap for Compound<'_> {
+    type Ok = ();
+    type Error = serde_json::Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Self::Error> {
+        if let Compound::Map(_, current, _) = self {
+            *current = match serde_json::to_value(key)? {
+                Value::String(s) => s,
+                other => other.to_string(),
+            };
+        }
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        let key = match self {
+            Compound::Map(_, key, _) => std::mem::take(key),
+            _ => return Err(ser::Error::custom("map value without a key")),
+        };
+        self.field(&key, value)
+    }
+
+    fn end(self) -> Result<(), Self::Error> { self.finish() }
+}
+
+impl ser::SerializeStruct for Compound<'_> {
+    type Ok = ();
+    type Error = serde_json::Error;
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error> { self.field(key, value) }
+    fn end(self) -> Result<(), Self::Error> { self.finish() }
+}
+
+impl ser::SerializeStructVariant for Compound<'_> {
+    type Ok = ();
+    type Error = serde_json::Error;
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error> { self.field(key, value) }
+    fn end(self) -> Result<(), Self::Error> { self.finish() }
+}
+
+/// Quotes a field if it contains a delimiter, quote or line break (RFC 4180, 2.6 and 2.7).
+fn escape(field: &str) -> std::borrow::Cow<'_, str> {
+    if field.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\"")).into()
+    } else {
+        field.into()
+    }
+}
+
+fn write_line<'a, W: Write>(out: &mut W, fields: impl Iterator<Item = &'a str>) -> io::Result<()> {
+    let line: Vec<_> = fields.map(escape).collect();
+    write!(out, "{}\r\n", line.join(","))
+}
+
+/// Splits one CSV line (a header) into fields.
+fn parse_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, quoted) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            ('"', _) => quoted = !quoted,
+            (',', false) => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields
+}
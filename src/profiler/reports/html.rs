@@ -0,0 +1,320 @@
+//! Self-contained HTML report with inline SVG charts and the embedded metrics.
+
+use super::{decode_metrics, is_gpu, ms, ReportExporter};
+use crate::profiler::core::Metric;
+use crate::profiler::export::HardwareFingerprint;
+use crate::profiler::stats::operation_stats;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::fs::File;
+use std::io::Write as _;
+use std::time::Duration;
+
+/// Spans drawn on the timeline. Larger snapshots keep the longest spans;
+/// the embedded data always holds all of them.
+pub const MAX_TIMELINE_SPANS: usize = 20_000;
+
+const WIDTH: f64 = 1000.0;
+const LABEL_WIDTH: f64 = 170.0;
+const ROW_HEIGHT: f64 = 16.0;
+const AXIS_HEIGHT: f64 = 20.0;
+const HISTOGRAM_BINS: usize = 24;
+const HISTOGRAM_WIDTH: f64 = 320.0;
+const HISTOGRAM_HEIGHT: f64 = 80.0;
+/// Nesting shown per track; deeper spans are drawn on the last row.
+const MAX_DEPTH: usize = 16;
+
+const STYLE: &str = "body{font:14px system-ui,sans-serif;margin:24px;color:#222}\
+h1{font-size:22px}h2{font-size:17px;margin-top:28px;border-bottom:1px solid #ddd}\
+table{border-collapse:collapse}td,th{padding:3px 10px;text-align:left}\
+th{background:#f3f3f3}td.n{text-align:right;font-variant-numeric:tabular-nums}\
+svg{display:block}svg text{font:11px system-ui,sans-serif}.muted{color:#777}\
+.ops{display:flex;flex-wrap:wrap;gap:18px}.op{border:1px solid #e3e3e3;padding:8px}";
+
+/// Exporter that writes profiler metrics as a single static HTML page.
+///
+/// The page shows a timeline per thread and device, a latency histogram per
+/// operation, the CPU/GPU share of busy time and the hardware summary. It
+/// needs no server or script; the metrics are embedded as JSON in an
+/// `archx-data` script element for further processing.
+#[derive(Debug, Clone)]
+pub struct HtmlReportExporter {
+    pub title: String,
+    pub hardware: Option<HardwareFingerprint>,
+}
+
+impl Default for HtmlReportExporter {
+    fn default() -> Self {
+        Self { title: "ArchX Performance Report".to_string(), hardware: None }
+    }
+}
+
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Stable color per operation name.
+fn color(name: &str) -> String {
+    let hash = name.bytes().fold(0xcbf29ce484222325u64, |h, b| (h ^ b as u64).wrapping_mul(0x100000001b3));
+    format!("hsl({},60%,55%)", hash % 360)
+}
+
+fn track_label(metric: &Metric) -> String {
+    if is_gpu(metric) {
+        format!("{} ({})", metric.device, metric.backend)
+    } else {
+        match metric.thread_id {
+            Some(tid) => format!("Thread {}", tid),
+            None => "Thread ?".to_string(),
+        }
+    }
+}
+
+impl HtmlReportExporter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// Adds the hardware summary, e.g. [`HardwareFingerprint::current`].
+    pub fn with_hardware(mut self, hardware: HardwareFingerprint) -> Self {
+        self.hardware = Some(hardware);
+        self
+    }
+
+    /// Builds the page for `metrics`.
+    pub fn render(&self, metrics: &[Metric]) -> String {
+        let mut html = String::new();
+        let start = metrics.iter().map(|m| m.start).min().unwrap_or_default();
+        let end = metrics.iter().map(|m| m.start + m.duration).max().unwrap_or_default();
+
+        let _ = write!(html, "<!DOCTYPE html>\n<html lang=\"en\"><head><meta charset=\"utf-8\"><title>{}</title><style>{}</style></head><body>\n",
+            escape(&self.title), STYLE);
+        let _ = writeln!(html, "<h1>{}</h1><p class=\"muted\">{} spans over {:.3} ms &middot; ArchX {}</p>",
+            escape(&self.title), metrics.len(), ms(end.saturating_sub(start)), env!("CARGO_PKG_VERSION"));
+
+        self.hardware_section(&mut html);
+        share_section(&mut html, metrics);
+        timeline_section(&mut html, metrics, start, end);
+        histogram_section(&mut html, metrics);
+
+        // `<` only occurs inside JSON strings, where `\u003c` means the same
+        // and cannot end the script element or start markup.
+        let data = serde_json::to_string(metrics).unwrap_or_else(|_| "[]".to_string()).replace('<', "\\u003c");
+        let _ = write!(html, "<script type=\"application/json\" id=\"archx-data\">{}</script>\n</body></html>\n", data);
+        html
+    }
+
+    fn hardware_section(&self, html: &mut String) {
+        html.push_str("<h2>Hardware</h2>\n");
+        let Some(hw) = &self.hardware else {
+            html.push_str("<p class=\"muted\">Not recorded.</p>\n");
+            return;
+        };
+        let gpus = if hw.gpus.is_empty() { "none".to_string() } else { hw.gpus.join(", ") };
+        let rows = [
+            ("CPU", hw.cpu_brand.clone()),
+            ("Architecture", hw.arch.clone()),
+            ("Threads", format!("{} logical, {} usable", hw.logical_threads, hw.effective_threads)),
+            ("Memory", format!("{} GiB", hw.memory_gib)),
+            ("GPUs", gpus),
+            ("Features", hw.features.join(" ")),
+        ];
+        html.push_str("<table>\n");
+        for (key, value) in rows {
+            let _ = writeln!(html, "<tr><th>{}</th><td>{}</td></tr>", key, escape(&value));
+        }
+        html.push_str("</table>\n");
+    }
+}
+
+/// Busy time per device. Spans nested in a span of the same device are not
+/// counted again.
+fn share_section(html: &mut String, metrics: &[Metric]) {
+    html.push_str("<h2>CPU vs GPU</h2>\n");
+    let by_id: HashMap<u64, &Metric> = metrics.iter().filter(|m| m.id != 0).map(|m| (m.id, m)).collect();
+    let mut devices: Vec<(String, Duration, f64)> = Vec::new();
+    for metric in metrics {
+        let nested = metric.parent_id.and_then(|p| by_id.get(&p)).is_some_and(|p| p.device == metric.device);
+        if nested {
+            continue;
+        }
+        match devices.iter_mut().find(|(device, _, _)| *device == metric.device) {
+            Some(entry) => {
+                entry.1 += metric.duration;
+                entry.2 += metric.energy_estimate as f64;
+            }
+            None => devices.push((metric.device.clone(), metric.duration, metric.energy_estimate as f64)),
+        }
+    }
+    let total: f64 = devices.iter().map(|(_, time, _)| time.as_secs_f64()).sum();
+    if total <= 0.0 {
+        html.push_str("<p class=\"muted\">No spans recorded.</p>\n");
+        return;
+    }
+
+    let gpu: f64 = devices.iter().filter(|(d, _, _)| d.contains("GPU")).map(|(_, t, _)| t.as_secs_f64()).sum();
+    let _ = writeln!(html, "<p>CPU {:.1}% &middot; GPU {:.1}% of busy time</p>", (total - gpu) / total * 100.0, gpu / total * 100.0);
+
+    let _ = write!(html, "<svg width=\"{w}\" height=\"24\" viewBox=\"0 0 {w} 24\">", w = WIDTH);
+    let mut x = 0.0;
+    for (device, time, _) in &devices {
+        let width = time.as_secs_f64() / total * WIDTH;
+        let _ = write!(html, "<rect x=\"{:.2}\" y=\"0\" width=\"{:.2}\" height=\"24\" fill=\"{}\"><title>{}: {:.1}%</title></rect>",
+            x, width, color(device), escape(device), time.as_secs_f64() / total * 100.0);
+        x += width;
+    }
+    html.push_str("</svg>\n<table><tr><th>Device</th><th>Busy (ms)</th><th>Share</th><th>Energy (J)</th></tr>\n");
+    for (device, time, energy) in &devices {
+        let _ = writeln!(html, "<tr><td><span style=\"color:{}\">&#9632;</span> {}</td><td class=\"n\">{:.3}</td><td class=\"n\">{:.1}%</td><td class=\"n\">{:.4}</td></tr>",
+            color(device), escape(device), ms(*time), time.as_secs_f64() / total * 100.0, energy);
+    }
+    html.push_str("</table>\n");
+}
+
+fn timeline_section(html: &mut String, metrics: &[Metric], start: Duration, end: Duration) {
+    html.push_str("<h2>Timeline</h2>\n");
+    if metrics.is_empty() {
+        html.push_str("<p class=\"muted\">No spans recorded.</p>\n");
+        return;
+    }
+
+    // CPU threads first, then GPU devices, each in order of appearance.
+    let labels: Vec<String> = metrics.iter().map(track_label).collect();
+    let mut tracks: Vec<(&str, bool)> = Vec::new();
+    for (metric, label) in metrics.iter().zip(&labels) {
+        if !tracks.iter().any(|(l, _)| *l == label.as_str()) {
+            tracks.push((label, is_gpu(metric)));
+        }
+    }
+    tracks.sort_by_key(|&(_, gpu)| gpu);
+
+    // Depth within the track, following parents on the same track.
+    let index_of: HashMap<u64, usize> = metrics.iter().enumerate().filter(|(_, m)| m.id != 0).map(|(i, m)| (m.id, i)).collect();
+    let depths: Vec<usize> = (0..metrics.len()).map(|i| {
+        let (mut depth, mut current) = (0, i);
+        while let Some(&parent) = metrics[current].parent_id.and_then(|p| index_of.get(&p)) {
+            if labels[parent] != labels[i] || depth == MAX_DEPTH - 1 {
+                break;
+            }
+            depth += 1;
+            current = parent;
+        }
+        depth
+    }).collect();
+
+    let mut lane_top: HashMap<&str, f64> = HashMap::new();
+    let mut y = AXIS_HEIGHT;
+    for &(label, _) in &tracks {
+        let rows = metrics.iter().zip(&labels).zip(&depths)
+            .filter(|((_, l), _)| l.as_str() == label)
+            .map(|(_, &d)| d + 1)
+            .max()
+            .unwrap_or(1);
+        lane_top.insert(label, y);
+        y += rows as f64 * ROW_HEIGHT + 6.0;
+    }
+    let height = y;
+
+    let mut shown: Vec<usize> = (0..metrics.len()).collect();
+    if shown.len() > MAX_TIMELINE_SPANS {
+        shown.sort_by(|&a, &b| metrics[b].duration.cmp(&metrics[a].duration));
+        shown.truncate(MAX_TIMELINE_SPANS);
+        let _ = writeln!(html, "<p class=\"muted\">Showing the {} longest of {} spans.</p>", MAX_TIMELINE_SPANS, metrics.len());
+    }
+
+    let range = end.saturating_sub(start).as_secs_f64().max(1e-9);
+    let plot = WIDTH - LABEL_WIDTH;
+    let x_of = |t: Duration| LABEL_WIDTH + t.saturating_sub(start).as_secs_f64() / range * plot;
+
+    let _ = write!(html, "<svg width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">", w = WIDTH, h = height);
+    for i in 0..=4 {
+        let x = LABEL_WIDTH + plot * i as f64 / 4.0;
+        let anchor = match i { 0 => "start", 4 => "end", _ => "middle" };
+        let _ = write!(html, "<line x1=\"{x:.2}\" y1=\"{a}\" x2=\"{x:.2}\" y2=\"{h}\" stroke=\"#e3e3e3\"/><text x=\"{x:.2}\" y=\"12\" text-anchor=\"{}\">{:.3} ms</text>",
+            anchor, range * 1000.0 * i as f64 / 4.0, a = AXIS_HEIGHT - 4.0, h = height);
+    }
+    for &(label, _) in &tracks {
+        let _ = write!(html, "<text x=\"4\" y=\"{:.2}\">{}</text>", lane_top[label] + ROW_HEIGHT - 4.0, escape(label));
+    }
+    for i in shown {
+        let metric = &metrics[i];
+        let x = x_of(metric.start);
+        let width = (x_of(metric.start + metric.duration) - x).max(0.5);
+        let y = lane_top[labels[i].as_str()] + depths[i] as f64 * ROW_HEIGHT;
+        let _ = write!(html, "<rect x=\"{:.2}\" y=\"{:.2}\" width=\"{:.2}\" height=\"{:.2}\" fill=\"{}\"><title>{} [{}/{}] {:.4} ms</title></rect>",
+            x, y, width, ROW_HEIGHT - 2.0, color(&metric.name), escape(&metric.name), escape(&metric.device), escape(&metric.backend), ms(metric.duration));
+    }
+    html.push_str("</svg>\n");
+}
+
+/// One histogram per operation over log-spaced bins between its fastest and
+/// slowest call, with p50 and p99 markers.
+fn histogram_section(html: &mut String, metrics: &[Metric]) {
+    html.push_str("<h2>Latency</h2>\n");
+    let stats = operation_stats(metrics);
+    if stats.is_empty() {
+        html.push_str("<p class=\"muted\">No spans recorded.</p>\n");
+        return;
+    }
+
+    html.push_str("<div class=\"ops\">\n");
+    for op in &stats {
+        let durations: Vec<f64> = metrics.iter()
+            .filter(|m| m.name == op.name && m.device == op.device && m.backend == op.backend)
+            .map(|m| m.duration.as_secs_f64().max(1e-9))
+            .collect();
+        let (low, high) = (op.min.as_secs_f64().max(1e-9).ln(), op.max.as_secs_f64().max(1e-9).ln());
+        let step = ((high - low) / HISTOGRAM_BINS as f64).max(f64::EPSILON);
+        let bin_of = |secs: f64| (((secs.ln() - low) / step) as usize).min(HISTOGRAM_BINS - 1);
+        let mut bins = [0u64; HISTOGRAM_BINS];
+        for &secs in &durations {
+            bins[bin_of(secs)] += 1;
+        }
+        let tallest = bins.iter().copied().max().unwrap_or(1).max(1) as f64;
+        let bar = HISTOGRAM_WIDTH / HISTOGRAM_BINS as f64;
+
+        let _ = write!(html, "<div class=\"op\"><b>{}</b> <span class=\"muted\">{}/{} &middot; {} calls</span>",
+            escape(&op.name), escape(&op.device), escape(&op.backend), op.count);
+        let _ = write!(html, "<svg width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">", w = HISTOGRAM_WIDTH, h = HISTOGRAM_HEIGHT);
+        for (i, &count) in bins.iter().enumerate().filter(|(_, &c)| c > 0) {
+            let height = count as f64 / tallest * (HISTOGRAM_HEIGHT - 4.0);
+            let (from, to) = ((low + step * i as f64).exp(), (low + step * (i + 1) as f64).exp());
+            let _ = write!(html, "<rect x=\"{:.2}\" y=\"{:.2}\" width=\"{:.2}\" height=\"{:.2}\" fill=\"{}\"><title>{:.4}-{:.4} ms: {} calls</title></rect>",
+                i as f64 * bar, HISTOGRAM_HEIGHT - height, bar - 1.0, height, color(&op.name), from * 1000.0, to * 1000.0, count);
+        }
+        for (label, value) in [("p50", op.p50), ("p99", op.p99)] {
+            let position = ((value.as_secs_f64().max(1e-9).ln() - low) / step / HISTOGRAM_BINS as f64).clamp(0.0, 1.0);
+            let x = position * (HISTOGRAM_WIDTH - 1.0) + 0.5;
+            let _ = write!(html, "<line x1=\"{x:.2}\" y1=\"0\" x2=\"{x:.2}\" y2=\"{h}\" stroke=\"#333\" stroke-dasharray=\"3 2\"><title>{} {:.4} ms</title></line>",
+                label, ms(value), h = HISTOGRAM_HEIGHT);
+        }
+        let _ = writeln!(html, "</svg><span class=\"muted\">min {:.4} &middot; p50 {:.4} &middot; p99 {:.4} &middot; max {:.4} ms</span></div>",
+            ms(op.min), ms(op.p50), ms(op.p99), ms(op.max));
+    }
+    html.push_str("</div>\n");
+}
+
+impl ReportExporter for HtmlReportExporter {
+    /// `data` must serialize as a list of [`Metric`]s, e.g. a profiler snapshot.
+    fn export<T: Serialize>(&self, data: &T, path: &str) -> std::io::Result<()> {
+        let metrics = decode_metrics(data, "HTML reports")?;
+        File::create(path)?.write_all(self.render(&metrics).as_bytes())
+    }
+}
@@ -1,6 +1,37 @@
+mod chrome;
+mod csv;
+mod html;
+
+pub use chrome::{ChromeTraceExporter, CounterSample};
+pub use csv::CsvWriter;
+pub use html::{HtmlReportExporter, MAX_TIMELINE_SPANS};
+
+use crate::profiler::core::Metric;
 use serde::Serialize;
 use std::fs::File;
-use std::io::Write;
+use std::io::BufWriter;
+use std::time::Duration;
+
+fn micros(d: Duration) -> f64 {
+    d.as_nanos() as f64 / 1000.0
+}
+
+fn ms(d: Duration) -> f64 {
+    d.as_secs_f64() * 1000.0
+}
+
+fn is_gpu(metric: &Metric) -> bool {
+    metric.device.contains("GPU")
+}
+
+/// Decodes `data` as a list of [`Metric`]s for exporters that need them.
+///
+/// `format` names the output in the error, e.g. "Chrome traces".
+fn decode_metrics<T: Serialize>(data: &T, format: &str) -> std::io::Result<Vec<Metric>> {
+    serde_json::to_value(data)
+        .and_then(serde_json::from_value)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, format!("{} need profiler metrics: {}", format, e)))
+}
 
 /// Trait for exporting collected profiling metrics to external formats.
 pub trait ReportExporter {
@@ -18,15 +49,24 @@ impl ReportExporter for JsonExporter {
     }
 }
 
-/// Exporter that writes profiling data to a CSV file.
+/// Exporter that writes profiling data to an RFC 4180 CSV file.
 ///
-/// Records timestamp, metric name, and value for each entry.
+/// `data` is a sequence of records (e.g. a profiler snapshot) or a single
+/// record. See [`CsvWriter`] for the column layout and for appending to a
+/// file from a long-running service.
 pub struct CsvExporter;
+
+impl CsvExporter {
+    /// Renders `data` as a CSV string.
+    pub fn render<T: Serialize>(data: &T) -> std::io::Result<String> {
+        let bytes = csv::write_all(data, Vec::new())?;
+        String::from_utf8(bytes).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
+}
+
 impl ReportExporter for CsvExporter {
-    fn export<T: Serialize>(&self, _data: &T, path: &str) -> std::io::Result<()> {
-        // Simple placeholder for CSV export logic
-        let mut file = File::create(path)?;
-        writeln!(file, "Timestamp, Metric, Value")?;
+    fn export<T: Serialize>(&self, data: &T, path: &str) -> std::io::Result<()> {
+        csv::write_all(data, BufWriter::new(File::create(path)?))?;
         Ok(())
     }
 }
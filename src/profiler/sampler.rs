@@ -1,26 +1,194 @@
-use crate::detect::HardwareState;
+//! Time series of system load, memory and CPU frequency.
+//!
+//! Samples come from the dynamic facts of the [`HardwareCache`], which
+//! refreshes CPU usage and memory without re-probing static hardware or GPUs.
+//! A sampler keeps the latest `capacity` samples in a ring buffer and can be
+//! fed by the hardware cache's background sampler, see [`start_system_sampling`].
 
+use crate::detect::{DynamicFacts, HardwareCache};
+use crate::profiler::core::get_profiler;
+use crate::profiler::reports::CounterSample;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Interval of the background sampler when none is given.
+pub const DEFAULT_SAMPLE_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Samples kept by default; 10 minutes at the default interval.
+pub const DEFAULT_SAMPLE_CAPACITY: usize = 6_000;
+
+/// One point of the system time series.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct SystemSample {
+    /// When the sample was taken, relative to the profiler epoch.
+    pub at: Duration,
+    /// Average CPU usage (%) since the previous refresh.
+    pub cpu_load: f32,
+    pub memory_used_kb: u64,
+    pub memory_total_kb: u64,
+    pub frequency_mhz: u64,
+    pub temperature_c: Option<f32>,
+}
+
+/// Bounded history of [`SystemSample`]s.
+///
+/// All methods take `&self`, so one sampler can be filled by a background
+/// thread while the decision layer queries it.
 pub struct Sampler {
-    pub history: Vec<HardwareState>,
-    pub max_samples: usize,
+    capacity: usize,
+    samples: Mutex<VecDeque<SystemSample>>,
 }
 
 impl Sampler {
-    pub fn new(max_samples: usize) -> Self {
-        Self {
-            history: Vec::with_capacity(max_samples),
-            max_samples,
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self { capacity, samples: Mutex::new(VecDeque::with_capacity(capacity)) }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Refreshes the hardware cache and records the result.
+    pub fn sample(&self) {
+        self.record_facts(&HardwareCache::global().refresh());
+    }
+
+    fn record_facts(&self, facts: &DynamicFacts) {
+        self.record(SystemSample {
+            at: get_profiler().now(),
+            cpu_load: facts.cpu_usage,
+            memory_used_kb: facts.memory.total_kb.saturating_sub(facts.memory.available_kb),
+            memory_total_kb: facts.memory.total_kb,
+            frequency_mhz: facts.frequency_mhz,
+            temperature_c: facts.temperature_c,
+        });
+    }
+
+    /// Appends a sample, overwriting the oldest one when full.
+    pub fn record(&self, sample: SystemSample) {
+        let Ok(mut samples) = self.samples.lock() else { return };
+        if samples.len() == self.capacity {
+            samples.pop_front();
         }
+        samples.push_back(sample);
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.lock().map(|s| s.len()).unwrap_or(0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
     }
 
-    pub fn sample(&mut self) {
-        if self.history.len() >= self.max_samples {
-            self.history.remove(0);
+    pub fn clear(&self) {
+        if let Ok(mut samples) = self.samples.lock() {
+            samples.clear();
         }
-        self.history.push(HardwareState::capture());
+    }
+
+    /// All retained samples, oldest first.
+    pub fn history(&self) -> Vec<SystemSample> {
+        self.samples.lock().map(|s| s.iter().copied().collect()).unwrap_or_default()
+    }
+
+    pub fn latest(&self) -> Option<SystemSample> {
+        self.samples.lock().ok()?.back().copied()
     }
 
     pub fn current_load(&self) -> f32 {
-        self.history.last().map(|s| s.cpu.usage).unwrap_or(0.0)
+        self.latest().map(|s| s.cpu_load).unwrap_or(0.0)
+    }
+
+    /// Samples taken during the last `window` before the latest sample.
+    pub fn window(&self, window: Duration) -> Vec<SystemSample> {
+        let Ok(samples) = self.samples.lock() else { return Vec::new() };
+        let Some(latest) = samples.back() else { return Vec::new() };
+        let from = latest.at.saturating_sub(window);
+        let first = samples.partition_point(|s| s.at < from);
+        samples.range(first..).copied().collect()
+    }
+
+    /// Mean CPU load (%) over the last `window`, or `None` without samples.
+    pub fn average_load(&self, window: Duration) -> Option<f32> {
+        let samples = self.window(window);
+        if samples.is_empty() {
+            return None;
+        }
+        Some(samples.iter().map(|s| s.cpu_load).sum::<f32>() / samples.len() as f32)
+    }
+
+    /// Slope of the CPU load over the last `window` in percentage points per
+    /// second (least squares). Positive means the load is rising; `None` with
+    /// fewer than two samples at different times.
+    pub fn load_trend(&self, window: Duration) -> Option<f32> {
+        let samples = self.window(window);
+        let n = samples.len() as f64;
+        if samples.len() < 2 {
+            return None;
+        }
+        let mean_t = samples.iter().map(|s| s.at.as_secs_f64()).sum::<f64>() / n;
+        let mean_load = samples.iter().map(|s| s.cpu_load as f64).sum::<f64>() / n;
+        let (mut covariance, mut variance) = (0.0, 0.0);
+        for s in &samples {
+            let dt = s.at.as_secs_f64() - mean_t;
+            covariance += dt * (s.cpu_load as f64 - mean_load);
+            variance += dt * dt;
+        }
+        (variance > 0.0).then(|| (covariance / variance) as f32)
+    }
+
+    /// CPU load (%), used memory (MiB) and frequency (MHz) of every sample,
+    /// for counter tracks in a [`ChromeTraceExporter`](crate::profiler::ChromeTraceExporter) trace.
+    pub fn counters(&self) -> Vec<CounterSample> {
+        self.history().into_iter().flat_map(|s| {
+            [
+                CounterSample { name: "CPU Load (%)".to_string(), at: s.at, value: s.cpu_load as f64 },
+                CounterSample { name: "Memory Used (MiB)".to_string(), at: s.at, value: s.memory_used_kb as f64 / 1024.0 },
+                CounterSample { name: "CPU Frequency (MHz)".to_string(), at: s.at, value: s.frequency_mhz as f64 },
+            ]
+        }).collect()
+    }
+}
+
+impl Default for Sampler {
+    fn default() -> Self {
+        Self::new(DEFAULT_SAMPLE_CAPACITY)
+    }
+}
+
+static SYSTEM_SAMPLER: Mutex<Option<Arc<Sampler>>> = Mutex::new(None);
+
+/// Records every `interval` into a new sampler of `capacity` samples,
+/// replacing any running one.
+///
+/// Samples are taken on the hardware cache's sampler thread (see
+/// [`HardwareCache::start_sampler_with`]), which keeps the dynamic facts fresh
+/// for every other reader too. Starting a cache sampler directly afterwards
+/// replaces that thread and ends the recording.
+pub fn start_system_sampling(interval: Duration, capacity: usize) -> Arc<Sampler> {
+    let sampler = Arc::new(Sampler::new(capacity));
+    let recorder = sampler.clone();
+    HardwareCache::global().start_sampler_with(interval, move |facts| recorder.record_facts(facts));
+    if let Ok(mut running) = SYSTEM_SAMPLER.lock() {
+        *running = Some(sampler.clone());
     }
+    sampler
+}
+
+/// Stops the background sampling. Its history stays readable through any
+/// `Arc` obtained earlier.
+pub fn stop_system_sampling() {
+    let running = SYSTEM_SAMPLER.lock().ok().and_then(|mut r| r.take());
+    if running.is_some() {
+        HardwareCache::global().stop_sampler();
+    }
+}
+
+/// The running sampler, if any.
+pub fn system_sampler() -> Option<Arc<Sampler>> {
+    SYSTEM_SAMPLER.lock().ok()?.clone()
 }
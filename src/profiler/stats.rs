@@ -0,0 +1,101 @@
+//! Per-operation latency statistics of profiler snapshots.
+
+use crate::profiler::core::Metric;
+use crate::profiler::histogram::LatencyHistogram;
+use serde::Serialize;
+use std::time::Duration;
+
+/// Metadata key with the number of elements an operation processed.
+pub const ELEMENTS_KEY: &str = "elements";
+/// Metadata key with the number of bytes an operation read and wrote.
+pub const BYTES_KEY: &str = "bytes";
+
+/// Aggregate of all metrics with the same name, device and backend.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OperationStats {
+    pub name: String,
+    pub device: String,
+    pub backend: String,
+    pub count: u64,
+    pub total: Duration,
+    pub min: Duration,
+    pub max: Duration,
+    pub mean: Duration,
+    pub p50: Duration,
+    pub p90: Duration,
+    pub p99: Duration,
+    pub p999: Duration,
+    /// Elements per second over all calls that reported an element count.
+    pub elements_per_sec: Option<f64>,
+    /// Gigabytes (10^9 bytes) per second over all calls that reported a byte count.
+    pub gb_per_sec: Option<f64>,
+    #[serde(skip)]
+    pub histogram: LatencyHistogram,
+}
+
+#[derive(Default)]
+struct Accumulator {
+    histogram: LatencyHistogram,
+    elements: u64,
+    elements_time: Duration,
+    bytes: u64,
+    bytes_time: Duration,
+}
+
+fn size(metric: &Metric, key: &str) -> Option<u64> {
+    metric.metadata.get(key).and_then(|v| v.parse().ok())
+}
+
+fn rate(amount: u64, time: Duration, scale: f64) -> Option<f64> {
+    (amount > 0 && !time.is_zero()).then(|| amount as f64 / time.as_secs_f64() / scale)
+}
+
+/// Aggregates `metrics` per operation, in order of first appearance.
+///
+/// Throughput uses the [`ELEMENTS_KEY`] and [`BYTES_KEY`] metadata, which
+/// `ProfileScope::with_size` sets.
+pub fn operation_stats(metrics: &[Metric]) -> Vec<OperationStats> {
+    let mut groups: Vec<(&Metric, Accumulator)> = Vec::new();
+    for metric in metrics {
+        let index = match groups.iter().position(|(first, _)| {
+            first.name == metric.name && first.device == metric.device && first.backend == metric.backend
+        }) {
+            Some(index) => index,
+            None => {
+                groups.push((metric, Accumulator::default()));
+                groups.len() - 1
+            }
+        };
+        let acc = &mut groups[index].1;
+        acc.histogram.record(metric.duration);
+        if let Some(elements) = size(metric, ELEMENTS_KEY) {
+            acc.elements += elements;
+            acc.elements_time += metric.duration;
+        }
+        if let Some(bytes) = size(metric, BYTES_KEY) {
+            acc.bytes += bytes;
+            acc.bytes_time += metric.duration;
+        }
+    }
+
+    groups.into_iter().map(|(first, acc)| {
+        let h = acc.histogram;
+        OperationStats {
+            name: first.name.clone(),
+            device: first.device.clone(),
+            backend: first.backend.clone(),
+            count: h.count(),
+            total: h.total(),
+            min: h.min(),
+            max: h.max(),
+            mean: h.mean(),
+            p50: h.percentile(50.0),
+            p90: h.percentile(90.0),
+            p99: h.percentile(99.0),
+            p999: h.percentile(99.9),
+            elements_per_sec: rate(acc.elements, acc.elements_time, 1.0),
+            gb_per_sec: rate(acc.bytes, acc.bytes_time, 1e9),
+            histogram: h,
+        }
+    }).collect()
+}
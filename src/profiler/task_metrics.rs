@@ -7,6 +7,12 @@ pub struct TaskMetrics {
     pub cpu_usage_end: f32,
 }
 
+impl Default for TaskMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl TaskMetrics {
     pub fn new() -> Self {
         Self {
@@ -0,0 +1,88 @@
+//! Call-tree aggregation of profiler snapshots.
+
+use crate::profiler::core::Metric;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// One row of the call tree: all spans with the same name, device and backend
+/// under the same parent row.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SpanSummary {
+    /// Nesting level; roots are 0.
+    pub depth: usize,
+    pub name: String,
+    pub device: String,
+    pub backend: String,
+    pub calls: usize,
+    pub total: Duration,
+    /// `total` minus the time spent in child spans. Children running in
+    /// parallel can add up to more than their parent; self time is then zero.
+    pub self_time: Duration,
+}
+
+/// Builds the call tree of `metrics` in depth-first order.
+///
+/// Spans whose parent is not in `metrics` (e.g. overwritten in the ring
+/// buffer) are treated as roots. Rows keep the order of their first span.
+pub fn summarize(metrics: &[Metric]) -> Vec<SpanSummary> {
+    let known: HashMap<u64, usize> = metrics.iter().enumerate()
+        .filter(|(_, m)| m.id != 0)
+        .map(|(i, m)| (m.id, i))
+        .collect();
+
+    let mut children: HashMap<u64, Vec<usize>> = HashMap::new();
+    let mut roots = Vec::new();
+    for (i, metric) in metrics.iter().enumerate() {
+        match metric.parent_id.filter(|p| known.contains_key(p)) {
+            Some(parent) => children.entry(parent).or_default().push(i),
+            None => roots.push(i),
+        }
+    }
+
+    let mut rows = Vec::new();
+    summarize_level(metrics, &children, &roots, 0, &mut rows);
+    rows
+}
+
+fn summarize_level(
+    metrics: &[Metric],
+    children: &HashMap<u64, Vec<usize>>,
+    spans: &[usize],
+    depth: usize,
+    rows: &mut Vec<SpanSummary>,
+) {
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    for &i in spans {
+        let m = &metrics[i];
+        let same = |g: &Vec<usize>| {
+            let first = &metrics[g[0]];
+            first.name == m.name && first.device == m.device && first.backend == m.backend
+        };
+        match groups.iter_mut().find(|g| same(g)) {
+            Some(group) => group.push(i),
+            None => groups.push(vec![i]),
+        }
+    }
+
+    for group in groups {
+        let nested: Vec<usize> = group.iter()
+            .filter(|&&i| metrics[i].id != 0)
+            .flat_map(|&i| children.get(&metrics[i].id).into_iter().flatten().copied())
+            .collect();
+        let total: Duration = group.iter().map(|&i| metrics[i].duration).sum();
+        let in_children: Duration = nested.iter().map(|&i| metrics[i].duration).sum();
+
+        let first = &metrics[group[0]];
+        rows.push(SpanSummary {
+            depth,
+            name: first.name.clone(),
+            device: first.device.clone(),
+            backend: first.backend.clone(),
+            calls: group.len(),
+            total,
+            self_time: total.saturating_sub(in_children),
+        });
+        summarize_level(metrics, children, &nested, depth + 1, rows);
+    }
+}
@@ -32,6 +32,7 @@ impl ArchX {
     }
 
     /// Compatibility alias for `adaptive()`.
+    #[allow(clippy::new_ret_no_self)]
     pub fn new() -> ArchXBuilder {
         ArchXBuilder::new()
     }
@@ -66,6 +67,12 @@ pub struct ArchXBuilder {
     gpu_enabled: bool,
 }
 
+impl Default for ArchXBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ArchXBuilder {
     pub fn new() -> Self {
         Self { 
@@ -135,6 +142,7 @@ impl ArchXBuilder {
         self.to_sovereign().run(task)
     }
 
+    #[allow(clippy::wrong_self_convention)]
     fn to_sovereign(self) -> crate::public_api::sovereign::SovereignBuilder {
         crate::public_api::ArchX::compute()
             .with_policy(self.policy)
@@ -8,6 +8,12 @@ pub struct MathBuilder {
     gpu_policy: GpuPolicy,
 }
 
+impl Default for MathBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl MathBuilder {
     pub fn new() -> Self {
         Self {
@@ -64,6 +70,7 @@ impl MathBuilder {
         self.to_sovereign().sum(a).unwrap_or(0.0)
     }
 
+    #[allow(clippy::wrong_self_convention)]
     fn to_sovereign(self) -> crate::public_api::sovereign::SovereignBuilder {
         crate::public_api::ArchX::compute()
             .with_mode(self.mode)
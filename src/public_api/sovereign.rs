@@ -3,7 +3,30 @@ use crate::gpu::GpuPolicy;
 use crate::math::MathMode;
 use crate::error::{ArchXResult, ArchXError};
 
-use crate::core::CoreEngine;
+use crate::core::{CoreEngine, MemoryReservation};
+use crate::core::resource::ResourceManager;
+use crate::runtime::{ArchXFuture, BlockingPool, CancellationToken, ExecControl, Progress};
+use std::ops::Deref;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// The engine a builder runs on: the global one, or one supplied by the caller.
+enum EngineRef {
+    Global(&'static CoreEngine),
+    Shared(Arc<CoreEngine>),
+}
+
+impl Deref for EngineRef {
+    type Target = CoreEngine;
+
+    fn deref(&self) -> &CoreEngine {
+        match self {
+            EngineRef::Global(engine) => engine,
+            EngineRef::Shared(engine) => engine,
+        }
+    }
+}
+
 /// The flagship unified builder for ArchX v3.0 (Sovereign Fluent).
 ///
 /// `SovereignBuilder` provides a single, chainable interface for high-performance 
@@ -16,7 +39,14 @@ pub struct SovereignBuilder {
     math_mode: MathMode,
     max_threads: Option<usize>,
     profiling: bool,
-    engine: &'static CoreEngine,
+    control: ExecControl,
+    engine: EngineRef,
+}
+
+impl Default for SovereignBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl SovereignBuilder {
@@ -28,10 +58,25 @@ impl SovereignBuilder {
             math_mode: MathMode::Balanced,
             max_threads: None,
             profiling: false,
-            engine: CoreEngine::global(),
+            control: ExecControl::none(),
+            engine: EngineRef::Global(CoreEngine::global()),
         }
     }
 
+    /// Runs on `engine` instead of the global one, e.g. one built with
+    /// `CoreEngine::builder()` around a `StaticHardwareProvider`.
+    ///
+    /// The engine's scheduler is shared by every builder using it: like
+    /// [`with_policy`](Self::with_policy) and [`with_gpu`](Self::with_gpu),
+    /// this sets the builder's current policies on that scheduler, where they
+    /// stay in effect for other builders on the same engine.
+    pub fn with_engine(mut self, engine: Arc<CoreEngine>) -> Self {
+        engine.scheduler.set_policy(self.policy);
+        engine.scheduler.set_gpu_policy(self.gpu_policy);
+        self.engine = EngineRef::Shared(engine);
+        self
+    }
+
     /// Sets the execution policy (Speed, Balance, Privacy, Throttled).
     ///
     /// The policy influences how the scheduler balances performance vs thermal/power efficiency.
@@ -82,12 +127,45 @@ impl SovereignBuilder {
         self
     }
 
+    /// Attaches a cancellation token.
+    ///
+    /// Cancelling the token stops the operation at its next chunk boundary and
+    /// makes it return `ArchXError::Cancelled`.
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.control = self.control.with_token(token);
+        self
+    }
+
+    /// Fails the operation with `ArchXError::DeadlineExceeded` once `deadline` passes.
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.control = self.control.with_deadline(deadline);
+        self
+    }
+
+    /// Convenience for `with_deadline(Instant::now() + timeout)`.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.control = self.control.with_timeout(timeout);
+        self
+    }
+
+    /// Registers a callback invoked after each completed chunk.
+    ///
+    /// The callback runs on worker threads and should return quickly.
+    pub fn on_progress<F>(mut self, f: F) -> Self
+    where
+        F: Fn(Progress) + Send + Sync + 'static,
+    {
+        self.control = self.control.with_progress(f);
+        self
+    }
+
     // --- Math Terminal Operations ---
 
     /// Performs vectorized element-wise addition: `out = a + b`.
     ///
     /// # Errors
-    /// Returns `ArchXError::InvalidInput` if slice lengths do not match.
+    /// Returns `ArchXError::InvalidInput` if slice lengths do not match, or
+    /// `Cancelled`/`DeadlineExceeded` if stopped by the configured control.
     ///
     /// ```rust
     /// # use archx::archx;
@@ -98,47 +176,50 @@ impl SovereignBuilder {
             return Err(ArchXError::InvalidInput("Slices must have identical lengths".to_string()));
         }
         self.pre_exec();
-        self.engine.engine.add(a, b, out, self.math_mode.into())
-            .map_err(|e| ArchXError::ExecutionError(e.to_string()))
+        self.engine.engine.add_with(a, b, out, self.math_mode.into(), &self.control)
+            .map_err(ArchXError::from)
     }
 
     /// Performs vectorized element-wise subtraction: `out = a - b`.
     ///
     /// # Errors
-    /// Returns `ArchXError::InvalidInput` if slice lengths do not match.
+    /// Returns `ArchXError::InvalidInput` if slice lengths do not match, or
+    /// `Cancelled`/`DeadlineExceeded` if stopped by the configured control.
     pub fn sub(self, a: &[f32], b: &[f32], out: &mut [f32]) -> ArchXResult<()> {
         if a.len() != b.len() || a.len() != out.len() {
             return Err(ArchXError::InvalidInput("Slices must have identical lengths".to_string()));
         }
         self.pre_exec();
-        self.engine.engine.sub(a, b, out, self.math_mode.into())
-            .map_err(|e| ArchXError::ExecutionError(e.to_string()))
+        self.engine.engine.sub_with(a, b, out, self.math_mode.into(), &self.control)
+            .map_err(ArchXError::from)
     }
 
     /// Performs vectorized element-wise multiplication: `out = a * b`.
     ///
     /// # Errors
-    /// Returns `ArchXError::InvalidInput` if slice lengths do not match.
+    /// Returns `ArchXError::InvalidInput` if slice lengths do not match, or
+    /// `Cancelled`/`DeadlineExceeded` if stopped by the configured control.
     pub fn mul(self, a: &[f32], b: &[f32], out: &mut [f32]) -> ArchXResult<()> {
         if a.len() != b.len() || a.len() != out.len() {
             return Err(ArchXError::InvalidInput("Slices must have identical lengths".to_string()));
         }
         self.pre_exec();
-        self.engine.engine.mul(a, b, out, self.math_mode.into())
-            .map_err(|e| ArchXError::ExecutionError(e.to_string()))
+        self.engine.engine.mul_with(a, b, out, self.math_mode.into(), &self.control)
+            .map_err(ArchXError::from)
     }
 
     /// Performs the dot product of two vectors: `sum(a[i] * b[i])`.
     ///
     /// # Errors
-    /// Returns `ArchXError::InvalidInput` if slice lengths do not match.
+    /// Returns `ArchXError::InvalidInput` if slice lengths do not match, or
+    /// `Cancelled`/`DeadlineExceeded` if stopped by the configured control.
     pub fn dot(self, a: &[f32], b: &[f32]) -> ArchXResult<f32> {
         if a.len() != b.len() {
             return Err(ArchXError::InvalidInput("Slices must have identical lengths".to_string()));
         }
         self.pre_exec();
-        self.engine.engine.dot(a, b, self.math_mode.into())
-            .map_err(|e| ArchXError::ExecutionError(e.to_string()))
+        self.engine.engine.dot_with(a, b, self.math_mode.into(), &self.control)
+            .map_err(ArchXError::from)
     }
 
     /// Performs a parallel sum reduction of a vector.
@@ -146,8 +227,8 @@ impl SovereignBuilder {
     /// Utilizes multi-threaded reduction on CPU.
     pub fn sum(self, a: &[f32]) -> ArchXResult<f32> {
         self.pre_exec();
-        self.engine.engine.sum(a, self.math_mode.into())
-            .map_err(|e| ArchXError::ExecutionError(e.to_string()))
+        self.engine.engine.sum_with(a, self.math_mode.into(), &self.control)
+            .map_err(ArchXError::from)
     }
 
     // --- Task Terminal Operations ---
@@ -164,6 +245,86 @@ impl SovereignBuilder {
         self.engine.scheduler.run(f).expect("Task execution failed")
     }
 
+    // --- Async Terminal Operations ---
+    //
+    // Each async variant runs its synchronous counterpart on the ArchX blocking
+    // pool, so awaiting never stalls the executor and no particular runtime is required.
+
+    /// Asynchronous `add`. Resolves to the output vector.
+    ///
+    /// The output allocation goes through memory admission and resolves to
+    /// `ArchXError::ResourceLimit` if it does not fit the (cgroup-aware) budget.
+    ///
+    /// ```rust
+    /// # use archx::archx;
+    /// # #[tokio::main] async fn main() -> archx::ArchXResult<()> {
+    /// let out = archx().add_async(vec![1.0; 4], vec![2.0; 4]).await?;
+    /// assert_eq!(out, vec![3.0; 4]);
+    /// # Ok(()) }
+    /// ```
+    pub fn add_async(self, a: Vec<f32>, b: Vec<f32>) -> ArchXFuture<Vec<f32>> {
+        BlockingPool::global().spawn(move || {
+            let resources = self.engine.resources.clone();
+            let _reservation = Self::reserve_output(resources.as_ref(), a.len())?;
+            let mut out = vec![0.0; a.len()];
+            self.add(&a, &b, &mut out).map(|_| out)
+        })
+    }
+
+    /// Asynchronous `sub`. Resolves to the output vector.
+    pub fn sub_async(self, a: Vec<f32>, b: Vec<f32>) -> ArchXFuture<Vec<f32>> {
+        BlockingPool::global().spawn(move || {
+            let resources = self.engine.resources.clone();
+            let _reservation = Self::reserve_output(resources.as_ref(), a.len())?;
+            let mut out = vec![0.0; a.len()];
+            self.sub(&a, &b, &mut out).map(|_| out)
+        })
+    }
+
+    /// Asynchronous `mul`. Resolves to the output vector.
+    pub fn mul_async(self, a: Vec<f32>, b: Vec<f32>) -> ArchXFuture<Vec<f32>> {
+        BlockingPool::global().spawn(move || {
+            let resources = self.engine.resources.clone();
+            let _reservation = Self::reserve_output(resources.as_ref(), a.len())?;
+            let mut out = vec![0.0; a.len()];
+            self.mul(&a, &b, &mut out).map(|_| out)
+        })
+    }
+
+    /// Asynchronous `dot`.
+    pub fn dot_async(self, a: Vec<f32>, b: Vec<f32>) -> ArchXFuture<f32> {
+        BlockingPool::global().spawn(move || self.dot(&a, &b))
+    }
+
+    /// Asynchronous `sum`.
+    pub fn sum_async(self, a: Vec<f32>) -> ArchXFuture<f32> {
+        BlockingPool::global().spawn(move || self.sum(&a))
+    }
+
+    /// Asynchronous `run`. Resolves to `ArchXError::ExecutionError` if the
+    /// scheduler rejects the task or the closure panics.
+    pub fn run_async<F, R>(self, f: F) -> ArchXFuture<R>
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        BlockingPool::global().spawn(move || {
+            // A queued task that was cancelled or expired before starting never runs.
+            self.control.check()?;
+            self.pre_exec();
+            self.engine.scheduler.run(f)
+                .map_err(|e| ArchXError::ExecutionError(e.to_string()))
+        })
+    }
+
+    /// Admits the allocation of an `f32` output of `len` elements.
+    fn reserve_output(resources: &dyn ResourceManager, len: usize) -> ArchXResult<MemoryReservation<'_>> {
+        let bytes = (len * std::mem::size_of::<f32>()) as u64;
+        MemoryReservation::new(resources, bytes).ok_or_else(|| {
+            ArchXError::ResourceLimit(format!("Output of {} bytes exceeds the memory budget", bytes))
+        })
+    }
+
     fn pre_exec(&self) {
         let state = self.engine.hardware.capture_state();
         if self.profiling {
@@ -0,0 +1,183 @@
+//! A snapshot of everything ArchX detects and decides, for diagnostics and
+//! support tickets. Printed by the `archx-info` binary.
+
+use crate::adaptive::{AdaptiveEngine, Strategy};
+use crate::core::DefaultResourceManager;
+use crate::detect::{CgroupLimits, HardwareCache, HardwareState};
+use crate::dispatch::select::{DispatchPath, Selector};
+use crate::gpu::{BackendStatus, GpuPolicy};
+use crate::hardware::SystemInfo;
+use crate::optimizer::scheduler::{PowerMode, WorkloadHints};
+use crate::optimizer::simd::{KernelNames, SimdDispatcher};
+use serde::Serialize;
+use std::fmt;
+
+/// Workload sizes (elements) the strategy table covers by default.
+pub const DEFAULT_SIZES: &[usize] = &[512, 4_096, 32_768, 262_144, 1_048_576, 4_194_304, 16_777_216];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DispatchReport {
+    /// Widest SIMD level the CPU supports.
+    pub best_path: DispatchPath,
+    pub kernels: KernelNames,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LimitsReport {
+    pub logical_threads: usize,
+    /// Threads after cgroup CPU quotas.
+    pub effective_threads: usize,
+    pub rayon_threads: usize,
+    pub cgroup: CgroupLimits,
+    /// Bytes the default resource manager admits right now.
+    pub memory_budget_bytes: u64,
+}
+
+/// Strategies `AdaptiveEngine::evaluate_strategy` picks for one workload size.
+#[derive(Debug, Clone, Serialize)]
+pub struct StrategyRow {
+    pub len: usize,
+    pub balanced: Strategy,
+    pub high_performance: Strategy,
+    pub power_saving: Strategy,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HardwareReport {
+    pub version: &'static str,
+    pub system: SystemInfo,
+    pub state: HardwareState,
+    pub enabled_features: Vec<&'static str>,
+    pub dispatch: DispatchReport,
+    pub gpu_policy: GpuPolicy,
+    pub gpu_backends: Vec<BackendStatus>,
+    pub limits: LimitsReport,
+    pub strategies: Vec<StrategyRow>,
+}
+
+impl HardwareReport {
+    /// Collects a report with the strategy table for [`DEFAULT_SIZES`].
+    pub fn collect() -> Self {
+        Self::collect_for(DEFAULT_SIZES)
+    }
+
+    pub fn collect_for(sizes: &[usize]) -> Self {
+        let system = SystemInfo::detect();
+        let statics = HardwareCache::global().static_facts();
+        let strategies = sizes.iter().map(|&len| {
+            let choose = |power_mode| {
+                AdaptiveEngine::evaluate_strategy(len, &WorkloadHints { power_mode, ..Default::default() }, &system)
+            };
+            StrategyRow {
+                len,
+                balanced: choose(PowerMode::Balanced),
+                high_performance: choose(PowerMode::HighPerformance),
+                power_saving: choose(PowerMode::PowerSaving),
+            }
+        }).collect();
+
+        Self {
+            version: env!("CARGO_PKG_VERSION"),
+            state: HardwareState::capture(),
+            enabled_features: system.cpu.features.enabled(),
+            dispatch: DispatchReport {
+                best_path: Selector::best_path(&system.cpu.features),
+                kernels: SimdDispatcher::global().kernels,
+            },
+            gpu_policy: crate::gpu::get_gpu_policy(),
+            gpu_backends: crate::gpu::backend_status(),
+            limits: LimitsReport {
+                logical_threads: statics.logical_threads,
+                effective_threads: statics.effective_threads,
+                rayon_threads: rayon::current_num_threads(),
+                cgroup: statics.cgroup.clone(),
+                memory_budget_bytes: DefaultResourceManager::new().memory_budget(),
+            },
+            strategies,
+            system,
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_else(|_| "{}".to_string())
+    }
+}
+
+fn yes_no(value: bool) -> &'static str {
+    if value { "yes" } else { "no" }
+}
+
+fn strategy_name(strategy: &Strategy) -> String {
+    match strategy {
+        Strategy::ParallelSimd(threads) => format!("ParallelSimd({})", threads),
+        other => format!("{:?}", other),
+    }
+}
+
+impl fmt::Display for HardwareReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cpu = &self.system.cpu;
+        writeln!(f, "ArchX {} hardware report", self.version)?;
+
+        writeln!(f, "\n== CPU ==")?;
+        writeln!(f, "Brand:             {}", cpu.brand)?;
+        writeln!(f, "Architecture:      {:?} ({:?})", cpu.arch, cpu.bits)?;
+        writeln!(f, "Cores / threads:   {} / {}", cpu.cores, cpu.logical_processors)?;
+        writeln!(f, "P / E threads:     {} / {}", self.state.cpu.performance_threads, self.state.cpu.efficiency_threads)?;
+        writeln!(f, "Frequency:         {} MHz", self.state.cpu.frequency_mhz)?;
+        writeln!(f, "Usage:             {:.1}%", self.state.cpu.usage)?;
+        writeln!(f, "Features:          {}", self.enabled_features.join(" "))?;
+
+        writeln!(f, "\n== Dispatch ==")?;
+        writeln!(f, "Best path:         {:?}", self.dispatch.best_path)?;
+        let k = &self.dispatch.kernels;
+        writeln!(f, "Kernels:           add={} sub={} mul={} dot={} sum={}", k.add, k.sub, k.mul, k.dot, k.sum)?;
+
+        writeln!(f, "\n== Memory ==")?;
+        writeln!(f, "Total:             {} MiB", self.state.memory.total_kb / 1024)?;
+        writeln!(f, "Available:         {} MiB", self.state.memory.available_kb / 1024)?;
+        writeln!(f, "Pressure:          {:.0}%", self.state.memory.pressure_estimate * 100.0)?;
+
+        writeln!(f, "\n== GPU ==")?;
+        if self.system.gpus.is_empty() {
+            writeln!(f, "Devices:           none")?;
+        }
+        for gpu in &self.system.gpus {
+            let memory = gpu.memory_gb.map(|gb| format!("{:.1} GB", gb)).unwrap_or_else(|| "unknown".to_string());
+            writeln!(f, "Device:            {} ({}, {}, driver {}, VRAM {})",
+                gpu.name,
+                gpu.vendor.as_deref().unwrap_or("unknown"),
+                if gpu.is_integrated { "integrated" } else { "discrete" },
+                gpu.driver.as_deref().unwrap_or("unknown"),
+                memory)?;
+        }
+        writeln!(f, "Policy:            {:?}", self.gpu_policy)?;
+        for backend in &self.gpu_backends {
+            writeln!(f, "Backend:           {:<10} available={} quarantined={}{}",
+                backend.name, yes_no(backend.available), yes_no(backend.quarantined),
+                if backend.registered { " (registered)" } else { "" })?;
+        }
+
+        writeln!(f, "\n== Limits ==")?;
+        writeln!(f, "Effective threads: {} of {} (rayon pool {})",
+            self.limits.effective_threads, self.limits.logical_threads, self.limits.rayon_threads)?;
+        match self.limits.cgroup.version {
+            Some(version) => writeln!(f, "Cgroup:            {:?}, CPU quota {}, memory limit {}",
+                version,
+                self.limits.cgroup.cpu_quota.map(|q| format!("{:.2}", q)).unwrap_or_else(|| "none".to_string()),
+                self.limits.cgroup.memory_limit_bytes.map(|b| format!("{} MiB", b / 1024 / 1024)).unwrap_or_else(|| "none".to_string()))?,
+            None => writeln!(f, "Cgroup:            none")?,
+        }
+        writeln!(f, "Memory budget:     {} MiB", self.limits.memory_budget_bytes / 1024 / 1024)?;
+        writeln!(f, "Power source:      {:?}{}", self.state.context.power_source,
+            self.state.context.battery_level.map(|l| format!(" ({:.0}%)", l)).unwrap_or_default())?;
+
+        writeln!(f, "\n== Strategies ==")?;
+        writeln!(f, "{:>12}  {:<20}  {:<20}  PowerSaving", "elements", "Balanced", "HighPerformance")?;
+        for row in &self.strategies {
+            writeln!(f, "{:>12}  {:<20}  {:<20}  {}", row.len,
+                strategy_name(&row.balanced), strategy_name(&row.high_performance), strategy_name(&row.power_saving))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,46 @@
+//! Thread-to-CPU pinning.
+
+/// CPUs the calling thread may run on (its cpuset / affinity mask).
+///
+/// Returns `None` if the platform cannot report it.
+#[cfg(target_os = "linux")]
+pub fn allowed_cpus() -> Option<Vec<usize>> {
+    // SAFETY: cpu_set_t is plain data; all-zero is the empty set.
+    let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+    // SAFETY: `set` is a valid cpu_set_t and pid 0 targets the calling thread.
+    if unsafe { libc::sched_getaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &mut set) } != 0 {
+        return None;
+    }
+    // SAFETY: every index is below CPU_SETSIZE.
+    Some((0..libc::CPU_SETSIZE as usize).filter(|&cpu| unsafe { libc::CPU_ISSET(cpu, &set) }).collect())
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn allowed_cpus() -> Option<Vec<usize>> {
+    None
+}
+
+/// Restricts the calling thread to `cpus`.
+///
+/// Returns `false` if pinning is unsupported on this platform, `cpus` is
+/// empty, or the OS rejected the set (e.g. CPUs outside the cgroup cpuset).
+/// Callers treat pinning as a hint and keep running either way.
+#[cfg(target_os = "linux")]
+pub fn pin_current_thread(cpus: &[usize]) -> bool {
+    let max = libc::CPU_SETSIZE as usize;
+    // SAFETY: cpu_set_t is plain data; all-zero is the empty set.
+    let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+    let mut any = false;
+    for &cpu in cpus.iter().filter(|&&cpu| cpu < max) {
+        // SAFETY: `cpu` is below CPU_SETSIZE.
+        unsafe { libc::CPU_SET(cpu, &mut set) };
+        any = true;
+    }
+    // SAFETY: `set` is a valid cpu_set_t and pid 0 targets the calling thread.
+    any && unsafe { libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &set) } == 0
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn pin_current_thread(_cpus: &[usize]) -> bool {
+    false
+}
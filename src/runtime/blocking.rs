@@ -0,0 +1,144 @@
+use crate::error::{ArchXError, ArchXResult};
+use std::future::Future;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::pin::Pin;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::task::{Context, Poll, Waker};
+use std::thread;
+
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+struct Slot<T> {
+    result: Option<ArchXResult<T>>,
+    waker: Option<Waker>,
+}
+
+/// A future resolving to the result of a job on the ArchX blocking pool.
+///
+/// Completion is signalled through the task's `Waker`, so it can be awaited
+/// from any executor (tokio, async-std, smol, or a hand-rolled `block_on`)
+/// without ever blocking the polling thread.
+pub struct ArchXFuture<T> {
+    slot: Arc<Mutex<Slot<T>>>,
+}
+
+impl<T> ArchXFuture<T> {
+    fn pending() -> (Self, Arc<Mutex<Slot<T>>>) {
+        let slot = Arc::new(Mutex::new(Slot { result: None, waker: None }));
+        (Self { slot: slot.clone() }, slot)
+    }
+
+    /// Returns a future that is already resolved to `result`.
+    pub fn ready(result: ArchXResult<T>) -> Self {
+        let (fut, slot) = Self::pending();
+        complete(&slot, result);
+        fut
+    }
+}
+
+impl<T> Future for ArchXFuture<T> {
+    type Output = ArchXResult<T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut slot = match self.slot.lock() {
+            Ok(slot) => slot,
+            Err(_) => return Poll::Ready(Err(ArchXError::ExecutionError("Async result slot poisoned".to_string()))),
+        };
+        match slot.result.take() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                slot.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+fn complete<T>(slot: &Mutex<Slot<T>>, result: ArchXResult<T>) {
+    let waker = match slot.lock() {
+        Ok(mut slot) => {
+            slot.result = Some(result);
+            slot.waker.take()
+        }
+        Err(_) => None,
+    };
+    if let Some(waker) = waker {
+        waker.wake();
+    }
+}
+
+/// A fixed-size pool of threads for running blocking ArchX work off the async executor.
+pub struct BlockingPool {
+    sender: Mutex<Sender<Job>>,
+    workers: usize,
+}
+
+static POOL: OnceLock<BlockingPool> = OnceLock::new();
+
+impl BlockingPool {
+    /// Creates a pool with `workers` threads (at least one).
+    pub fn new(workers: usize) -> Self {
+        let workers = workers.max(1);
+        let (tx, rx) = channel::<Job>();
+        let rx = Arc::new(Mutex::new(rx));
+
+        for i in 0..workers {
+            let rx = rx.clone();
+            let _ = thread::Builder::new()
+                .name(format!("archx-blocking-{}", i))
+                .spawn(move || Self::worker_loop(&rx));
+        }
+
+        Self { sender: Mutex::new(tx), workers }
+    }
+
+    /// The shared pool used by the async API, sized to the effective parallelism
+    /// (cgroup CPU quotas included).
+    pub fn global() -> &'static Self {
+        POOL.get_or_init(|| Self::new(crate::detect::effective_threads()))
+    }
+
+    pub fn workers(&self) -> usize {
+        self.workers
+    }
+
+    /// Runs `task` on the pool and returns a future for its result.
+    ///
+    /// A panicking task resolves to `ArchXError::ExecutionError` instead of
+    /// leaving the future pending forever.
+    pub fn spawn<F, R>(&self, task: F) -> ArchXFuture<R>
+    where
+        F: FnOnce() -> ArchXResult<R> + Send + 'static,
+        R: Send + 'static,
+    {
+        let (fut, slot) = ArchXFuture::pending();
+        let job_slot = slot.clone();
+        let job: Job = Box::new(move || {
+            let result = catch_unwind(AssertUnwindSafe(task))
+                .unwrap_or_else(|_| Err(ArchXError::ExecutionError("Async task panicked".to_string())));
+            complete(&job_slot, result);
+        });
+
+        let sent = self.sender.lock()
+            .map(|tx| tx.send(job).is_ok())
+            .unwrap_or(false);
+        if !sent {
+            complete(&slot, Err(ArchXError::ExecutionError("ArchX blocking pool is unavailable".to_string())));
+        }
+        fut
+    }
+
+    fn worker_loop(rx: &Mutex<Receiver<Job>>) {
+        loop {
+            let job = match rx.lock() {
+                Ok(rx) => rx.recv(),
+                Err(_) => return,
+            };
+            match job {
+                Ok(job) => job(),
+                Err(_) => return,
+            }
+        }
+    }
+}
@@ -0,0 +1,165 @@
+use crate::error::{ArchXError, ArchXResult};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Largest chunk handed to a worker while cancellation or progress is active.
+///
+/// Keeps the interval between control checks in the low milliseconds even
+/// for multi-hundred-million element workloads.
+pub const CONTROLLED_CHUNK_MAX: usize = 1 << 20;
+
+/// A cheap, cloneable flag for cancelling in-flight ArchX operations.
+///
+/// ```rust
+/// use archx::runtime::CancellationToken;
+///
+/// let token = CancellationToken::new();
+/// let handle = token.clone();
+/// handle.cancel();
+/// assert!(token.is_cancelled());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requests cancellation. Operations stop at their next chunk boundary.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Release);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Acquire)
+    }
+}
+
+/// Progress of a chunked operation, reported after each completed chunk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Progress {
+    pub completed_chunks: usize,
+    pub total_chunks: usize,
+}
+
+impl Progress {
+    /// Completion ratio in `0.0..=1.0`.
+    pub fn fraction(&self) -> f32 {
+        if self.total_chunks == 0 {
+            1.0
+        } else {
+            self.completed_chunks as f32 / self.total_chunks as f32
+        }
+    }
+}
+
+pub type ProgressFn = Arc<dyn Fn(Progress) + Send + Sync>;
+
+/// Cancellation, deadline and progress settings for a single operation.
+#[derive(Clone, Default)]
+pub struct ExecControl {
+    pub token: Option<CancellationToken>,
+    pub deadline: Option<Instant>,
+    pub progress: Option<ProgressFn>,
+}
+
+impl ExecControl {
+    /// A control that never cancels and reports nothing.
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    pub fn with_token(mut self, token: CancellationToken) -> Self {
+        self.token = Some(token);
+        self
+    }
+
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    pub fn with_timeout(self, timeout: Duration) -> Self {
+        self.with_deadline(Instant::now() + timeout)
+    }
+
+    pub fn with_progress<F>(mut self, f: F) -> Self
+    where
+        F: Fn(Progress) + Send + Sync + 'static,
+    {
+        self.progress = Some(Arc::new(f));
+        self
+    }
+
+    /// Returns true if any setting requires per-chunk bookkeeping.
+    pub fn is_active(&self) -> bool {
+        self.token.is_some() || self.deadline.is_some() || self.progress.is_some()
+    }
+
+    /// Fails with `Cancelled` or `DeadlineExceeded` if the operation must stop.
+    pub fn check(&self) -> ArchXResult<()> {
+        if self.token.as_ref().is_some_and(|t| t.is_cancelled()) {
+            return Err(ArchXError::Cancelled);
+        }
+        if self.deadline.is_some_and(|d| Instant::now() >= d) {
+            return Err(ArchXError::DeadlineExceeded);
+        }
+        Ok(())
+    }
+
+    /// Caps `chunk_size` so control checks happen often enough.
+    pub fn chunk_size(&self, chunk_size: usize) -> usize {
+        if self.is_active() {
+            chunk_size.clamp(1, CONTROLLED_CHUNK_MAX)
+        } else {
+            chunk_size.max(1)
+        }
+    }
+
+    /// Starts tracking an operation split into `total_chunks` chunks.
+    pub fn tracker(&self, total_chunks: usize) -> ChunkTracker<'_> {
+        ChunkTracker {
+            control: self,
+            completed: AtomicUsize::new(0),
+            total: total_chunks,
+        }
+    }
+}
+
+impl std::fmt::Debug for ExecControl {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ExecControl")
+            .field("token", &self.token)
+            .field("deadline", &self.deadline)
+            .field("progress", &self.progress.is_some())
+            .finish()
+    }
+}
+
+/// Per-operation chunk counter shared by all workers.
+pub struct ChunkTracker<'a> {
+    control: &'a ExecControl,
+    completed: AtomicUsize,
+    total: usize,
+}
+
+impl ChunkTracker<'_> {
+    /// Called before starting a chunk.
+    #[inline]
+    pub fn check(&self) -> ArchXResult<()> {
+        self.control.check()
+    }
+
+    /// Called after finishing a chunk; reports progress if a callback is set.
+    #[inline]
+    pub fn complete(&self) {
+        if let Some(progress) = &self.control.progress {
+            let done = self.completed.fetch_add(1, Ordering::AcqRel) + 1;
+            progress(Progress { completed_chunks: done, total_chunks: self.total });
+        }
+    }
+}
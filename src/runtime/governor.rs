@@ -1 +1,45 @@
+use crate::detect::HardwareState;
+use crate::profiler::sampler::system_sampler;
+use serde::Serialize;
+use std::time::Duration;
+
+/// CPU usage (%) above which `Heuristics::decide` throttles execution.
+pub const HIGH_LOAD_PERCENT: f32 = 90.0;
+
+/// Window over which [`Governor::state`] averages the load when a system
+/// sampler is running, so a single busy refresh does not throttle.
+pub const LOAD_WINDOW: Duration = Duration::from_secs(1);
+
+/// Why ArchX is currently holding back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+pub struct ThrottleState {
+    /// CPU usage is above [`HIGH_LOAD_PERCENT`]; new work runs throttled.
+    pub high_load: bool,
+    /// On battery below the low-battery threshold; balanced policies run as power saving.
+    pub low_power: bool,
+}
+
+impl ThrottleState {
+    pub fn is_throttled(&self) -> bool {
+        self.high_load || self.low_power
+    }
+}
+
 pub struct Governor;
+
+impl Governor {
+    pub fn state() -> ThrottleState {
+        let mut throttle = Self::state_of(&HardwareState::capture());
+        if let Some(load) = system_sampler().and_then(|s| s.average_load(LOAD_WINDOW)) {
+            throttle.high_load = load > HIGH_LOAD_PERCENT;
+        }
+        throttle
+    }
+
+    pub fn state_of(state: &HardwareState) -> ThrottleState {
+        ThrottleState {
+            high_load: state.cpu.usage > HIGH_LOAD_PERCENT,
+            low_power: state.context.is_low_power_mode,
+        }
+    }
+}
@@ -1,5 +1,14 @@
 pub mod governor;
 pub mod protection;
 pub mod scheduler;
+pub mod blocking;
+pub mod control;
+pub mod affinity;
+pub mod numa;
+pub(crate) mod periodic;
 
 pub use scheduler::ArchXSched;
+pub use governor::{Governor, ThrottleState};
+pub use blocking::{ArchXFuture, BlockingPool};
+pub use control::{CancellationToken, ExecControl, Progress};
+pub use numa::{NumaPool, WorkerGroup};
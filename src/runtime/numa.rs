@@ -0,0 +1,273 @@
+use crate::detect::{effective_threads, HardwareCache, NumaTopology};
+use crate::error::{ArchXError, ArchXResult};
+use crate::optimizer::simd::{BinaryOpFn, SimdDispatcher};
+use crate::runtime::affinity::{allowed_cpus, pin_current_thread};
+use crate::runtime::control::ExecControl;
+use crate::runtime::scheduler::ArchXSched;
+use rayon::prelude::*;
+use std::cell::Cell;
+use std::ops::Range;
+use std::sync::OnceLock;
+use std::thread;
+
+/// `f32` elements per 4 KiB page. Node ranges are cut on these boundaries so
+/// that first-touch placement and execution agree page by page.
+const PAGE_ELEMS: usize = 4096 / std::mem::size_of::<f32>();
+
+thread_local! {
+    static CURRENT_NODE: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+/// A rayon pool whose threads are pinned to the CPUs of one NUMA node.
+pub struct WorkerGroup {
+    pub node: usize,
+    pub cpus: Vec<usize>,
+    pool: rayon::ThreadPool,
+}
+
+impl WorkerGroup {
+    pub fn threads(&self) -> usize {
+        self.pool.current_num_threads()
+    }
+}
+
+/// Per-node worker groups with node-affine partitioning.
+///
+/// A slice of `len` elements is split into one contiguous range per node,
+/// proportional to the node's worker count. Buffers allocated with
+/// [`NumaPool::zeroed`] or [`NumaPool::copy_from`] are first touched by the
+/// same group that later processes each range, so every chunk runs on the
+/// node holding its pages.
+pub struct NumaPool {
+    topology: NumaTopology,
+    groups: Vec<WorkerGroup>,
+}
+
+static NUMA_POOL: OnceLock<Option<NumaPool>> = OnceLock::new();
+
+/// Raw output pointer shared by workers writing disjoint ranges.
+#[derive(Clone, Copy)]
+struct SendPtr(*mut f32);
+unsafe impl Send for SendPtr {}
+unsafe impl Sync for SendPtr {}
+
+impl SendPtr {
+    fn get(self) -> *mut f32 {
+        self.0
+    }
+}
+
+impl NumaPool {
+    /// Builds one worker group per node of `topology` that the process may use.
+    ///
+    /// Node CPUs are restricted to the calling thread's allowed cpuset and the
+    /// total number of workers is capped at [`effective_threads`], so a
+    /// cgroup quota or `taskset` mask is respected like on the shared pool.
+    pub fn new(topology: NumaTopology) -> ArchXResult<Self> {
+        Self::with_limits(topology, effective_threads(), allowed_cpus().as_deref())
+    }
+
+    /// Builds the worker groups with explicit limits.
+    ///
+    /// Each node keeps only the CPUs in `allowed` (all of them for `None`);
+    /// nodes left without CPUs are skipped. `max_threads` is split across the
+    /// remaining nodes by CPU count, with at least one worker per node.
+    ///
+    /// Pinning is best effort: CPUs the process may not use (or that do not
+    /// exist, for synthetic topologies) leave the worker unpinned.
+    pub fn with_limits(topology: NumaTopology, max_threads: usize, allowed: Option<&[usize]>) -> ArchXResult<Self> {
+        let nodes: Vec<(usize, Vec<usize>)> = topology.compute_nodes()
+            .map(|node| {
+                let cpus = node.cpus.iter().copied()
+                    .filter(|cpu| allowed.is_none_or(|allowed| allowed.contains(cpu)))
+                    .collect::<Vec<_>>();
+                (node.id, cpus)
+            })
+            .filter(|(_, cpus)| !cpus.is_empty())
+            .collect();
+        let total: usize = nodes.iter().map(|(_, cpus)| cpus.len()).sum();
+
+        let mut groups = Vec::new();
+        for (id, cpus) in nodes {
+            let threads = (max_threads.saturating_mul(cpus.len()) / total).clamp(1, cpus.len());
+            let pin = cpus.clone();
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .thread_name(move |i| format!("archx-numa{}-{}", id, i))
+                .start_handler(move |_| {
+                    CURRENT_NODE.with(|n| n.set(Some(id)));
+                    pin_current_thread(&pin);
+                })
+                .build()
+                .map_err(|e| ArchXError::ExecutionError(format!("Failed to start NUMA node {} workers: {}", id, e)))?;
+            groups.push(WorkerGroup { node: id, cpus, pool });
+        }
+
+        if groups.is_empty() {
+            return Err(ArchXError::InvalidInput("NUMA topology has no node with usable CPUs".to_string()));
+        }
+        Ok(Self { topology, groups })
+    }
+
+    /// The process-wide pool, present only on hosts with more than one NUMA node.
+    pub fn global() -> Option<&'static NumaPool> {
+        NUMA_POOL.get_or_init(|| {
+            let topology = HardwareCache::global().static_facts().numa.clone();
+            if topology.is_numa() { Self::new(topology).ok() } else { None }
+        }).as_ref()
+    }
+
+    /// Node of the calling thread, if it is a NUMA worker.
+    pub fn current_node() -> Option<usize> {
+        CURRENT_NODE.with(|n| n.get())
+    }
+
+    pub fn topology(&self) -> &NumaTopology {
+        &self.topology
+    }
+
+    pub fn groups(&self) -> &[WorkerGroup] {
+        &self.groups
+    }
+
+    /// Splits `0..len` into one page-aligned range per worker group, weighted
+    /// by the group's worker count.
+    pub fn node_ranges(&self, len: usize) -> Vec<Range<usize>> {
+        let total: usize = self.groups.iter().map(|g| g.threads()).sum();
+        let last = self.groups.len() - 1;
+        let mut weight = 0;
+        let mut start = 0;
+
+        self.groups.iter().enumerate().map(|(i, group)| {
+            weight += group.threads();
+            let end = if i == last {
+                len
+            } else {
+                (len * weight / total / PAGE_ELEMS * PAGE_ELEMS).clamp(start, len)
+            };
+            let range = start..end;
+            start = end;
+            range
+        }).collect()
+    }
+
+    /// Runs `f` on the worker group of `node`.
+    pub fn run_on_node<R: Send>(&self, node: usize, f: impl FnOnce() -> R + Send) -> Option<R> {
+        self.groups.iter().find(|g| g.node == node).map(|g| g.pool.install(f))
+    }
+
+    /// Allocates a zeroed buffer whose pages are first touched by their owning node.
+    pub fn zeroed(&self, len: usize) -> Vec<f32> {
+        let mut buf: Vec<f32> = Vec::with_capacity(len);
+        let ptr = SendPtr(buf.as_mut_ptr());
+        let filled = self.for_each_range(len, 1, &ExecControl::none(), |_, r| {
+            // SAFETY: ranges are disjoint and within the allocated capacity.
+            unsafe { std::ptr::write_bytes(ptr.get().add(r.start), 0, r.len()) }
+        });
+        if filled.is_ok() {
+            // SAFETY: every element in 0..len was written above.
+            unsafe { buf.set_len(len) };
+        }
+        buf
+    }
+
+    /// Copies `data` into a buffer placed like [`NumaPool::zeroed`].
+    pub fn copy_from(&self, data: &[f32]) -> Vec<f32> {
+        let mut buf: Vec<f32> = Vec::with_capacity(data.len());
+        let ptr = SendPtr(buf.as_mut_ptr());
+        let filled = self.for_each_range(data.len(), 2, &ExecControl::none(), |_, r| {
+            // SAFETY: ranges are disjoint and within the allocated capacity.
+            unsafe { std::ptr::copy_nonoverlapping(data[r.clone()].as_ptr(), ptr.get().add(r.start), r.len()) }
+        });
+        if filled.is_ok() {
+            // SAFETY: every element in 0..len was written above.
+            unsafe { buf.set_len(data.len()) };
+        }
+        buf
+    }
+
+    /// Calls `f(node, chunk)` for every chunk of `0..len`, on the group owning the chunk.
+    ///
+    /// `streams` is the number of `f32` arrays the caller touches per element and
+    /// sizes the chunks from the cache topology.
+    pub fn for_each_range<F>(&self, len: usize, streams: usize, ctl: &ExecControl, f: F) -> ArchXResult<()>
+    where
+        F: Fn(usize, Range<usize>) + Sync,
+    {
+        self.map_ranges(len, streams, ctl, f).map(|_| ())
+    }
+
+    pub fn add_with(&self, a: &[f32], b: &[f32], out: &mut [f32], ctl: &ExecControl) -> ArchXResult<()> {
+        self.binary_op(a, b, out, SimdDispatcher::global().add, ctl)
+    }
+
+    pub fn sub_with(&self, a: &[f32], b: &[f32], out: &mut [f32], ctl: &ExecControl) -> ArchXResult<()> {
+        self.binary_op(a, b, out, SimdDispatcher::global().sub, ctl)
+    }
+
+    pub fn mul_with(&self, a: &[f32], b: &[f32], out: &mut [f32], ctl: &ExecControl) -> ArchXResult<()> {
+        self.binary_op(a, b, out, SimdDispatcher::global().mul, ctl)
+    }
+
+    pub fn dot_with(&self, a: &[f32], b: &[f32], ctl: &ExecControl) -> ArchXResult<f32> {
+        let dot = SimdDispatcher::global().dot;
+        let len = a.len().min(b.len());
+        let partials = self.map_ranges(len, 2, ctl, |_, r| dot(&a[r.clone()], &b[r]))?;
+        Ok(partials.into_iter().sum())
+    }
+
+    pub fn sum_with(&self, a: &[f32], ctl: &ExecControl) -> ArchXResult<f32> {
+        let sum = SimdDispatcher::global().sum;
+        let partials = self.map_ranges(a.len(), 1, ctl, |_, r| sum(&a[r]))?;
+        Ok(partials.into_iter().sum())
+    }
+
+    fn binary_op(&self, a: &[f32], b: &[f32], out: &mut [f32], op: BinaryOpFn, ctl: &ExecControl) -> ArchXResult<()> {
+        let len = a.len().min(b.len()).min(out.len());
+        let ptr = SendPtr(out.as_mut_ptr());
+        self.for_each_range(len, 3, ctl, |_, r| {
+            // SAFETY: ranges are disjoint and within `out`, which is borrowed mutably for the call.
+            let chunk = unsafe { std::slice::from_raw_parts_mut(ptr.get().add(r.start), r.len()) };
+            op(&a[r.clone()], &b[r], chunk);
+        })
+    }
+
+    fn map_ranges<T, F>(&self, len: usize, streams: usize, ctl: &ExecControl, f: F) -> ArchXResult<Vec<T>>
+    where
+        T: Send,
+        F: Fn(usize, Range<usize>) -> T + Sync,
+    {
+        let statics = HardwareCache::global().static_facts();
+        let plan: Vec<(&WorkerGroup, Range<usize>, usize)> = self.groups.iter()
+            .zip(self.node_ranges(len))
+            .map(|(group, range)| {
+                let chunk = ArchXSched::chunk_size_for(range.len(), group.threads(), streams, &statics.caches);
+                (group, range, ctl.chunk_size(chunk))
+            })
+            .collect();
+        let tracker = ctl.tracker(plan.iter().map(|(_, r, chunk)| r.len().div_ceil(*chunk)).sum());
+
+        thread::scope(|s| {
+            let handles: Vec<_> = plan.into_iter().map(|(group, range, chunk)| {
+                let (f, tracker) = (&f, &tracker);
+                s.spawn(move || group.pool.install(|| {
+                    range.clone().step_by(chunk).collect::<Vec<_>>()
+                        .into_par_iter()
+                        .map(|start| {
+                            tracker.check()?;
+                            let value = f(group.node, start..(start + chunk).min(range.end));
+                            tracker.complete();
+                            Ok(value)
+                        })
+                        .collect::<ArchXResult<Vec<T>>>()
+                }))
+            }).collect();
+
+            let mut results = Vec::new();
+            for handle in handles {
+                results.extend(handle.join().unwrap_or_else(|panic| std::panic::resume_unwind(panic))?);
+            }
+            Ok(results)
+        })
+    }
+}
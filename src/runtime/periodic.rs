@@ -0,0 +1,36 @@
+//! Background threads that run a task at a fixed interval.
+
+use std::sync::mpsc::{channel, RecvTimeoutError, Sender};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// A named thread calling a task every `interval` until stopped.
+///
+/// Shared by the hardware cache and the energy sampler so that starting,
+/// stopping and joining work the same way everywhere.
+pub(crate) struct PeriodicThread {
+    stop: Sender<()>,
+    handle: JoinHandle<()>,
+}
+
+impl PeriodicThread {
+    /// Spawns the thread; the first call to `tick` happens after one `interval`.
+    pub(crate) fn spawn(name: &str, interval: Duration, mut tick: impl FnMut() + Send + 'static) -> Option<Self> {
+        let (stop, rx) = channel::<()>();
+        let handle = thread::Builder::new()
+            .name(name.to_string())
+            .spawn(move || {
+                while let Err(RecvTimeoutError::Timeout) = rx.recv_timeout(interval) {
+                    tick();
+                }
+            })
+            .ok()?;
+        Some(Self { stop, handle })
+    }
+
+    /// Stops the thread, waiting for a tick in progress to finish.
+    pub(crate) fn stop(self) {
+        let _ = self.stop.send(());
+        let _ = self.handle.join();
+    }
+}
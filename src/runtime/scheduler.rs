@@ -1,78 +1,137 @@
 use rayon::prelude::*;
-use crate::optimizer::simd::SimdDispatcher;
+use crate::detect::{CacheTopology, HardwareCache};
+use crate::error::ArchXResult;
+use crate::optimizer::simd::{BinaryOpFn, SimdDispatcher};
+use crate::runtime::control::ExecControl;
+use crate::runtime::numa::NumaPool;
 
 pub struct ArchXSched;
 
 impl ArchXSched {
     /// Parallel addition using work-stealing and SIMD.
     pub fn parallel_add(a: &[f32], b: &[f32], out: &mut [f32]) {
-        let dispatcher = SimdDispatcher::global();
-        let chunk_size = Self::calc_chunk(a.len());
-        
-        out.par_chunks_mut(chunk_size)
-            .enumerate()
-            .for_each(|(i, chunk)| {
-                let start = i * chunk_size;
-                let end = (start + chunk_size).min(a.len());
-                (dispatcher.add)(&a[start..end], &b[start..end], chunk);
-            });
+        let _ = Self::parallel_add_with(a, b, out, &ExecControl::none());
     }
 
     /// Parallel subtraction using work-stealing and SIMD.
     pub fn parallel_sub(a: &[f32], b: &[f32], out: &mut [f32]) {
-        let dispatcher = SimdDispatcher::global();
-        let chunk_size = Self::calc_chunk(a.len());
-        
-        out.par_chunks_mut(chunk_size)
-            .enumerate()
-            .for_each(|(i, chunk)| {
-                let start = i * chunk_size;
-                let end = (start + chunk_size).min(a.len());
-                (dispatcher.sub)(&a[start..end], &b[start..end], chunk);
-            });
+        let _ = Self::parallel_sub_with(a, b, out, &ExecControl::none());
     }
 
     /// Parallel multiplication using work-stealing and SIMD.
     pub fn parallel_mul(a: &[f32], b: &[f32], out: &mut [f32]) {
-        let dispatcher = SimdDispatcher::global();
-        let chunk_size = Self::calc_chunk(a.len());
-        
-        out.par_chunks_mut(chunk_size)
-            .enumerate()
-            .for_each(|(i, chunk)| {
-                let start = i * chunk_size;
-                let end = (start + chunk_size).min(a.len());
-                (dispatcher.mul)(&a[start..end], &b[start..end], chunk);
-            });
+        let _ = Self::parallel_mul_with(a, b, out, &ExecControl::none());
     }
 
     /// Parallel dot product using work-stealing, SIMD, and parallel reduction.
     pub fn parallel_dot(a: &[f32], b: &[f32]) -> f32 {
+        Self::parallel_dot_with(a, b, &ExecControl::none()).unwrap_or(0.0)
+    }
+
+    /// Parallel sum reduction using work-stealing and SIMD.
+    pub fn parallel_sum(a: &[f32]) -> f32 {
+        Self::parallel_sum_with(a, &ExecControl::none()).unwrap_or(0.0)
+    }
+
+    /// Parallel addition that honours cancellation and deadlines at chunk boundaries.
+    ///
+    /// On multi-node hosts every `_with` operation runs on [`NumaPool::global`],
+    /// so each chunk executes on the node that first touched its pages.
+    pub fn parallel_add_with(a: &[f32], b: &[f32], out: &mut [f32], ctl: &ExecControl) -> ArchXResult<()> {
+        if let Some(pool) = NumaPool::global() {
+            return pool.add_with(a, b, out, ctl);
+        }
+        Self::binary_op(a, b, out, SimdDispatcher::global().add, ctl)
+    }
+
+    /// Parallel subtraction that honours cancellation and deadlines at chunk boundaries.
+    pub fn parallel_sub_with(a: &[f32], b: &[f32], out: &mut [f32], ctl: &ExecControl) -> ArchXResult<()> {
+        if let Some(pool) = NumaPool::global() {
+            return pool.sub_with(a, b, out, ctl);
+        }
+        Self::binary_op(a, b, out, SimdDispatcher::global().sub, ctl)
+    }
+
+    /// Parallel multiplication that honours cancellation and deadlines at chunk boundaries.
+    pub fn parallel_mul_with(a: &[f32], b: &[f32], out: &mut [f32], ctl: &ExecControl) -> ArchXResult<()> {
+        if let Some(pool) = NumaPool::global() {
+            return pool.mul_with(a, b, out, ctl);
+        }
+        Self::binary_op(a, b, out, SimdDispatcher::global().mul, ctl)
+    }
+
+    /// Parallel dot product that honours cancellation and deadlines at chunk boundaries.
+    pub fn parallel_dot_with(a: &[f32], b: &[f32], ctl: &ExecControl) -> ArchXResult<f32> {
+        if let Some(pool) = NumaPool::global() {
+            return pool.dot_with(a, b, ctl);
+        }
         let dispatcher = SimdDispatcher::global();
-        let chunk_size = Self::calc_chunk(a.len());
-        
+        let chunk_size = ctl.chunk_size(Self::calc_chunk(a.len(), 2));
+        let tracker = ctl.tracker(a.len().div_ceil(chunk_size));
+
         a.par_chunks(chunk_size)
             .enumerate()
             .map(|(i, chunk)| {
+                tracker.check()?;
                 let start = i * chunk_size;
                 let end = (start + chunk_size).min(b.len());
-                (dispatcher.dot)(chunk, &b[start..end])
+                let partial = (dispatcher.dot)(chunk, &b[start..end]);
+                tracker.complete();
+                Ok(partial)
             })
-            .sum()
+            .try_reduce(|| 0.0, |x, y| Ok(x + y))
     }
 
-    /// Parallel sum reduction using work-stealing and SIMD.
-    pub fn parallel_sum(a: &[f32]) -> f32 {
+    /// Parallel sum that honours cancellation and deadlines at chunk boundaries.
+    pub fn parallel_sum_with(a: &[f32], ctl: &ExecControl) -> ArchXResult<f32> {
+        if let Some(pool) = NumaPool::global() {
+            return pool.sum_with(a, ctl);
+        }
         let dispatcher = SimdDispatcher::global();
-        let chunk_size = Self::calc_chunk(a.len());
-        
+        let chunk_size = ctl.chunk_size(Self::calc_chunk(a.len(), 1));
+        let tracker = ctl.tracker(a.len().div_ceil(chunk_size));
+
         a.par_chunks(chunk_size)
-            .map(|chunk| (dispatcher.sum)(chunk))
-            .sum()
+            .map(|chunk| {
+                tracker.check()?;
+                let partial = (dispatcher.sum)(chunk);
+                tracker.complete();
+                Ok(partial)
+            })
+            .try_reduce(|| 0.0, |x, y| Ok(x + y))
+    }
+
+    fn binary_op(a: &[f32], b: &[f32], out: &mut [f32], op: BinaryOpFn, ctl: &ExecControl) -> ArchXResult<()> {
+        let chunk_size = ctl.chunk_size(Self::calc_chunk(a.len(), 3));
+        let tracker = ctl.tracker(out.len().div_ceil(chunk_size));
+
+        out.par_chunks_mut(chunk_size)
+            .enumerate()
+            .try_for_each(|(i, chunk)| {
+                tracker.check()?;
+                let start = i * chunk_size;
+                let end = (start + chunk_size).min(a.len());
+                op(&a[start..end], &b[start..end], chunk);
+                tracker.complete();
+                Ok(())
+            })
+    }
+
+    /// Chunk size for an operation touching `streams` `f32` arrays of `len` elements.
+    ///
+    /// Each thread gets an even share, but never less than an L1-sized tile
+    /// (too little work per task) nor more than an L2-sized tile, so that large
+    /// inputs still split into enough chunks for work stealing to balance.
+    pub fn chunk_size_for(len: usize, threads: usize, streams: usize, topology: &CacheTopology) -> usize {
+        let elem = std::mem::size_of::<f32>();
+        let min = topology.tile_elements(1, elem, streams);
+        let max = topology.tile_elements(2, elem, streams).max(min);
+        (len / threads.max(1)).clamp(min, max)
     }
 
     #[inline(always)]
-    fn calc_chunk(len: usize) -> usize {
-        (len / rayon::current_num_threads()).max(1024)
+    fn calc_chunk(len: usize, streams: usize) -> usize {
+        let statics = HardwareCache::global().static_facts();
+        Self::chunk_size_for(len, rayon::current_num_threads(), streams, &statics.caches)
     }
 }
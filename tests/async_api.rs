@@ -0,0 +1,49 @@
+use archx::{archx, ArchXError};
+use std::time::{Duration, Instant};
+
+#[tokio::test]
+async fn test_async_math_operations() {
+    let a = vec![6.0f32; 10_000];
+    let b = vec![2.0f32; 10_000];
+
+    let added = archx().add_async(a.clone(), b.clone()).await.unwrap();
+    let subbed = archx().sub_async(a.clone(), b.clone()).await.unwrap();
+    let mulled = archx().mul_async(a.clone(), b.clone()).await.unwrap();
+    assert!(added.iter().all(|&v| v == 8.0));
+    assert!(subbed.iter().all(|&v| v == 4.0));
+    assert!(mulled.iter().all(|&v| v == 12.0));
+
+    let dot = archx().dot_async(a.clone(), b.clone()).await.unwrap();
+    assert_eq!(dot, 12.0 * 10_000.0);
+    let sum = archx().sum_async(b).await.unwrap();
+    assert_eq!(sum, 20_000.0);
+
+    let err = archx().add_async(a, vec![1.0; 3]).await;
+    assert!(matches!(err, Err(ArchXError::InvalidInput(_))));
+}
+
+#[tokio::test]
+async fn test_async_task_and_panic() {
+    let value = archx().run_async(|| (1..=10).sum::<u32>()).await.unwrap();
+    assert_eq!(value, 55);
+
+    let panicked = archx().run_async(|| -> u32 { panic!("boom") }).await;
+    assert!(matches!(panicked, Err(ArchXError::ExecutionError(_))));
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn test_async_does_not_block_executor() {
+    // On a single-threaded runtime, a blocking future would starve the timer task.
+    let start = Instant::now();
+    let slow = archx().run_async(move || {
+        std::thread::sleep(Duration::from_millis(300));
+        start.elapsed()
+    });
+    let fast = async {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        start.elapsed()
+    };
+
+    let (slow_done, fast_done) = tokio::join!(slow, fast);
+    assert!(fast_done < slow_done.unwrap());
+}
@@ -0,0 +1,89 @@
+mod common;
+
+use archx::detect::{CacheKind, CacheTopology, TopologySource};
+use archx::optimizer::scheduler::{Scheduler, WorkloadHints};
+use archx::runtime::ArchXSched;
+use common::FakeSysfs;
+
+/// Four CPUs as two SMT pairs: private L1d/L1i, L2 per pair, one shared L3.
+fn fixture() -> FakeSysfs {
+    let sys = FakeSysfs::new("cache-topology");
+    for cpu in 0..4 {
+        let pair = if cpu < 2 { "0-1" } else { "2-3" };
+        sys.cache(cpu, 0, 1, "Data", "48K", &cpu.to_string())
+            .cache(cpu, 1, 1, "Instruction", "32K", &cpu.to_string())
+            .cache(cpu, 2, 2, "Unified", "2048K", pair)
+            .cache(cpu, 3, 3, "Unified", "30M", "0-3");
+    }
+    sys
+}
+
+#[test]
+fn test_sysfs_topology_is_deduplicated() {
+    let sys = fixture();
+    let topo = CacheTopology::from_sysfs(sys.root()).expect("fixture has caches");
+
+    assert_eq!(topo.source, TopologySource::Sysfs);
+    // 4 L1d + 4 L1i + 2 L2 + 1 L3
+    assert_eq!(topo.caches.len(), 11);
+
+    let l1 = topo.data_cache(1).unwrap();
+    assert_eq!(l1.kind, CacheKind::Data);
+    assert_eq!(l1.size_bytes, 48 * 1024);
+    assert_eq!(l1.line_size, 64);
+    assert_eq!(l1.ways, Some(8));
+
+    let l2 = topo.data_cache(2).unwrap();
+    assert_eq!(l2.shared_cpus, vec![0, 1]);
+    assert_eq!(l2.per_cpu_bytes(), 1024 * 1024);
+
+    let l3 = topo.last_level().unwrap();
+    assert_eq!(l3.level, 3);
+    assert_eq!(l3.size_bytes, 30 * 1024 * 1024);
+    assert_eq!(l3.shared_cpus, vec![0, 1, 2, 3]);
+}
+
+#[test]
+fn test_missing_tree_returns_none() {
+    let sys = FakeSysfs::new("cache-empty");
+    assert!(CacheTopology::from_sysfs(sys.root()).is_none());
+    assert_eq!(CacheTopology::default().source, TopologySource::Default);
+}
+
+#[test]
+fn test_tile_sizes_follow_cache_sizes() {
+    let sys = fixture();
+    let topo = CacheTopology::from_sysfs(sys.root()).unwrap();
+
+    // 48 KiB / (3 * 4 bytes) = 4096 elements
+    assert_eq!(topo.tile_elements(1, 4, 3), 4096);
+    // 1 MiB per-CPU L2 share / (2 * 4 bytes)
+    assert_eq!(topo.tile_elements(2, 4, 2), 131072);
+    // Missing levels fall back to the closest lower one.
+    assert_eq!(topo.tile_elements(4, 4, 1), topo.tile_elements(3, 4, 1));
+}
+
+#[test]
+fn test_schedulers_derive_chunks_from_topology() {
+    let sys = fixture();
+    let big = CacheTopology::from_sysfs(sys.root()).unwrap();
+    let small = CacheTopology::default();
+
+    // Floors come from the L1 tile, caps from the L2 tile.
+    assert_eq!(ArchXSched::chunk_size_for(1000, 8, 3, &big), 4096);
+    assert_eq!(ArchXSched::chunk_size_for(100_000_000, 8, 3, &big), big.tile_elements(2, 4, 3));
+    assert!(ArchXSched::chunk_size_for(100_000_000, 8, 3, &small) < ArchXSched::chunk_size_for(100_000_000, 8, 3, &big));
+
+    let hints = WorkloadHints::default();
+    assert_eq!(Scheduler::calculate_chunk_size_with(1000, 8, &hints, &big), 1000);
+    assert_eq!(Scheduler::calculate_chunk_size_with(100_000, 8, &hints, &big), 12512);
+    assert_eq!(Scheduler::calculate_chunk_size_with(10_000, 8, &hints, &big), 4096);
+}
+
+#[test]
+fn test_detected_topology_is_usable() {
+    let topo = CacheTopology::detect();
+    assert!(topo.line_size() >= 16);
+    assert!(topo.data_cache(1).is_some());
+    assert!(topo.tile_elements(1, 4, 3) > 0);
+}
@@ -0,0 +1,85 @@
+use archx::runtime::CancellationToken;
+use archx::{archx, ArchXError};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+#[test]
+fn test_progress_reports_every_chunk() {
+    let size = 5_000_000;
+    let a = vec![1.0f32; size];
+    let b = vec![2.0f32; size];
+    let mut out = vec![0.0f32; size];
+
+    let reports = Arc::new(AtomicUsize::new(0));
+    let last_total = Arc::new(AtomicUsize::new(0));
+    let (r, t) = (reports.clone(), last_total.clone());
+
+    archx()
+        .on_progress(move |p| {
+            r.fetch_add(1, Ordering::SeqCst);
+            t.store(p.total_chunks, Ordering::SeqCst);
+            assert!(p.completed_chunks <= p.total_chunks);
+        })
+        .add(&a, &b, &mut out)
+        .unwrap();
+
+    assert!(out.iter().all(|&v| v == 3.0));
+    assert!(last_total.load(Ordering::SeqCst) > 1);
+    assert_eq!(reports.load(Ordering::SeqCst), last_total.load(Ordering::SeqCst));
+}
+
+#[test]
+fn test_cancel_stops_operation() {
+    let size = 5_000_000;
+    let a = vec![1.0f32; size];
+    let b = vec![2.0f32; size];
+
+    let token = CancellationToken::new();
+    token.cancel();
+    let res = archx().with_cancellation(token.clone()).dot(&a, &b);
+    assert_eq!(res, Err(ArchXError::Cancelled));
+
+    // Cancel from the progress callback after the first chunk
+    let token = CancellationToken::new();
+    let handle = token.clone();
+    let mut out = vec![0.0f32; size];
+    let res = archx()
+        .with_cancellation(token)
+        .on_progress(move |_| handle.cancel())
+        .mul(&a, &b, &mut out);
+    assert_eq!(res, Err(ArchXError::Cancelled));
+}
+
+#[test]
+fn test_deadline_exceeded() {
+    let a = vec![1.0f32; 1_000_000];
+    let res = archx()
+        .with_deadline(Instant::now() - Duration::from_millis(1))
+        .sum(&a);
+    assert_eq!(res, Err(ArchXError::DeadlineExceeded));
+
+    let res = archx().with_timeout(Duration::from_secs(60)).sum(&a);
+    assert_eq!(res, Ok(1_000_000.0));
+}
+
+#[test]
+fn test_parallel_impl_honours_control() {
+    use archx::optimizer::parallel::add_parallel_impl_with;
+    use archx::runtime::ExecControl;
+
+    let size = 4_000_000;
+    let a = vec![1.0f32; size];
+    let b = vec![1.0f32; size];
+    let mut out = vec![0.0f32; size];
+
+    let ctl = ExecControl::none().with_token(CancellationToken::new());
+    add_parallel_impl_with(&a, &b, &mut out, &archx::WorkloadHints::default(), &ctl).unwrap();
+    assert!(out.iter().all(|&v| v == 2.0));
+
+    let token = CancellationToken::new();
+    token.cancel();
+    let ctl = ExecControl::none().with_token(token);
+    let res = add_parallel_impl_with(&a, &b, &mut out, &archx::WorkloadHints::default(), &ctl);
+    assert_eq!(res, Err(ArchXError::Cancelled));
+}
@@ -0,0 +1,135 @@
+mod common;
+
+use archx::core::{DefaultResourceManager, MemoryReservation, ResourceManager};
+use archx::detect::memory::MemoryDetails;
+use archx::detect::{CgroupLimits, CgroupVersion};
+use common::FakeSysfs;
+
+#[test]
+fn test_cgroup_v2_limits_follow_tightest_ancestor() {
+    let sys = FakeSysfs::new("cgroup-v2");
+    sys.write("proc/self/cgroup", "0::/kubepods/pod1/app")
+        .write("sys/fs/cgroup/cgroup.controllers", "cpu memory")
+        .write("sys/fs/cgroup/kubepods/cpu.max", "max 100000")
+        .write("sys/fs/cgroup/kubepods/memory.max", "8589934592")
+        .write("sys/fs/cgroup/kubepods/pod1/cpu.max", "400000 100000")
+        .write("sys/fs/cgroup/kubepods/pod1/memory.max", "max")
+        .write("sys/fs/cgroup/kubepods/pod1/app/cpu.max", "max 100000")
+        .write("sys/fs/cgroup/kubepods/pod1/app/memory.max", "max")
+        .write("sys/fs/cgroup/kubepods/pod1/app/memory.current", "1073741824");
+
+    let limits = CgroupLimits::from_root(sys.root());
+    assert_eq!(limits.version, Some(CgroupVersion::V2));
+    assert_eq!(limits.cpu_quota, Some(4.0));
+    assert_eq!(limits.memory_limit_bytes, Some(8 << 30));
+    assert_eq!(limits.memory_usage_bytes(), Some(1 << 30));
+    assert_eq!(limits.effective_cpus(64), 4);
+    assert_eq!(limits.effective_cpus(2), 2);
+}
+
+#[test]
+fn test_cgroup_v2_namespace_uses_mount_root() {
+    let sys = FakeSysfs::new("cgroup-v2-ns");
+    sys.write("proc/self/cgroup", "0::/")
+        .write("sys/fs/cgroup/cgroup.controllers", "cpu memory")
+        .write("sys/fs/cgroup/cpu.max", "150000 100000")
+        .write("sys/fs/cgroup/memory.max", "max");
+
+    let limits = CgroupLimits::from_root(sys.root());
+    assert_eq!(limits.cpu_quota, Some(1.5));
+    assert_eq!(limits.memory_limit_bytes, None);
+    assert_eq!(limits.effective_cpus(64), 2);
+}
+
+#[test]
+fn test_cgroup_v1_limits() {
+    let sys = FakeSysfs::new("cgroup-v1");
+    sys.write("proc/self/cgroup", "12:memory:/docker/abc\n4:cpu,cpuacct:/docker/abc\n1:name=systemd:/docker/abc")
+        .write("sys/fs/cgroup/cpu,cpuacct/docker/abc/cpu.cfs_quota_us", "200000")
+        .write("sys/fs/cgroup/cpu,cpuacct/docker/abc/cpu.cfs_period_us", "100000")
+        .write("sys/fs/cgroup/memory/memory.limit_in_bytes", "9223372036854771712")
+        .write("sys/fs/cgroup/memory/docker/abc/memory.limit_in_bytes", "536870912")
+        .write("sys/fs/cgroup/memory/docker/abc/memory.usage_in_bytes", "268435456");
+
+    let limits = CgroupLimits::from_root(sys.root());
+    assert_eq!(limits.version, Some(CgroupVersion::V1));
+    assert_eq!(limits.cpu_quota, Some(2.0));
+    assert_eq!(limits.memory_limit_bytes, Some(512 << 20));
+    assert_eq!(limits.memory_usage_bytes(), Some(256 << 20));
+}
+
+#[test]
+fn test_unlimited_and_missing_cgroups() {
+    let sys = FakeSysfs::new("cgroup-v1-unlimited");
+    sys.write("proc/self/cgroup", "3:cpu:/\n2:memory:/")
+        .write("sys/fs/cgroup/cpu/cpu.cfs_quota_us", "-1")
+        .write("sys/fs/cgroup/cpu/cpu.cfs_period_us", "100000")
+        .write("sys/fs/cgroup/memory/memory.limit_in_bytes", "9223372036854771712");
+
+    let limits = CgroupLimits::from_root(sys.root());
+    assert_eq!(limits.cpu_quota, None);
+    assert_eq!(limits.memory_limit_bytes, None);
+    assert_eq!(limits.effective_cpus(16), 16);
+
+    let none = CgroupLimits::from_root(FakeSysfs::new("cgroup-none").root());
+    assert_eq!(none.version, None);
+}
+
+#[test]
+fn test_memory_details_are_capped_by_limit() {
+    let sys = FakeSysfs::new("cgroup-mem");
+    sys.write("proc/self/cgroup", "0::/")
+        .write("sys/fs/cgroup/cgroup.controllers", "memory")
+        .write("sys/fs/cgroup/memory.max", "8589934592")
+        .write("sys/fs/cgroup/memory.current", "2147483648");
+    let limits = CgroupLimits::from_root(sys.root());
+
+    // A 512 GiB host with 400 GiB free.
+    let mut memory = MemoryDetails {
+        total_kb: 512 << 20,
+        available_kb: 400 << 20,
+        used_kb: 112 << 20,
+        swap_total_kb: 0,
+        swap_free_kb: 0,
+        pressure_estimate: 0.2,
+    };
+    memory.apply_limits(&limits);
+
+    assert_eq!(memory.total_kb, 8 << 20);
+    assert_eq!(memory.used_kb, 2 << 20);
+    assert_eq!(memory.available_kb, 6 << 20);
+    assert!((memory.pressure_estimate - 0.25).abs() < 1e-6);
+}
+
+#[test]
+fn test_resource_manager_admission() {
+    let manager = DefaultResourceManager::with_limits(4, Some(1000));
+
+    assert!(manager.reserve_threads(3));
+    assert!(!manager.reserve_threads(2));
+    manager.release_threads(3);
+    assert!(manager.reserve_threads(4));
+    manager.release_threads(4);
+
+    let first = MemoryReservation::new(&manager, 600).expect("fits the budget");
+    assert!(MemoryReservation::new(&manager, 600).is_none());
+    assert_eq!(manager.get_current_usage().reserved_memory_bytes, 600);
+    drop(first);
+    assert_eq!(manager.get_current_usage().reserved_memory_bytes, 0);
+    assert!(manager.reserve_memory(1000));
+}
+
+#[test]
+fn test_detected_limits_are_consistent() {
+    let info = archx::get_system_info();
+    assert!(info.cpu.effective_threads >= 1);
+    assert!(info.cpu.effective_threads <= info.cpu.logical_processors.max(1));
+
+    // Memory is reported in KiB, so the GB figure must be below the physical total.
+    let physical_gb = sysinfo::System::new_all().total_memory() as f64 / (1u64 << 30) as f64;
+    assert!(info.available_memory_gb > 0.0);
+    assert!(info.available_memory_gb <= physical_gb + 0.01);
+
+    let manager = DefaultResourceManager::new();
+    assert!(manager.max_threads() <= archx::detect::effective_threads());
+}
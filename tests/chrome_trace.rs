@@ -0,0 +1,112 @@
+mod common;
+
+use archx::profiler::{ChromeTraceExporter, CounterSample, Metric, ReportExporter};
+use common::TempDir;
+use serde_json::Value;
+use std::time::Duration;
+
+fn span(name: &str, device: &str, id: u64, parent: Option<u64>, thread: usize, start_ms: u64, ms: u64) -> Metric {
+    Metric {
+        start: Duration::from_millis(start_ms),
+        thread_id: Some(thread),
+        id,
+        parent_id: parent,
+        ..Metric::new(name, if device == "CPU" { "SIMD" } else { "Vulkan" }, device, Duration::from_millis(ms))
+    }
+}
+
+fn sample_run() -> Vec<Metric> {
+    vec![
+        span("Parallel Add", "CPU", 1, None, 100, 0, 10),
+        span("Parallel Chunk", "CPU", 2, Some(1), 101, 1, 8),
+        span("Parallel Chunk", "CPU", 3, Some(1), 102, 1, 4),
+        span("Dispatch", "CPU", 4, Some(1), 100, 2, 1),
+        span("GPU Add", "dGPU", 5, Some(1), 100, 2, 6),
+    ]
+}
+
+fn events<'a>(trace: &'a Value, ph: &str) -> Vec<&'a Value> {
+    trace["traceEvents"].as_array().unwrap().iter().filter(|e| e["ph"] == ph).collect()
+}
+
+#[test]
+fn test_spans_are_placed_on_thread_and_device_tracks() {
+    let trace = ChromeTraceExporter::new().trace(&sample_run());
+
+    let complete = events(&trace, "X");
+    assert_eq!(complete.len(), 5);
+    let add = complete.iter().find(|e| e["name"] == "Parallel Add").unwrap();
+    assert_eq!(add["ts"], 0.0);
+    assert_eq!(add["dur"], 10_000.0);
+    assert_eq!(add["pid"], 1);
+    assert_eq!(add["tid"], 100);
+
+    let chunk_tids: Vec<&Value> = complete.iter().filter(|e| e["name"] == "Parallel Chunk").map(|e| &e["tid"]).collect();
+    assert_eq!(chunk_tids, vec![101, 102]);
+
+    let gpu = complete.iter().find(|e| e["name"] == "GPU Add").unwrap();
+    assert_eq!(gpu["pid"], 2);
+    assert_eq!(gpu["args"]["parent_id"], 1);
+
+    let names: Vec<&Value> = events(&trace, "M").into_iter().map(|e| &e["args"]["name"]).collect();
+    for expected in ["CPU", "GPU", "Thread 100", "Thread 101", "Thread 102", "dGPU (Vulkan)"] {
+        assert!(names.iter().any(|n| *n == expected), "missing track name {}", expected);
+    }
+}
+
+#[test]
+fn test_metadata_does_not_override_builtin_args() {
+    let mut metric = span("Tagged", "CPU", 7, Some(1), 100, 0, 1);
+    for (key, value) in [("id", "bogus"), ("device", "bogus"), ("energy_source", "bogus"), ("stage", "warmup")] {
+        metric.metadata.insert(key.to_string(), value.to_string());
+    }
+    let trace = ChromeTraceExporter::new().trace(&[metric]);
+
+    let args = &events(&trace, "X")[0]["args"];
+    assert_eq!(args["id"], 7);
+    assert_eq!(args["device"], "CPU");
+    assert_ne!(args["energy_source"], "bogus");
+    assert_eq!(args["stage"], "warmup");
+}
+
+#[test]
+fn test_cross_track_children_get_flow_arrows() {
+    let trace = ChromeTraceExporter::new().trace(&sample_run());
+
+    let starts = events(&trace, "s");
+    let finishes = events(&trace, "f");
+    // Both chunks and the GPU span ran on other tracks than their parent; "Dispatch" did not.
+    let mut ids: Vec<u64> = starts.iter().map(|e| e["id"].as_u64().unwrap()).collect();
+    ids.sort();
+    assert_eq!(ids, vec![2, 3, 5]);
+    assert_eq!(finishes.len(), 3);
+    assert!(starts.iter().all(|e| e["tid"] == 100 && e["pid"] == 1));
+}
+
+#[test]
+fn test_counters_become_counter_tracks() {
+    let counters = vec![
+        CounterSample { name: "CPU Load (%)".into(), at: Duration::from_millis(5), value: 42.0 },
+        CounterSample { name: "Memory Used (MiB)".into(), at: Duration::from_millis(5), value: 512.0 },
+    ];
+    let trace = ChromeTraceExporter::new().with_counters(counters).trace(&[]);
+
+    let counter_events = events(&trace, "C");
+    assert_eq!(counter_events.len(), 2);
+    assert_eq!(counter_events[0]["ts"], 5_000.0);
+    assert_eq!(counter_events[0]["args"]["value"], 42.0);
+}
+
+#[test]
+fn test_export_writes_a_loadable_trace() {
+    let dir = TempDir::new("chrome-trace");
+    let path = dir.join("trace.json");
+    let path = path.to_str().unwrap();
+
+    ChromeTraceExporter::new().export(&sample_run(), path).unwrap();
+    let trace: Value = serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+    assert_eq!(events(&trace, "X").len(), 5);
+
+    let err = ChromeTraceExporter::new().export(&vec!["not", "metrics"], path).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+}
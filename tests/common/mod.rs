@@ -0,0 +1,78 @@
+//! Shared helpers for integration tests.
+#![allow(dead_code)]
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// A throwaway directory for files written by a test.
+///
+/// Removed again when dropped.
+pub struct TempDir {
+    path: PathBuf,
+}
+
+impl TempDir {
+    pub fn new(name: &str) -> Self {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+        let path = std::env::temp_dir().join(format!(
+            "archx-{}-{}-{}",
+            name,
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        let _ = std::fs::remove_dir_all(&path);
+        std::fs::create_dir_all(&path).unwrap();
+        Self { path }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Path of `file` inside the directory.
+    pub fn join(&self, file: &str) -> PathBuf {
+        self.path.join(file)
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.path);
+    }
+}
+
+/// A throwaway directory tree mimicking `/sys` (or any other root).
+///
+/// Removed again when dropped.
+pub struct FakeSysfs {
+    dir: TempDir,
+}
+
+impl FakeSysfs {
+    pub fn new(name: &str) -> Self {
+        Self { dir: TempDir::new(name) }
+    }
+
+    pub fn root(&self) -> &Path {
+        self.dir.path()
+    }
+
+    /// Writes `contents` to `relative`, creating parent directories.
+    pub fn write(&self, relative: &str, contents: &str) -> &Self {
+        let path = self.root().join(relative);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, format!("{}\n", contents)).unwrap();
+        self
+    }
+
+    /// Writes one `cpu<cpu>/cache/index<index>` entry.
+    pub fn cache(&self, cpu: usize, index: usize, level: u8, kind: &str, size: &str, shared: &str) -> &Self {
+        let dir = format!("devices/system/cpu/cpu{}/cache/index{}", cpu, index);
+        self.write(&format!("{}/level", dir), &level.to_string())
+            .write(&format!("{}/type", dir), kind)
+            .write(&format!("{}/size", dir), size)
+            .write(&format!("{}/coherency_line_size", dir), "64")
+            .write(&format!("{}/ways_of_associativity", dir), "8")
+            .write(&format!("{}/shared_cpu_list", dir), shared)
+    }
+}
@@ -0,0 +1,129 @@
+mod common;
+
+use archx::detect::{CacheTopology, CoreTopology, CoreType};
+use archx::optimizer::parallel::add_parallel_impl;
+use archx::optimizer::scheduler::{Scheduler, WorkloadHints};
+use archx::Policy;
+use common::FakeSysfs;
+
+/// Four P-core threads (capacity 1024) followed by four E-cores (capacity 512).
+fn big_little() -> FakeSysfs {
+    let sys = FakeSysfs::new("cores");
+    for cpu in 0..8 {
+        let dir = format!("devices/system/cpu/cpu{}", cpu);
+        sys.write(&format!("{}/cpu_capacity", dir), if cpu < 4 { "1024" } else { "512" })
+            .write(&format!("{}/topology/core_id", dir), &(cpu / 2).to_string())
+            .write(&format!("{}/topology/physical_package_id", dir), "0");
+    }
+    sys
+}
+
+#[test]
+fn test_capacity_classification() {
+    let sys = big_little();
+    let cores = CoreTopology::from_sysfs(sys.root()).unwrap();
+
+    assert!(cores.is_hybrid());
+    assert_eq!(cores.performance_cpus(), vec![0, 1, 2, 3]);
+    assert_eq!(cores.efficiency_cpus(), vec![4, 5, 6, 7]);
+    assert_eq!(cores.capacity_of(6), 512);
+    assert_eq!(cores.cores[3].core_id, Some(1));
+    assert_eq!(cores.cores[5].core_type, CoreType::Efficiency);
+}
+
+#[test]
+fn test_intel_hybrid_pmu_lists() {
+    let sys = FakeSysfs::new("cores-intel");
+    for cpu in 0..6 {
+        let freq = if cpu < 2 { "5000000" } else { "3800000" };
+        sys.write(&format!("devices/system/cpu/cpu{}/cpufreq/cpuinfo_max_freq", cpu), freq);
+    }
+    sys.write("devices/cpu_core/cpus", "0-1").write("devices/cpu_atom/cpus", "2-5");
+
+    let cores = CoreTopology::from_sysfs(sys.root()).unwrap();
+    assert_eq!(cores.performance_cpus(), vec![0, 1]);
+    assert_eq!(cores.efficiency_cpus(), vec![2, 3, 4, 5]);
+    // Capacities are scaled from the maximum frequency.
+    assert_eq!(cores.capacity_of(0), 1024);
+    assert_eq!(cores.capacity_of(3), 778);
+}
+
+#[test]
+fn test_boost_differences_are_not_hybrid() {
+    // Two preferred cores boost to 5.7 GHz, the rest to 5.5 GHz.
+    let sys = FakeSysfs::new("cores-boost");
+    for cpu in 0..8 {
+        let freq = if cpu < 2 { "5700000" } else { "5500000" };
+        sys.write(&format!("devices/system/cpu/cpu{}/cpufreq/cpuinfo_max_freq", cpu), freq);
+    }
+
+    let cores = CoreTopology::from_sysfs(sys.root()).unwrap();
+    assert!(!cores.is_hybrid());
+    assert_eq!(cores.performance_cpus(), (0..8).collect::<Vec<_>>());
+    // Capacities still rank the preferred cores first.
+    assert_eq!(cores.capacity_of(0), 1024);
+    assert_eq!(cores.capacity_of(5), 988);
+    assert_eq!(cores.by_capacity()[..2], [0, 1]);
+}
+
+#[test]
+fn test_homogeneous_fallbacks() {
+    let sys = FakeSysfs::new("cores-plain");
+    sys.write("devices/system/cpu/cpu0/topology/core_id", "0")
+        .write("devices/system/cpu/cpu1/topology/core_id", "1");
+    let cores = CoreTopology::from_sysfs(sys.root()).unwrap();
+    assert!(!cores.is_hybrid());
+    assert_eq!(cores.performance_cpus(), vec![0, 1]);
+
+    assert!(CoreTopology::from_sysfs(FakeSysfs::new("cores-none").root()).is_none());
+    assert_eq!(CoreTopology::homogeneous(3).by_capacity(), vec![0, 1, 2]);
+}
+
+#[test]
+fn test_capacity_weighted_plan() {
+    let sys = big_little();
+    let cores = CoreTopology::from_sysfs(sys.root()).unwrap();
+    let caches = CacheTopology::default();
+    let len = 1 << 20;
+
+    // Unpinned: equal split, no CPU assignment.
+    let equal = Scheduler::plan_chunks(len, 8, &WorkloadHints::default(), &caches, &cores);
+    assert_eq!(equal.len(), 8);
+    assert!(equal.iter().all(|p| p.cpu.is_none() && p.len == len / 8));
+
+    // Pinned: P-cores first, with twice the work of an E-core.
+    let hints = WorkloadHints { pin_threads: true, ..Default::default() };
+    let weighted = Scheduler::plan_chunks(len, 8, &hints, &caches, &cores);
+    assert_eq!(weighted.iter().map(|p| p.cpu.unwrap()).collect::<Vec<_>>(), (0..8).collect::<Vec<_>>());
+    let ratio = weighted[0].len as f64 / weighted[6].len as f64;
+    assert!((ratio - 2.0).abs() < 0.01, "ratio {}", ratio);
+    assert_eq!(weighted.iter().map(|p| p.len).sum::<usize>(), len);
+    assert!(weighted.windows(2).all(|w| w[0].offset + w[0].len == w[1].offset));
+}
+
+#[test]
+fn test_performance_policy_prefers_p_cores() {
+    let sys = big_little();
+    let cores = CoreTopology::from_sysfs(sys.root()).unwrap();
+    let hints = WorkloadHints { policy: Policy::Performance, ..Default::default() };
+
+    let plan = Scheduler::plan_chunks(1 << 20, 8, &hints, &CacheTopology::default(), &cores);
+    assert_eq!(plan.len(), 4);
+    assert!(plan.iter().all(|p| p.cpu.unwrap() < 4));
+
+    // Non-hybrid machines are unaffected by the policy.
+    let flat = Scheduler::plan_chunks(1 << 20, 8, &hints, &CacheTopology::default(), &CoreTopology::homogeneous(8));
+    assert!(flat.iter().all(|p| p.cpu.is_none()));
+}
+
+#[test]
+fn test_pinned_parallel_add() {
+    let len = 200_003;
+    let a = vec![1.0; len];
+    let b = vec![2.0; len];
+    let mut out = vec![0.0; len];
+    let hints = WorkloadHints { pin_threads: true, thread_count: Some(4), ..Default::default() };
+
+    add_parallel_impl(&a, &b, &mut out, &hints);
+    assert!(out.iter().all(|&x| x == 3.0));
+}
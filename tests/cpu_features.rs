@@ -0,0 +1,84 @@
+use archx::cpu::features::CpuFeatures;
+use archx::optimizer::simd::{avx2, SimdDispatcher};
+
+const X86_CPUINFO: &str = "\
+processor\t: 0
+vendor_id\t: GenuineIntel
+model name\t: Intel(R) Xeon(R) Platinum 8480+
+flags\t\t: fpu sse2 popcnt avx avx2 fma f16c bmi1 bmi2 avx512f avx512dq avx512bw avx512vl avx_vnni avx512_bf16 avx512_vnni avx512_fp16 amx_bf16 amx_tile amx_int8
+";
+
+const ARM_CPUINFO: &str = "\
+processor\t: 0
+BogoMIPS\t: 243.75
+Features\t: fp asimd evtstrm aes pmull sha1 sha2 crc32 atomics fphp asimdhp cpuid asimdrdm asimddp sve sve2 i8mm bf16
+CPU implementer\t: 0x41
+";
+
+#[test]
+fn test_parse_x86_cpuinfo() {
+    let f = CpuFeatures::from_cpuinfo(X86_CPUINFO);
+
+    assert!(f.sse2 && f.avx && f.avx2 && f.fma && f.f16c);
+    assert!(f.bmi1 && f.bmi2 && f.popcnt);
+    assert!(f.has_avx512_common());
+    assert!(f.avx512vnni && f.avx512bf16 && f.avx512fp16 && f.avxvnni);
+    assert!(f.amx_tile && f.amx_int8 && f.amx_bf16);
+    assert!(!f.neon && !f.sve && !f.dotprod);
+}
+
+#[test]
+fn test_parse_arm_cpuinfo() {
+    let f = CpuFeatures::from_cpuinfo(ARM_CPUINFO);
+
+    assert!(f.neon && f.sve && f.sve2 && f.dotprod && f.fp16 && f.i8mm);
+    assert!(!f.avx2 && !f.fma);
+    assert_eq!(f.enabled(), vec!["neon", "sve", "sve2", "dotprod", "fp16", "i8mm"]);
+}
+
+#[test]
+fn test_features_roundtrip_json() {
+    let detected = CpuFeatures::detect();
+    let json = serde_json::to_string(&detected).unwrap();
+    let parsed: CpuFeatures = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed, detected);
+
+    // Snapshots from older versions only carry the original five flags.
+    let legacy: CpuFeatures = serde_json::from_str(r#"{"sse2":true,"avx":true,"avx2":true,"avx512f":false,"neon":false}"#).unwrap();
+    assert!(legacy.avx2 && !legacy.fma);
+}
+
+#[test]
+fn test_detect_matches_std() {
+    let f = CpuFeatures::detect();
+
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        assert_eq!(f.fma, std::is_x86_feature_detected!("fma"));
+        assert_eq!(f.bmi2, std::is_x86_feature_detected!("bmi2"));
+        assert_eq!(f.avx512vl, std::is_x86_feature_detected!("avx512vl"));
+        // AMX sub-features imply the tile architecture.
+        assert!(f.amx_tile || (!f.amx_int8 && !f.amx_bf16));
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    assert!(f.neon);
+
+    println!("Detected features: {:?}", f.enabled());
+}
+
+#[test]
+fn test_fma_dot_kernel_matches() {
+    let features = CpuFeatures::detect();
+    let a: Vec<f32> = (0..1027).map(|i| (i % 13) as f32 * 0.5).collect();
+    let b: Vec<f32> = (0..1027).map(|i| (i % 7) as f32 - 3.0).collect();
+    let expected: f32 = a.iter().zip(&b).map(|(x, y)| x * y).sum();
+
+    if features.has_fma_avx2() {
+        let fma = avx2::dot_avx2_fma_impl(&a, &b);
+        assert!((fma - expected).abs() < 1e-2, "fma={} expected={}", fma, expected);
+    }
+
+    let dispatched = (SimdDispatcher::global().dot)(&a, &b);
+    assert!((dispatched - expected).abs() < 1e-2);
+}
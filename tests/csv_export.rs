@@ -0,0 +1,100 @@
+mod common;
+
+use archx::profiler::{CsvExporter, CsvWriter, Metric, ReportExporter};
+use common::TempDir;
+use serde::Serialize;
+use std::time::Duration;
+
+fn metric(name: &str, ms: u64, metadata: &[(&str, &str)]) -> Metric {
+    Metric {
+        start: Duration::from_micros(1500),
+        thread_id: Some(7),
+        id: 3,
+        metadata: metadata.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect(),
+        ..Metric::new(name, "SIMD", "CPU", Duration::from_millis(ms))
+    }
+}
+
+#[test]
+fn test_metrics_are_flattened_with_units() {
+    let csv = CsvExporter::render(&vec![metric("Parallel Add", 2, &[])]).unwrap();
+    let lines: Vec<&str> = csv.split("\r\n").collect();
+
+    assert_eq!(lines[0], "name,backend,device,start_ms,duration_ms,energy_estimate,energy_source,thread_id,id,parent_id");
+    assert_eq!(lines[1], "Parallel Add,SIMD,CPU,1.5,2,0.0,Estimate,7,3,");
+    assert_eq!(lines[2], "", "every record ends with CRLF");
+}
+
+#[test]
+fn test_fields_are_quoted_per_rfc_4180() {
+    let csv = CsvExporter::render(&vec![metric("say \"hi\", then\nleave", 1, &[])]).unwrap();
+    let row = csv.split("\r\n").nth(1).unwrap();
+    assert!(row.starts_with("\"say \"\"hi\"\", then\nleave\",SIMD,"), "{}", row);
+}
+
+#[test]
+fn test_metadata_keys_become_columns() {
+    let records = vec![
+        metric("A", 1, &[("elements", "1024")]),
+        metric("B", 1, &[("error", "lost, device")]),
+    ];
+    let csv = CsvExporter::render(&records).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+
+    assert!(lines[0].ends_with(",metadata.elements,metadata.error"));
+    assert!(lines[1].ends_with(",1024,"));
+    assert!(lines[2].ends_with(",,\"lost, device\""));
+}
+
+#[test]
+fn test_metadata_columns_are_sorted() {
+    let keys = [("zeta", "1"), ("alpha", "2"), ("mid", "3"), ("beta", "4"), ("omega", "5")];
+    let mut reversed = keys;
+    reversed.reverse();
+    let records = vec![metric("A", 1, &keys), metric("B", 1, &reversed)];
+    let csv = CsvExporter::render(&records).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+
+    // HashMap iteration order differs between processes; the header must not.
+    assert!(lines[0].ends_with(",metadata.alpha,metadata.beta,metadata.mid,metadata.omega,metadata.zeta"), "{}", lines[0]);
+    assert!(lines[1].ends_with(",2,4,3,5,1") && lines[2].ends_with(",2,4,3,5,1"));
+}
+
+#[derive(Serialize)]
+struct Sample {
+    service: &'static str,
+    latency: Duration,
+    tags: Vec<&'static str>,
+    load: Option<f32>,
+}
+
+#[test]
+fn test_streaming_append_reuses_the_header() {
+    let dir = TempDir::new("csv-export");
+    let path = dir.join("samples.csv");
+
+    {
+        let mut writer = CsvWriter::create(&path).unwrap();
+        writer.write_record(&Sample { service: "api", latency: Duration::from_millis(12), tags: vec!["a", "b"], load: Some(0.5) }).unwrap();
+        writer.flush().unwrap();
+    }
+    {
+        let mut writer = CsvWriter::append(&path).unwrap();
+        assert_eq!(writer.columns(), ["service", "latency_ms", "tags", "load"]);
+        writer.write_record(&Sample { service: "db", latency: Duration::from_micros(250), tags: vec![], load: None }).unwrap();
+        writer.flush().unwrap();
+    }
+
+    let contents = std::fs::read_to_string(&path).unwrap();
+    assert_eq!(contents, "service,latency_ms,tags,load\r\napi,12,\"[\"\"a\"\",\"\"b\"\"]\",0.5\r\ndb,0.25,[],\r\n");
+}
+
+#[test]
+fn test_export_writes_the_data() {
+    let dir = TempDir::new("csv-export");
+    let path = dir.join("report.csv");
+    CsvExporter.export(&vec![metric("A", 1, &[]), metric("B", 2, &[])], path.to_str().unwrap()).unwrap();
+
+    let contents = std::fs::read_to_string(&path).unwrap();
+    assert_eq!(contents.lines().count(), 3);
+}
@@ -21,6 +21,7 @@ fn test_stress_500m_extreme() {
 }
 
 #[tokio::test]
+#[allow(deprecated)]
 async fn test_async_parity() {
     let size = 100_000;
     let a = vec![10.0; size];
@@ -27,6 +27,7 @@ fn test_universal_consistency() {
 }
 
 #[tokio::test]
+#[allow(deprecated)]
 async fn test_async_consistency() {
     let size = 50_000;
     let a = vec![5.0; size];
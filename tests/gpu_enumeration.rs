@@ -0,0 +1,116 @@
+mod common;
+
+use archx::detect::gpu_info::{enumerate_gpus, vendor_name};
+use common::FakeSysfs;
+
+/// Writes `class/drm/card<n>/device` with a `uevent` and the given attributes.
+fn card(sysfs: &FakeSysfs, n: usize, uevent: &str, attrs: &[(&str, &str)]) {
+    let dir = format!("class/drm/card{}/device", n);
+    sysfs.write(&format!("{}/uevent", dir), uevent);
+    for (name, value) in attrs {
+        sysfs.write(&format!("{}/{}", dir, name), value);
+    }
+}
+
+#[test]
+fn test_hybrid_laptop() {
+    let sysfs = FakeSysfs::new("drm-hybrid");
+    card(&sysfs, 0, "DRIVER=i915\nPCI_SLOT_NAME=0000:00:02.0",
+        &[("vendor", "0x8086"), ("device", "0xa7a0"), ("class", "0x030000"), ("boot_vga", "1")]);
+    card(&sysfs, 1, "DRIVER=nvidia\nPCI_SLOT_NAME=0000:01:00.0",
+        &[("vendor", "0x10de"), ("device", "0x28e0"), ("class", "0x030200"), ("boot_vga", "0")]);
+    sysfs.write("class/drm/card0-eDP-1/status", "connected");
+    sysfs.write("class/drm/renderD128/dev", "226:128");
+
+    let gpus = enumerate_gpus(sysfs.root());
+    assert_eq!(gpus.len(), 2, "connectors and render nodes are not devices");
+
+    let (nvidia, intel) = (&gpus[0], &gpus[1]);
+    assert_eq!(nvidia.vendor, "NVIDIA");
+    assert_eq!((nvidia.vendor_id, nvidia.device_id), (Some(0x10de), Some(0x28e0)));
+    assert_eq!(nvidia.driver.as_deref(), Some("nvidia"));
+    assert!(!nvidia.is_integrated, "the discrete GPU comes first");
+    assert_eq!(nvidia.name, "NVIDIA GPU [10de:28e0]");
+
+    assert_eq!(intel.vendor, "Intel");
+    assert_eq!(intel.pci_slot.as_deref(), Some("0000:00:02.0"));
+    assert!(intel.is_integrated && intel.shared_memory);
+    assert!(intel.boot_vga);
+}
+
+#[test]
+fn test_discrete_vram_and_product_name() {
+    let sysfs = FakeSysfs::new("drm-amd");
+    card(&sysfs, 0, "DRIVER=amdgpu\nPCI_SLOT_NAME=0000:03:00.0", &[
+        ("vendor", "0x1002"), ("device", "0x744c"), ("class", "0x030000"), ("boot_vga", "1"),
+        ("mem_info_vram_total", "25753026560"), ("product_name", "Radeon RX 7900 XTX"),
+    ]);
+    card(&sysfs, 1, "DRIVER=amdgpu\nPCI_SLOT_NAME=0000:0c:00.0", &[
+        ("vendor", "0x1002"), ("device", "0x73ff"), ("class", "0x030000"),
+        ("mem_info_vram_total", "8573157376"),
+    ]);
+
+    let gpus = enumerate_gpus(sysfs.root());
+    assert_eq!(gpus.len(), 2);
+    assert_eq!(gpus[0].name, "Radeon RX 7900 XTX");
+    assert_eq!(gpus[0].vram_bytes, Some(25_753_026_560));
+    assert_eq!(gpus[1].vram_bytes, Some(8_573_157_376));
+    assert!(gpus.iter().all(|g| !g.is_integrated));
+}
+
+#[test]
+fn test_boot_gpu_next_to_compute_card_is_discrete() {
+    let sysfs = FakeSysfs::new("drm-compute");
+    card(&sysfs, 0, "DRIVER=amdgpu\nPCI_SLOT_NAME=0000:03:00.0", &[
+        ("vendor", "0x1002"), ("device", "0x744c"), ("class", "0x030000"), ("boot_vga", "1"),
+        ("mem_info_vram_total", "25753026560"), ("mem_info_gtt_total", "33554432000"),
+    ]);
+    card(&sysfs, 1, "DRIVER=nvidia\nPCI_SLOT_NAME=0000:41:00.0",
+        &[("vendor", "0x10de"), ("device", "0x2330"), ("class", "0x030200"), ("boot_vga", "0")]);
+    // Intel Arc sits behind a PCIe bridge, not on bus 0.
+    card(&sysfs, 2, "DRIVER=i915\nPCI_SLOT_NAME=0000:07:00.0",
+        &[("vendor", "0x8086"), ("device", "0x56a0"), ("class", "0x030000")]);
+
+    let gpus = enumerate_gpus(sysfs.root());
+    assert_eq!(gpus.len(), 3);
+    assert!(gpus.iter().all(|g| !g.is_integrated && !g.shared_memory), "{:?}", gpus);
+}
+
+#[test]
+fn test_amd_apu_off_the_root_bus() {
+    let sysfs = FakeSysfs::new("drm-apu");
+    card(&sysfs, 0, "DRIVER=amdgpu\nPCI_SLOT_NAME=0000:c1:00.0", &[
+        ("vendor", "0x1002"), ("device", "0x15bf"), ("class", "0x030000"), ("boot_vga", "1"),
+        ("mem_info_vram_total", "536870912"), ("mem_info_gtt_total", "16777216000"),
+    ]);
+    card(&sysfs, 1, "DRIVER=nvidia\nPCI_SLOT_NAME=0000:01:00.0",
+        &[("vendor", "0x10de"), ("device", "0x28e0"), ("class", "0x030200")]);
+
+    let gpus = enumerate_gpus(sysfs.root());
+    assert_eq!(gpus[0].vendor, "NVIDIA");
+    assert!(!gpus[0].is_integrated);
+    assert_eq!(gpus[1].pci_slot.as_deref(), Some("0000:c1:00.0"));
+    assert!(gpus[1].is_integrated && gpus[1].shared_memory, "512 MiB carve-out next to 16 GB GTT");
+}
+
+#[test]
+fn test_skips_framebuffers_and_keeps_platform_gpus() {
+    let sysfs = FakeSysfs::new("drm-soc");
+    card(&sysfs, 0, "DRIVER=simpledrm", &[]);
+    card(&sysfs, 1, "DRIVER=panfrost\nOF_NAME=gpu", &[]);
+    card(&sysfs, 2, "DRIVER=ast\nPCI_SLOT_NAME=0000:02:00.0",
+        &[("vendor", "0x1a03"), ("device", "0x2000"), ("class", "0x088000")]);
+
+    let gpus = enumerate_gpus(sysfs.root());
+    assert_eq!(gpus.len(), 1);
+    assert_eq!(gpus[0].vendor, "panfrost");
+    assert!(gpus[0].is_integrated, "non-PCI GPUs are part of the SoC");
+}
+
+#[test]
+fn test_no_drm_tree() {
+    let sysfs = FakeSysfs::new("drm-none");
+    assert!(enumerate_gpus(sysfs.root()).is_empty());
+    assert_eq!(vendor_name(0x10de), "NVIDIA");
+    assert_eq!(vendor_name(0xabcd), "Vendor abcd");
+}
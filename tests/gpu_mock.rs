@@ -0,0 +1,73 @@
+use archx::dispatch::hybrid::HybridScheduler;
+use archx::gpu::{self, FailureSchedule, GpuBackend, GpuFault, GpuPolicy, MockGpuBackend};
+
+#[test]
+fn test_mock_failure_schedules() {
+    let a = vec![1.0f32; 256];
+    let b = vec![2.0f32; 256];
+    let mut out = vec![0.0f32; 256];
+
+    // Every 3rd call fails with a timeout
+    let nth = MockGpuBackend::new().fail_with(GpuFault::Timeout, FailureSchedule::EveryNth(3));
+    let results: Vec<bool> = (0..6).map(|_| nth.add(&a, &b, &mut out).is_ok()).collect();
+    assert_eq!(results, vec![true, true, false, true, true, false]);
+    assert_eq!(nth.call_count(), 6);
+    assert_eq!(nth.fault_count(), 2);
+    assert_eq!(nth.bytes_processed(), 6 * 256 * 2 * 4);
+
+    // Out of memory once 4KB have been submitted (each call submits 2KB)
+    let oom = MockGpuBackend::new().fail_with(GpuFault::OutOfMemory, FailureSchedule::AfterBytes(4096));
+    assert!(oom.mul(&a, &b, &mut out).is_ok());
+    assert!(oom.mul(&a, &b, &mut out).is_ok());
+    let err = oom.mul(&a, &b, &mut out).unwrap_err();
+    assert!(err.contains("out of device memory"));
+
+    // Corrupted data succeeds but poisons the output
+    let corrupt = MockGpuBackend::new().fail_with(GpuFault::CorruptData, FailureSchedule::EveryNth(1));
+    assert!(corrupt.sub(&a, &b, &mut out).is_ok());
+    assert!(out.iter().all(|v| v.is_nan()));
+    assert!(corrupt.dot(&a, &b).unwrap().is_nan());
+
+    // Random schedules are reproducible for the same seed
+    let run = |seed| {
+        let mock = MockGpuBackend::new()
+            .fail_with(GpuFault::DeviceLost, FailureSchedule::Random { probability: 0.5, seed });
+        (0..64).map(|_| mock.dot(&a, &b).is_ok()).collect::<Vec<_>>()
+    };
+    assert_eq!(run(42), run(42));
+    assert!(run(42).iter().any(|ok| *ok) && run(42).iter().any(|ok| !*ok));
+
+    // Mismatched lengths are rejected before the call is submitted
+    let mock = MockGpuBackend::new();
+    assert!(mock.add(&a, &b[..128], &mut out).unwrap_err().contains("identical lengths"));
+    assert!(mock.add(&a, &b, &mut out[..128]).is_err());
+    assert!(mock.dot(&a[..1], &b).is_err());
+    assert_eq!(mock.call_count(), 0);
+}
+
+#[test]
+fn test_mock_registered_with_hybrid() {
+    let mock = MockGpuBackend::new()
+        .with_name("MockHybrid")
+        .fail_with(GpuFault::DeviceLost, FailureSchedule::EveryNth(2));
+    gpu::register_backend(Box::new(mock.clone()));
+    gpu::set_gpu_policy(GpuPolicy::Adaptive);
+
+    // 2M elements: 70% goes to the GPU partition, the rest to the CPU
+    let size = 2_000_000;
+    let a = vec![1.0f32; size];
+    let b = vec![4.0f32; size];
+    let mut out = vec![0.0f32; size];
+
+    HybridScheduler::dispatch_add(&a, &b, &mut out).unwrap();
+    HybridScheduler::dispatch_add(&a, &b, &mut out).unwrap();
+    assert!(out.iter().all(|&v| v == 5.0));
+
+    let calls = mock.calls();
+    assert_eq!(calls.len(), 2);
+    assert_eq!(calls[0].op, "add");
+    assert_eq!(calls[0].elements, (size as f32 * 0.7) as usize);
+    assert_eq!(calls[0].fault, None);
+    assert_eq!(calls[1].fault, Some(GpuFault::DeviceLost));
+    assert!(!gpu::is_quarantined("MockHybrid"));
+}
@@ -0,0 +1,46 @@
+use archx::detect::{HardwareCache, HardwareState};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+#[test]
+fn test_hardware_cache_lifetimes() {
+    let cache = HardwareCache::global();
+
+    // Static facts are probed once and shared.
+    let s1 = cache.static_facts();
+    let s2 = cache.static_facts();
+    assert!(Arc::ptr_eq(&s1, &s2));
+    assert!(s1.logical_threads > 0);
+
+    // Fresh dynamic snapshots are reused until the TTL expires.
+    cache.set_ttl(Duration::from_secs(60));
+    let d1 = cache.dynamic_facts();
+    let d2 = cache.dynamic_facts();
+    assert!(Arc::ptr_eq(&d1, &d2));
+
+    cache.invalidate();
+    let d3 = cache.dynamic_facts();
+    assert!(!Arc::ptr_eq(&d1, &d3));
+
+    // Operations no longer pay a detection probe each time.
+    let a = vec![1.0f32; 64];
+    let mut out = vec![0.0f32; 64];
+    let start = Instant::now();
+    for _ in 0..200 {
+        archx::archx().add(&a, &a, &mut out).unwrap();
+        let _ = HardwareState::capture();
+    }
+    assert!(start.elapsed() < Duration::from_secs(2), "200 ops took {:?}", start.elapsed());
+
+    // The background sampler refreshes snapshots without readers doing any work.
+    cache.start_sampler(Duration::from_millis(20));
+    assert!(cache.is_sampling());
+    let before = cache.dynamic_facts();
+    std::thread::sleep(Duration::from_millis(120));
+    let after = cache.dynamic_facts();
+    assert!(after.captured_at > before.captured_at);
+    cache.stop_sampler();
+    assert!(!cache.is_sampling());
+
+    cache.set_ttl(archx::detect::cache::DEFAULT_DYNAMIC_TTL);
+}
@@ -0,0 +1,93 @@
+use archx::adaptive::{AdaptiveEngine, Strategy};
+use archx::core::{
+    ArchXCoreError, ArithmeticMode, CoreEngine, CoreResult, DefaultResourceManager, MathProcessor,
+    StaticHardwareProvider,
+};
+use archx::{archx, WorkloadHints};
+use std::path::Path;
+use std::sync::Arc;
+
+fn fixture(name: &str) -> StaticHardwareProvider {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/hardware").join(name);
+    StaticHardwareProvider::from_file(path).unwrap()
+}
+
+#[test]
+fn test_replay_server_profile() {
+    let engine = CoreEngine::builder().with_hardware(fixture("server_128c_avx512.json")).build();
+    let info = engine.system_info();
+    assert!(info.cpu.features.avx512f && info.cpu.features.has_avx512_common());
+    assert_eq!(info.gpu.as_ref().map(|g| g.name.as_str()), Some("NVIDIA GPU [10de:2330]"));
+
+    let cpu_only = WorkloadHints { prefer_gpu: false, ..Default::default() };
+    assert_eq!(AdaptiveEngine::choose_strategy(1_000_000, &cpu_only, &info), Strategy::ParallelSimd(256));
+    assert_eq!(AdaptiveEngine::choose_strategy(2_000_000, &WorkloadHints::default(), &info), Strategy::Hybrid);
+}
+
+#[test]
+fn test_replay_low_battery_arm_board() {
+    let info = CoreEngine::builder().with_hardware(fixture("arm_2c_board.json")).build().system_info();
+    assert!(info.cpu.features.neon && !info.cpu.features.avx2);
+    assert!(info.gpu.is_none());
+
+    // Balanced hints resolve to power saving on a low battery.
+    assert_eq!(AdaptiveEngine::choose_strategy(100_000, &WorkloadHints::default(), &info), Strategy::SingleThreadSimd);
+    // 0.5 GB cannot hold three 100M-element buffers.
+    assert!(!info.can_handle_dataset(100_000_000));
+}
+
+#[test]
+fn test_invalid_profile() {
+    let err = StaticHardwareProvider::from_json("{\"cpu\": 1}").unwrap_err();
+    assert!(matches!(err, ArchXCoreError::InvalidInput(_)));
+    assert!(matches!(StaticHardwareProvider::from_file("/nonexistent/profile.json"), Err(ArchXCoreError::DetectionError(_))));
+}
+
+#[test]
+fn test_default_scheduler_uses_injected_resources() {
+    let engine = CoreEngine::builder().with_resources(DefaultResourceManager::with_limits(1, Some(1024))).build();
+    assert!(!engine.resources().reserve_memory(2048));
+
+    assert!(engine.resources().reserve_threads(1));
+    assert!(engine.scheduler().prepare_task().is_err(), "the only thread is taken");
+    engine.resources().release_threads(1);
+    assert_eq!(engine.scheduler().run(|| 7).unwrap(), 7);
+}
+
+struct ConstantMath;
+
+impl MathProcessor for ConstantMath {
+    fn add(&self, _: &[f32], _: &[f32], out: &mut [f32], _: ArithmeticMode) -> CoreResult<()> {
+        out.fill(42.0);
+        Ok(())
+    }
+    fn sub(&self, a: &[f32], b: &[f32], out: &mut [f32], mode: ArithmeticMode) -> CoreResult<()> {
+        self.add(a, b, out, mode)
+    }
+    fn mul(&self, a: &[f32], b: &[f32], out: &mut [f32], mode: ArithmeticMode) -> CoreResult<()> {
+        self.add(a, b, out, mode)
+    }
+    fn dot(&self, _: &[f32], _: &[f32], _: ArithmeticMode) -> CoreResult<f32> {
+        Ok(42.0)
+    }
+    fn sum(&self, _: &[f32], _: ArithmeticMode) -> CoreResult<f32> {
+        Ok(42.0)
+    }
+}
+
+#[test]
+fn test_sovereign_builder_on_custom_engine() {
+    let engine = Arc::new(
+        CoreEngine::builder()
+            .with_hardware(fixture("arm_2c_board.json"))
+            .with_math(ConstantMath)
+            .build(),
+    );
+
+    let mut out = [0.0; 3];
+    archx().with_engine(engine.clone()).add(&[1.0; 3], &[2.0; 3], &mut out).unwrap();
+    assert_eq!(out, [42.0; 3]);
+    assert_eq!(archx().with_engine(engine.clone()).sum(&[1.0]).unwrap(), 42.0);
+    // The builders hold the only other references and have been dropped.
+    assert_eq!(Arc::strong_count(&engine), 1);
+}
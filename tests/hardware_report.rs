@@ -0,0 +1,40 @@
+use archx::adaptive::Strategy;
+use archx::report::HardwareReport;
+use std::process::Command;
+
+#[test]
+fn test_report_contents() {
+    let report = HardwareReport::collect_for(&[100, 10_000_000]);
+    assert_eq!(report.strategies.len(), 2);
+    assert_eq!(report.strategies[0].balanced, Strategy::ScalarFallback);
+    assert!(report.limits.effective_threads >= 1);
+    assert!(report.gpu_backends.iter().any(|b| b.name == "Vulkan"));
+    assert_eq!(report.enabled_features, report.system.cpu.features.enabled());
+
+    let text = report.to_string();
+    for section in ["== CPU ==", "== Dispatch ==", "== GPU ==", "== Limits ==", "== Strategies =="] {
+        assert!(text.contains(section), "missing {}", section);
+    }
+    assert!(text.contains("ScalarFallback"));
+}
+
+#[test]
+fn test_report_json() {
+    let json: serde_json::Value = serde_json::from_str(&HardwareReport::collect_for(&[4096]).to_json()).unwrap();
+    assert_eq!(json["strategies"][0]["len"], 4096);
+    assert!(json["dispatch"]["kernels"]["add"].is_string());
+    assert!(json["state"]["cpu"]["logical_threads"].as_u64().unwrap() >= 1);
+    assert!(json["limits"]["memory_budget_bytes"].is_u64());
+}
+
+#[test]
+fn test_archx_info_binary() {
+    let output = Command::new(env!("CARGO_BIN_EXE_archx-info")).args(["--json", "--sizes", "512,1_000_000"]).output().unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["strategies"].as_array().unwrap().len(), 2);
+    assert_eq!(json["strategies"][1]["len"], 1_000_000);
+
+    let output = Command::new(env!("CARGO_BIN_EXE_archx-info")).arg("--sizes").arg("lots").output().unwrap();
+    assert_eq!(output.status.code(), Some(2));
+}
@@ -0,0 +1,101 @@
+mod common;
+
+use archx::profiler::{get_profiler, HardwareFingerprint, HtmlReportExporter, Metric, ProfileScope, ReportExporter};
+use archx::profiler::reports::MAX_TIMELINE_SPANS;
+use common::TempDir;
+use std::time::Duration;
+
+fn span(name: &str, device: &str, id: u64, parent: Option<u64>, thread: usize, start_ms: u64, ms: u64) -> Metric {
+    Metric {
+        start: Duration::from_millis(start_ms),
+        thread_id: Some(thread),
+        id,
+        parent_id: parent,
+        ..Metric::new(name, if device == "CPU" { "SIMD" } else { "Vulkan" }, device, Duration::from_millis(ms))
+    }
+}
+
+fn sample_run() -> Vec<Metric> {
+    vec![
+        span("Parallel Add", "CPU", 1, None, 100, 0, 10),
+        span("Parallel Chunk", "CPU", 2, Some(1), 101, 1, 8),
+        span("Parallel Chunk", "CPU", 3, Some(1), 102, 1, 4),
+        span("Dispatch", "CPU", 4, Some(1), 100, 2, 1),
+        span("GPU Add", "dGPU", 5, Some(1), 100, 2, 6),
+    ]
+}
+
+fn embedded(html: &str) -> Vec<Metric> {
+    let start = html.find("id=\"archx-data\">").unwrap() + "id=\"archx-data\">".len();
+    let end = start + html[start..].find("</script>").unwrap();
+    serde_json::from_str(&html[start..end]).unwrap()
+}
+
+#[test]
+fn test_report_sections() {
+    let hardware = HardwareFingerprint::current();
+    let html = HtmlReportExporter::new().with_title("Nightly").with_hardware(hardware.clone()).render(&sample_run());
+
+    assert!(html.starts_with("<!DOCTYPE html>") && html.trim_end().ends_with("</html>"));
+    assert!(html.contains("<title>Nightly</title>"));
+    for track in ["Thread 100", "Thread 101", "Thread 102", "dGPU (Vulkan)"] {
+        assert!(html.contains(&format!(">{}</text>", track)), "missing track {}", track);
+    }
+    assert!(html.contains("<title>Parallel Add [CPU/SIMD] 10.0000 ms</title>"));
+    // Nested CPU spans are not counted twice: 10 ms CPU and 6 ms GPU.
+    assert!(html.contains("CPU 62.5% &middot; GPU 37.5% of busy time"));
+    assert!(html.contains("<b>Parallel Chunk</b> <span class=\"muted\">CPU/SIMD &middot; 2 calls</span>"));
+    assert!(html.contains("<th>Threads</th>"));
+    assert!(html.contains(&format!("{} logical", hardware.logical_threads)));
+    assert!(!html.contains("<script>"), "the report needs no script");
+
+    assert_eq!(embedded(&html).len(), 5);
+}
+
+#[test]
+fn test_untrusted_names_are_escaped() {
+    let mut run = sample_run();
+    run[0].name = "</script><img src=x onerror=alert(1)>".to_string();
+    let html = HtmlReportExporter::new().render(&run);
+
+    assert!(!html.contains("<img"));
+    assert!(html.contains("&lt;/script&gt;&lt;img"));
+    assert_eq!(embedded(&html)[0].name, run[0].name);
+    assert!(html.contains("Not recorded."));
+}
+
+#[test]
+fn test_empty_and_large_snapshots() {
+    let html = HtmlReportExporter::new().render(&[]);
+    assert_eq!(html.matches("No spans recorded.").count(), 3);
+    assert!(embedded(&html).is_empty());
+
+    let many: Vec<Metric> = (0..MAX_TIMELINE_SPANS as u64 + 10)
+        .map(|i| span("Tiny", "CPU", i + 1, None, 1, i, 1 + i % 3))
+        .collect();
+    let html = HtmlReportExporter::new().render(&many);
+    assert!(html.contains(&format!("Showing the {} longest of {} spans.", MAX_TIMELINE_SPANS, many.len())));
+    assert_eq!(embedded(&html).len(), many.len());
+}
+
+#[test]
+fn test_export_and_profiler_report() {
+    let dir = TempDir::new("html-report");
+    let path = dir.join("report.html");
+    HtmlReportExporter::new().export(&sample_run(), path.to_str().unwrap()).unwrap();
+    assert!(std::fs::read_to_string(&path).unwrap().contains("GPU Add"));
+    assert!(HtmlReportExporter::new().export(&vec!["not a metric"], path.to_str().unwrap()).is_err());
+
+    let profiler = get_profiler();
+    profiler.clear();
+    profiler.set_enabled(true);
+    {
+        let _scope = ProfileScope::new("Html Work", "CPU", "Test");
+    }
+    let html = profiler.to_html();
+    profiler.set_enabled(false);
+    profiler.clear();
+
+    assert!(html.contains("<b>Html Work</b>"));
+    assert!(!html.contains("Not recorded."));
+}
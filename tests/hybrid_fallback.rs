@@ -0,0 +1,70 @@
+use archx::dispatch::hybrid::HybridScheduler;
+use archx::gpu::{self, GpuBackend, GpuPolicy};
+use std::time::Duration;
+
+struct FailingBackend;
+
+impl GpuBackend for FailingBackend {
+    fn is_available(&self) -> bool { true }
+    fn add(&self, _: &[f32], _: &[f32], _: &mut [f32]) -> Result<(), String> {
+        Err("device lost".to_string())
+    }
+    fn sub(&self, _: &[f32], _: &[f32], _: &mut [f32]) -> Result<(), String> {
+        Err("device lost".to_string())
+    }
+    fn mul(&self, _: &[f32], _: &[f32], _: &mut [f32]) -> Result<(), String> {
+        Err("device lost".to_string())
+    }
+    fn dot(&self, _: &[f32], _: &[f32]) -> Result<f32, String> {
+        Err("device lost".to_string())
+    }
+    fn name(&self) -> &'static str { "Failing" }
+}
+
+#[test]
+fn test_gpu_failure_falls_back_to_cpu() {
+    gpu::register_backend(Box::new(FailingBackend));
+    gpu::set_gpu_policy(GpuPolicy::ForceGpu);
+    archx::get_profiler().set_enabled(true);
+
+    let size = 10_000;
+    let a = vec![2.0f32; size];
+    let b = vec![3.0f32; size];
+    let mut out = vec![0.0f32; size];
+    let fallbacks_before = gpu::fallback_count();
+
+    HybridScheduler::dispatch_add(&a, &b, &mut out).unwrap();
+    assert!(out.iter().all(|&v| v == 5.0));
+
+    HybridScheduler::dispatch_sub(&a, &b, &mut out).unwrap();
+    assert!(out.iter().all(|&v| v == -1.0));
+
+    HybridScheduler::dispatch_mul(&a, &b, &mut out).unwrap();
+    assert!(out.iter().all(|&v| v == 6.0));
+
+    // The failing backend is now quarantined; the next call runs on a built-in backend.
+    assert!(gpu::is_quarantined("Failing"));
+    let dot = HybridScheduler::dispatch_dot(&a, &b).unwrap();
+    assert_eq!(dot, 6.0 * size as f32);
+
+    assert!(gpu::fallback_count() >= fallbacks_before + 3);
+    let fallbacks = archx::get_profiler().get_snapshot().into_iter()
+        .filter(|m| m.name == "GPU Fallback" && m.backend == "Failing")
+        .count();
+    assert!(fallbacks >= 3);
+
+    let err = HybridScheduler::dispatch_sub(&a, &b[..10], &mut out);
+    assert!(matches!(err, Err(archx::ArchXError::InvalidInput(_))));
+
+    // After the cooldown the backend is probed again; a new fault re-quarantines it at once.
+    gpu::set_quarantine_cooldown(Duration::from_millis(20));
+    std::thread::sleep(Duration::from_millis(40));
+    assert!(!gpu::is_quarantined("Failing"));
+    HybridScheduler::dispatch_add(&a, &b, &mut out).unwrap();
+    assert!(out.iter().all(|&v| v == 5.0));
+    gpu::set_quarantine_cooldown(gpu::health::DEFAULT_QUARANTINE_COOLDOWN);
+    assert!(gpu::is_quarantined("Failing"));
+
+    gpu::clear_quarantine();
+    gpu::set_gpu_policy(GpuPolicy::Adaptive);
+}
@@ -0,0 +1,123 @@
+mod common;
+
+use archx::detect::NumaTopology;
+use archx::runtime::{CancellationToken, ExecControl, NumaPool};
+use archx::ArchXError;
+use common::FakeSysfs;
+use std::sync::Mutex;
+
+#[test]
+fn test_numa_topology_from_sysfs() {
+    let sys = FakeSysfs::new("numa");
+    sys.write("devices/system/node/node0/cpulist", "0-3,8-11")
+        .write("devices/system/node/node0/distance", "10 21")
+        .write("devices/system/node/node0/meminfo", "Node 0 MemTotal:       65536 kB\nNode 0 MemFree:        1024 kB")
+        .write("devices/system/node/node1/cpulist", "4-7,12-15")
+        .write("devices/system/node/node1/distance", "21 10")
+        .write("devices/system/node/node1/meminfo", "Node 1 MemTotal:       32768 kB");
+
+    let topo = NumaTopology::from_sysfs(sys.root()).unwrap();
+    assert_eq!(topo.nodes.len(), 2);
+    assert!(topo.is_numa());
+    assert_eq!(topo.nodes[0].cpus, vec![0, 1, 2, 3, 8, 9, 10, 11]);
+    assert_eq!(topo.nodes[1].distances, vec![21, 10]);
+    assert_eq!(topo.nodes[0].memory_bytes, Some(65536 * 1024));
+    assert_eq!(topo.node_of_cpu(13), Some(1));
+    assert_eq!(topo.node_of_cpu(64), None);
+
+    assert!(NumaTopology::from_sysfs(FakeSysfs::new("numa-empty").root()).is_none());
+    assert!(!NumaTopology::single_node(4).is_numa());
+}
+
+#[test]
+fn test_node_ranges_are_page_aligned_and_weighted() {
+    let mut topo = NumaTopology::uniform(2, 1);
+    topo.nodes[1].cpus = vec![1, 2, 3];
+    let pool = NumaPool::with_limits(topo, usize::MAX, None).unwrap();
+
+    let ranges = pool.node_ranges(1 << 20);
+    assert_eq!(ranges, vec![0..(1 << 18), (1 << 18)..(1 << 20)]);
+    assert_eq!(ranges[0].end % 1024, 0);
+
+    // Tiny inputs may leave a node idle but always cover the whole slice.
+    let tiny = pool.node_ranges(10);
+    assert_eq!(tiny.last().unwrap().end, 10);
+}
+
+#[test]
+fn test_chunks_run_on_owning_node() {
+    let pool = NumaPool::with_limits(NumaTopology::uniform(2, 2), usize::MAX, None).unwrap();
+    assert_eq!(pool.groups().len(), 2);
+    assert_eq!(pool.run_on_node(1, NumaPool::current_node), Some(Some(1)));
+    assert_eq!(NumaPool::current_node(), None);
+
+    let len = 1 << 18;
+    let ranges = pool.node_ranges(len);
+    let seen = Mutex::new(Vec::new());
+    pool.for_each_range(len, 1, &ExecControl::none(), |node, chunk| {
+        seen.lock().unwrap().push((node, NumaPool::current_node(), chunk));
+    }).unwrap();
+
+    let seen = seen.into_inner().unwrap();
+    let covered: usize = seen.iter().map(|(_, _, c)| c.len()).sum();
+    assert_eq!(covered, len);
+    for (node, current, chunk) in seen {
+        assert_eq!(current, Some(node));
+        assert!(ranges[node].start <= chunk.start && chunk.end <= ranges[node].end);
+    }
+}
+
+#[test]
+fn test_numa_operations_and_allocation() {
+    let pool = NumaPool::with_limits(NumaTopology::uniform(2, 2), usize::MAX, None).unwrap();
+    let len = 300_001;
+    let a = pool.copy_from(&vec![1.5; len]);
+    let b = pool.copy_from(&vec![2.0; len]);
+    let mut out = pool.zeroed(len);
+    assert!(out.iter().all(|&x| x == 0.0));
+
+    let ctl = ExecControl::none();
+    pool.add_with(&a, &b, &mut out, &ctl).unwrap();
+    assert!(out.iter().all(|&x| x == 3.5));
+    pool.mul_with(&a, &b, &mut out, &ctl).unwrap();
+    assert!(out.iter().all(|&x| x == 3.0));
+    pool.sub_with(&a, &b, &mut out, &ctl).unwrap();
+    assert!(out.iter().all(|&x| x == -0.5));
+
+    let small = vec![1.0; 1000];
+    assert_eq!(pool.sum_with(&small, &ctl).unwrap(), 1000.0);
+    assert_eq!(pool.dot_with(&small, &small, &ctl).unwrap(), 1000.0);
+}
+
+#[test]
+fn test_numa_operations_honour_cancellation() {
+    let pool = NumaPool::with_limits(NumaTopology::uniform(2, 1), usize::MAX, None).unwrap();
+    let token = CancellationToken::new();
+    token.cancel();
+    let ctl = ExecControl::none().with_token(token);
+
+    let data = vec![1.0; 100_000];
+    assert!(matches!(pool.sum_with(&data, &ctl), Err(ArchXError::Cancelled)));
+}
+
+#[test]
+fn test_pool_respects_quota_and_cpuset() {
+    // Two 4-CPU nodes, a 4-thread quota and a cpuset covering only CPUs 1-2 and 4-7.
+    let allowed = [1, 2, 4, 5, 6, 7];
+    let pool = NumaPool::with_limits(NumaTopology::uniform(2, 4), 4, Some(&allowed)).unwrap();
+    let groups = pool.groups();
+    assert_eq!(groups[0].cpus, vec![1, 2]);
+    assert_eq!(groups[1].cpus, vec![4, 5, 6, 7]);
+    assert_eq!(groups.iter().map(|g| g.threads()).collect::<Vec<_>>(), vec![1, 2]);
+
+    // A node outside the cpuset gets no workers; a tiny quota still leaves one per node.
+    let pool = NumaPool::with_limits(NumaTopology::uniform(2, 4), 1, Some(&[0, 1])).unwrap();
+    assert_eq!(pool.groups().len(), 1);
+    assert_eq!(pool.groups()[0].threads(), 1);
+    assert!(NumaPool::with_limits(NumaTopology::uniform(2, 4), 4, Some(&[64])).is_err());
+
+    // The host constructor never starts more workers than the process may use.
+    let pool = NumaPool::new(NumaTopology::uniform(2, 2)).unwrap();
+    let workers: usize = pool.groups().iter().map(|g| g.threads()).sum();
+    assert!(workers <= archx::detect::effective_threads().max(pool.groups().len()));
+}
@@ -0,0 +1,125 @@
+use archx::adaptive::AdaptiveEngine;
+use archx::hardware::SystemInfo;
+use archx::metrics::{render_openmetrics, serve_metrics, OPENMETRICS_CONTENT_TYPE};
+use archx::profiler::{get_profiler, Metric, ProfileScope, THREAD_BUFFER_CAPACITY};
+use archx::WorkloadHints;
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::sync::Mutex;
+use std::time::Duration;
+
+static LOCK: Mutex<()> = Mutex::new(());
+
+fn sample<'a>(text: &'a str, prefix: &str) -> Option<&'a str> {
+    text.lines().find(|l| l.starts_with(prefix)).and_then(|l| l.rsplit(' ').next())
+}
+
+#[test]
+fn test_exposition_covers_runtime_metrics() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let profiler = get_profiler();
+    profiler.clear();
+    profiler.set_enabled(true);
+    for _ in 0..3 {
+        let _scope = ProfileScope::new("Metrics \"Op\"", "CPU", "Test");
+        std::thread::sleep(Duration::from_micros(50));
+    }
+    profiler.set_enabled(false);
+    AdaptiveEngine::choose_strategy(10, &WorkloadHints::default(), &SystemInfo::detect());
+
+    let text = render_openmetrics();
+    profiler.clear();
+
+    let labels = r#"name="Metrics \"Op\"",device="CPU",backend="Test""#;
+    assert_eq!(sample(&text, &format!("archx_operations_total{{{}}}", labels)), Some("3"));
+    assert_eq!(sample(&text, &format!("archx_operation_duration_seconds_count{{{}}}", labels)), Some("3"));
+    assert_eq!(sample(&text, &format!("archx_operation_duration_seconds_bucket{{{},le=\"0.00001\"}}", labels)), Some("0"));
+    assert_eq!(sample(&text, &format!("archx_operation_duration_seconds_bucket{{{},le=\"1\"}}", labels)), Some("3"));
+    assert_eq!(sample(&text, &format!("archx_operation_duration_seconds_bucket{{{},le=\"+Inf\"}}", labels)), Some("3"));
+
+    let scalar: u64 = sample(&text, "archx_strategy_chosen_total{strategy=\"ScalarFallback\"}").unwrap().parse().unwrap();
+    assert!(scalar >= 1);
+    for prefix in ["archx_active_threads ", "archx_reserved_memory_bytes ", "archx_gpu_fallbacks_total ",
+                   "archx_throttled{reason=\"high_load\"} ", "archx_throttled{reason=\"low_power\"} ",
+                   "archx_gpu_backend_quarantined{backend=\"Vulkan\"} "] {
+        assert!(text.lines().any(|l| l.starts_with(prefix)), "missing {}", prefix);
+    }
+}
+
+#[test]
+fn test_operation_counters_are_cumulative() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let profiler = get_profiler();
+    profiler.clear();
+    profiler.set_enabled(true);
+    let labels = r#"name="Metrics Wrap",device="CPU",backend="Test""#;
+    let total = |text: &str| -> u64 { sample(text, &format!("archx_operations_total{{{}}}", labels)).unwrap().parse().unwrap() };
+
+    let spans = |n: usize| {
+        for _ in 0..n {
+            let _scope = ProfileScope::new("Metrics Wrap", "CPU", "Test");
+        }
+    };
+    spans(THREAD_BUFFER_CAPACITY);
+    let first = total(&render_openmetrics());
+    assert_eq!(first, THREAD_BUFFER_CAPACITY as u64);
+
+    // The ring buffer wraps and is cleared, yet the counter keeps growing.
+    spans(THREAD_BUFFER_CAPACITY + 100);
+    profiler.clear();
+    let text = render_openmetrics();
+    assert_eq!(total(&text), 2 * THREAD_BUFFER_CAPACITY as u64 + 100);
+    assert_eq!(sample(&text, &format!("archx_operation_duration_seconds_bucket{{{},le=\"+Inf\"}}", labels)),
+        Some((2 * THREAD_BUFFER_CAPACITY + 100).to_string().as_str()));
+
+    // Spans of exited threads and recorded metrics count as well.
+    std::thread::spawn(move || spans(10)).join().unwrap();
+    profiler.record(Metric::new("Metrics Wrap", "Test", "CPU", Duration::from_micros(5)));
+    profiler.set_enabled(false);
+    spans(10);
+    assert_eq!(total(&render_openmetrics()), 2 * THREAD_BUFFER_CAPACITY as u64 + 111);
+    profiler.clear();
+}
+
+#[test]
+fn test_exposition_is_well_formed() {
+    let text = render_openmetrics();
+    assert!(text.ends_with("# EOF\n"));
+
+    let families: Vec<&str> = text.lines()
+        .filter_map(|l| l.strip_prefix("# TYPE "))
+        .map(|l| l.split(' ').next().unwrap())
+        .collect();
+    for line in text.lines().filter(|l| !l.starts_with('#')) {
+        let name = line.split(['{', ' ']).next().unwrap();
+        assert!(families.iter().any(|f| name.starts_with(f)), "sample {} has no TYPE", name);
+        assert!(line.rsplit(' ').next().unwrap().parse::<f64>().is_ok(), "bad value in {}", line);
+    }
+}
+
+fn get(addr: std::net::SocketAddr, request: &str) -> String {
+    let mut stream = TcpStream::connect(addr).unwrap();
+    stream.write_all(request.as_bytes()).unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    response
+}
+
+#[test]
+fn test_server_serves_metrics_on_localhost() {
+    let server = serve_metrics(0).unwrap();
+    let addr = server.local_addr();
+    assert!(addr.ip().is_loopback());
+
+    let response = get(addr, "GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n");
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+    assert!(response.contains(&format!("Content-Type: {}", OPENMETRICS_CONTENT_TYPE)));
+    assert!(response.contains("archx_active_threads "));
+    assert!(response.ends_with("# EOF\n"));
+
+    assert!(get(addr, "GET /other HTTP/1.1\r\n\r\n").starts_with("HTTP/1.1 404"));
+    assert!(get(addr, "POST /metrics HTTP/1.1\r\n\r\n").starts_with("HTTP/1.1 405"));
+
+    server.shutdown();
+    assert!(TcpStream::connect(addr).is_err());
+}
@@ -0,0 +1,97 @@
+mod common;
+
+use archx::decision::Policy;
+use archx::detect::power::read_power_supplies;
+use archx::detect::system::detect_context_with;
+use archx::detect::{BatteryStatus, PowerConfig, PowerSource, PowerSupplyKind, SystemContext};
+use archx::PowerMode;
+use common::FakeSysfs;
+
+fn config(sysfs: &FakeSysfs, threshold: f32) -> PowerConfig {
+    PowerConfig { sysfs_root: sysfs.root().to_path_buf(), low_battery_threshold: threshold }
+}
+
+fn laptop(name: &str, ac_online: bool, capacity: u32, status: &str) -> FakeSysfs {
+    let sysfs = FakeSysfs::new(name);
+    sysfs
+        .write("class/power_supply/AC/type", "Mains")
+        .write("class/power_supply/AC/online", if ac_online { "1" } else { "0" })
+        .write("class/power_supply/BAT0/type", "Battery")
+        .write("class/power_supply/BAT0/capacity", &capacity.to_string())
+        .write("class/power_supply/BAT0/status", status);
+    sysfs
+}
+
+#[test]
+fn test_reads_power_supplies() {
+    let sysfs = laptop("power-read", true, 80, "Charging");
+    sysfs
+        .write("class/power_supply/hidpp_battery_0/type", "Battery")
+        .write("class/power_supply/hidpp_battery_0/scope", "Device")
+        .write("class/power_supply/hidpp_battery_0/capacity", "5");
+
+    let supplies = read_power_supplies(sysfs.root());
+    assert_eq!(supplies.len(), 2, "device-scoped batteries are ignored");
+    assert_eq!(supplies[0].name, "AC");
+    assert_eq!(supplies[0].kind, PowerSupplyKind::Mains);
+    assert_eq!(supplies[0].online, Some(true));
+    assert_eq!(supplies[1].kind, PowerSupplyKind::Battery);
+    assert_eq!(supplies[1].capacity, Some(80.0));
+    assert_eq!(supplies[1].status, Some(BatteryStatus::Charging));
+}
+
+#[test]
+fn test_context_on_ac() {
+    let sysfs = laptop("power-ac", true, 10, "Charging");
+    let ctx = detect_context_with(&config(&sysfs, 20.0));
+    assert_eq!(ctx.power_source, PowerSource::AC);
+    assert_eq!(ctx.battery_level, Some(10.0));
+    assert!(!ctx.is_low_power_mode, "a low battery on AC is not low-power mode");
+}
+
+#[test]
+fn test_context_low_battery() {
+    let sysfs = laptop("power-low", false, 15, "Discharging");
+    let ctx = detect_context_with(&config(&sysfs, 20.0));
+    assert_eq!(ctx.power_source, PowerSource::Battery);
+    assert_eq!(ctx.battery_status, Some(BatteryStatus::Discharging));
+    assert!(ctx.is_low_power_mode);
+
+    let ctx = detect_context_with(&config(&sysfs, 10.0));
+    assert!(!ctx.is_low_power_mode, "threshold is configurable");
+}
+
+#[test]
+fn test_context_battery_without_mains_entry() {
+    let sysfs = FakeSysfs::new("power-nomains");
+    sysfs
+        .write("class/power_supply/BAT1/type", "Battery")
+        .write("class/power_supply/BAT1/capacity", "50")
+        .write("class/power_supply/BAT1/status", "Discharging");
+    let ctx = detect_context_with(&config(&sysfs, 60.0));
+    assert_eq!(ctx.power_source, PowerSource::Battery);
+    assert!(ctx.is_low_power_mode);
+}
+
+#[test]
+fn test_context_without_supplies() {
+    let sysfs = FakeSysfs::new("power-none");
+    let ctx = detect_context_with(&config(&sysfs, 20.0));
+    assert_eq!(ctx.power_source, PowerSource::Unknown);
+    assert_eq!(ctx.battery_level, None);
+    assert!(!ctx.is_low_power_mode);
+}
+
+#[test]
+fn test_low_power_resolves_policy() {
+    let low = SystemContext { is_low_power_mode: true, ..SystemContext::default() };
+    let normal = SystemContext::default();
+
+    assert_eq!(Policy::Balanced.resolve(&low), Policy::PowerSaving);
+    assert_eq!(Policy::SmartAuto.resolve(&low), Policy::PowerSaving);
+    assert_eq!(Policy::Performance.resolve(&low), Policy::Performance, "explicit policies are kept");
+    assert_eq!(Policy::Balanced.resolve(&normal), Policy::Balanced);
+
+    assert!(matches!(PowerMode::Balanced.resolve(&low), PowerMode::PowerSaving));
+    assert!(matches!(PowerMode::HighPerformance.resolve(&low), PowerMode::HighPerformance));
+}
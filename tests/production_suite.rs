@@ -19,6 +19,7 @@ fn test_v1_0_engine_stability() {
 }
 
 #[tokio::test]
+#[allow(deprecated)]
 async fn test_v1_0_async_parity() {
     let a = vec![5.0; 10_000];
     let b = vec![5.0; 10_000];
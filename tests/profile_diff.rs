@@ -0,0 +1,101 @@
+mod common;
+
+use archx::profiler::diff::mann_whitney_p;
+use archx::profiler::{DiffStatus, DiffThresholds, HardwareFingerprint, Metric, ProfileDiff, ProfileExport};
+use common::TempDir;
+use std::process::Command;
+use std::time::Duration;
+
+fn run(ops: &[(&str, &[u64])]) -> ProfileExport {
+    let metrics = ops.iter()
+        .flat_map(|(name, micros)| micros.iter().map(move |&us| Metric::new(*name, "SIMD", "CPU", Duration::from_micros(us))))
+        .collect();
+    ProfileExport::from_metrics(metrics)
+}
+
+const STEADY: &[u64] = &[100, 102, 98, 101, 99, 100, 103, 97, 100, 101];
+const SLOWER: &[u64] = &[130, 128, 133, 131, 129, 130, 132, 127, 131, 129];
+const FASTER: &[u64] = &[50, 51, 49, 50, 52, 48, 50, 51, 49, 50];
+
+#[test]
+fn test_mann_whitney_separates_distributions() {
+    let steady: Vec<f64> = STEADY.iter().map(|&v| v as f64).collect();
+    let slower: Vec<f64> = SLOWER.iter().map(|&v| v as f64).collect();
+    assert!(mann_whitney_p(&steady, &slower) < 0.001);
+    assert!(mann_whitney_p(&steady, &steady) > 0.9);
+    assert_eq!(mann_whitney_p(&[1.0, 1.0], &[1.0, 1.0]), 1.0);
+}
+
+#[test]
+fn test_compare_classifies_operations() {
+    let baseline = run(&[("Add", STEADY), ("Dot", STEADY), ("Sum", STEADY), ("Graph", &[100]), ("Old", STEADY)]);
+    let candidate = run(&[("Add", SLOWER), ("Dot", FASTER), ("Sum", &[101, 99, 100, 102, 98, 100, 101, 99, 100, 100]), ("Graph", &[200]), ("New", STEADY)]);
+    let diff = ProfileDiff::compare(&baseline, &candidate, &DiffThresholds::default());
+
+    let status = |name: &str| diff.operations.iter().find(|op| op.name == name).unwrap().status;
+    assert_eq!(status("Add"), DiffStatus::Regressed);
+    assert_eq!(status("Dot"), DiffStatus::Improved);
+    assert_eq!(status("Sum"), DiffStatus::Unchanged);
+    assert_eq!(status("Graph"), DiffStatus::Inconclusive, "one call is too few to test");
+    assert_eq!(status("Old"), DiffStatus::Removed);
+    assert_eq!(status("New"), DiffStatus::Added);
+
+    let add = diff.regressions().next().unwrap();
+    assert_eq!(add.name, "Add");
+    assert!((add.delta_pct.unwrap() - 30.0).abs() < 1.0);
+    assert!(add.p_value.unwrap() < 0.05);
+    assert!(diff.has_regressions());
+    assert!(diff.hardware_warnings.is_empty(), "same machine: {:?}", diff.hardware_warnings);
+
+    let lenient = ProfileDiff::compare(&baseline, &candidate, &DiffThresholds { max_regression_pct: 50.0, ..Default::default() });
+    assert!(!lenient.has_regressions());
+
+    let table = diff.to_string();
+    assert!(table.contains("Add [CPU/SIMD]") && table.contains("Regressed") && table.contains("+29.9%"));
+}
+
+#[test]
+fn test_hardware_mismatch_is_reported() {
+    let baseline = run(&[("Add", STEADY)]);
+    let mut candidate = run(&[("Add", STEADY)]);
+    let fingerprint = candidate.fingerprint.as_mut().unwrap();
+    fingerprint.cpu_brand = "Other CPU".to_string();
+    fingerprint.logical_threads += 8;
+
+    let diff = ProfileDiff::compare(&baseline, &candidate, &DiffThresholds::default());
+    assert_eq!(diff.hardware_warnings.len(), 2);
+    assert!(diff.hardware_warnings[0].contains("Other CPU"));
+    assert!(diff.to_string().starts_with("warning: different hardware"));
+
+    let plain = ProfileExport::from_json(&serde_json::to_string(&baseline.metrics).unwrap()).unwrap();
+    assert!(plain.fingerprint.is_none());
+    assert_eq!(plain.metrics.len(), STEADY.len());
+    let diff = ProfileDiff::compare(&plain, &candidate, &DiffThresholds::default());
+    assert_eq!(diff.hardware_warnings, vec!["baseline has no hardware fingerprint"]);
+    assert!(HardwareFingerprint::current().differences(&HardwareFingerprint::current()).is_empty());
+}
+
+#[test]
+fn test_archx_diff_binary() {
+    let dir = TempDir::new("profile-diff");
+    let path = |name: &str| dir.join(name).to_str().unwrap().to_string();
+    run(&[("Add", STEADY)]).save(path("base.json")).unwrap();
+    run(&[("Add", SLOWER)]).save(path("slow.json")).unwrap();
+    run(&[("Add", STEADY)]).save(path("same.json")).unwrap();
+    let diff = |args: &[&str]| Command::new(env!("CARGO_BIN_EXE_archx-diff")).args(args).output().unwrap();
+
+    let output = diff(&[&path("base.json"), &path("slow.json")]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Regressed"));
+
+    assert_eq!(diff(&[&path("base.json"), &path("same.json")]).status.code(), Some(0));
+    assert_eq!(diff(&[&path("base.json"), &path("slow.json"), "--threshold", "40"]).status.code(), Some(0));
+
+    let output = diff(&[&path("base.json"), &path("slow.json"), "--json"]);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["operations"][0]["status"], "Regressed");
+
+    assert_eq!(diff(&[&path("base.json")]).status.code(), Some(2));
+    assert_eq!(diff(&[&path("base.json"), &path("missing.json")]).status.code(), Some(2));
+    assert_eq!(diff(&[&path("base.json"), &path("slow.json"), "--alpha", "x"]).status.code(), Some(2));
+}
@@ -0,0 +1,196 @@
+use archx::profiler::{get_profiler, summarize, Metric, ProfileScope, SpanContext, THREAD_BUFFER_CAPACITY};
+use rayon::prelude::*;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+// The profiler is global; tests that toggle it must not interleave.
+static LOCK: Mutex<()> = Mutex::new(());
+
+fn count(name: &str) -> usize {
+    get_profiler().get_snapshot().iter().filter(|m| m.name == name).count()
+}
+
+#[test]
+fn test_disabled_records_nothing() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let profiler = get_profiler();
+    profiler.set_enabled(false);
+
+    for _ in 0..100 {
+        let _scope = ProfileScope::new("Disabled Scope", "CPU", "Test");
+    }
+    profiler.record(Metric::new("Disabled Event", "Test", "CPU", Duration::from_millis(1)));
+    assert_eq!(count("Disabled Scope"), 0);
+    assert_eq!(count("Disabled Event"), 0);
+}
+
+#[test]
+fn test_per_thread_buffers_are_merged() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let profiler = get_profiler();
+    profiler.set_enabled(true);
+
+    std::thread::scope(|s| {
+        for _ in 0..4 {
+            s.spawn(|| {
+                for _ in 0..250 {
+                    let _scope = ProfileScope::new("Merged Scope", "CPU", "Test");
+                }
+            });
+        }
+    });
+    {
+        let _scope = ProfileScope::new("Merged Scope", "iGPU", "Test");
+        std::thread::sleep(Duration::from_millis(2));
+    }
+    profiler.record(Metric::new("Merged Event", "Test", "CPU", Duration::from_millis(3)));
+
+    let snapshot = profiler.get_snapshot();
+    profiler.set_enabled(false);
+
+    let scopes: Vec<&Metric> = snapshot.iter().filter(|m| m.name == "Merged Scope").collect();
+    assert_eq!(scopes.len(), 1001);
+    let threads: std::collections::HashSet<_> = scopes.iter().map(|m| m.thread_id).collect();
+    assert!(threads.len() >= 2, "spans come from several threads");
+    assert!(snapshot.windows(2).all(|w| w[0].start <= w[1].start), "snapshot is ordered by start");
+
+    let gpu = scopes.iter().find(|m| m.device == "iGPU").unwrap();
+    assert!(gpu.duration >= Duration::from_millis(2));
+    assert!(gpu.energy_estimate > 0.0);
+
+    let event = snapshot.iter().find(|m| m.name == "Merged Event").unwrap();
+    assert_eq!(event.duration, Duration::from_millis(3));
+    assert!(event.thread_id.is_some());
+}
+
+#[test]
+fn test_ring_buffer_overwrites_oldest() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let profiler = get_profiler();
+    profiler.clear();
+    profiler.set_enabled(true);
+
+    std::thread::spawn(|| {
+        for _ in 0..THREAD_BUFFER_CAPACITY + 10 {
+            let _scope = ProfileScope::new("Ring Scope", "CPU", "Test");
+        }
+    }).join().unwrap();
+    profiler.set_enabled(false);
+
+    assert_eq!(count("Ring Scope"), THREAD_BUFFER_CAPACITY);
+    assert!(profiler.dropped() >= 10);
+
+    profiler.clear();
+    assert_eq!(count("Ring Scope"), 0);
+    assert_eq!(profiler.dropped(), 0);
+}
+
+#[test]
+fn test_nested_scopes_form_a_tree() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let profiler = get_profiler();
+    profiler.clear();
+    profiler.set_enabled(true);
+
+    {
+        let _outer = ProfileScope::new("Tree Outer", "CPU", "Test");
+        let context = SpanContext::current();
+        {
+            let _inner = ProfileScope::new("Tree Inner", "CPU", "Test");
+            std::thread::sleep(Duration::from_millis(2));
+        }
+        std::thread::scope(|s| {
+            s.spawn(|| {
+                let _entered = context.enter();
+                let _worker = ProfileScope::new("Tree Worker", "CPU", "Test");
+            });
+        });
+        (0..8).into_par_iter().for_each(|_| {
+            let _entered = context.enter();
+            let _task = ProfileScope::new("Tree Rayon", "CPU", "Test");
+        });
+        profiler.record(Metric::new("Tree Event", "Test", "CPU", Duration::from_millis(1)));
+    }
+    let _detached = ProfileScope::new("Tree Detached", "CPU", "Test");
+    drop(_detached);
+
+    let snapshot = profiler.get_snapshot();
+    profiler.set_enabled(false);
+
+    let find = |name: &str| snapshot.iter().find(|m| m.name == name).unwrap();
+    let outer = find("Tree Outer");
+    assert_eq!(outer.parent_id, None);
+    assert_ne!(outer.id, 0);
+    for child in ["Tree Inner", "Tree Worker", "Tree Rayon", "Tree Event"] {
+        assert_eq!(find(child).parent_id, Some(outer.id), "{} is a child of the outer scope", child);
+    }
+    assert_eq!(find("Tree Detached").parent_id, None, "the parent is restored when a scope ends");
+    assert!(find("Tree Worker").thread_id != outer.thread_id);
+
+    let ids: std::collections::HashSet<u64> = snapshot.iter().map(|m| m.id).collect();
+    assert_eq!(ids.len(), snapshot.len(), "span ids are unique");
+
+    #[cfg(target_os = "linux")]
+    {
+        let tid = outer.thread_id.unwrap();
+        assert!(std::path::Path::new(&format!("/proc/self/task/{}", tid)).exists(), "thread ids are OS thread ids");
+    }
+
+    let rows = summarize(&snapshot);
+    let row = |name: &str| rows.iter().find(|r| r.name == name).unwrap();
+    assert_eq!(row("Tree Outer").depth, 0);
+    assert_eq!(row("Tree Rayon").depth, 1);
+    assert_eq!(row("Tree Rayon").calls, 8);
+    assert!(row("Tree Outer").self_time < row("Tree Outer").total);
+    profiler.clear();
+}
+
+#[test]
+fn test_summary_separates_self_and_total_time() {
+    let span = |name: &str, id: u64, parent: Option<u64>, ms: u64| Metric {
+        id,
+        parent_id: parent,
+        ..Metric::new(name, "Test", "CPU", Duration::from_millis(ms))
+    };
+    let metrics = vec![
+        span("Parallel Add", 1, None, 10),
+        span("Parallel Chunk", 2, Some(1), 3),
+        span("Parallel Chunk", 3, Some(1), 4),
+        span("Kernel", 4, Some(3), 1),
+        span("Orphan", 5, Some(99), 2),
+    ];
+
+    let rows = summarize(&metrics);
+    let shape: Vec<(usize, &str, usize)> = rows.iter().map(|r| (r.depth, r.name.as_str(), r.calls)).collect();
+    assert_eq!(shape, vec![(0, "Parallel Add", 1), (1, "Parallel Chunk", 2), (2, "Kernel", 1), (0, "Orphan", 1)]);
+    assert_eq!(rows[0].total, Duration::from_millis(10));
+    assert_eq!(rows[0].self_time, Duration::from_millis(3));
+    assert_eq!(rows[1].total, Duration::from_millis(7));
+    assert_eq!(rows[1].self_time, Duration::from_millis(6));
+}
+
+#[test]
+#[ignore]
+fn benchmark_scope_overhead() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let profiler = get_profiler();
+    let iterations = 1_000_000;
+
+    // Every enabled scope reads the clock twice; on VMs that alone can take ~80ns.
+    let start = Instant::now();
+    for _ in 0..iterations {
+        std::hint::black_box((Instant::now(), Instant::now()));
+    }
+    println!("clock baseline: {:?} per pair of reads", start.elapsed() / iterations);
+
+    for enabled in [false, true] {
+        profiler.set_enabled(enabled);
+        let start = Instant::now();
+        for _ in 0..iterations {
+            let _scope = ProfileScope::new("Bench Scope", "CPU", "Test");
+        }
+        println!("enabled={}: {:?} per scope", enabled, start.elapsed() / iterations);
+    }
+    profiler.set_enabled(false);
+    profiler.clear();
+}
@@ -0,0 +1,110 @@
+mod common;
+
+use archx::profiler::{get_profiler, operation_stats, LatencyHistogram, Metric, ProfileScope};
+use archx::{JsonExporter, ReportExporter};
+use common::FakeSysfs;
+use std::time::Duration;
+
+fn within_one_percent(actual: Duration, expected: Duration) -> bool {
+    let (a, e) = (actual.as_nanos() as f64, expected.as_nanos() as f64);
+    (a - e).abs() <= e * 0.01
+}
+
+#[test]
+fn test_histogram_percentiles_are_accurate() {
+    let mut histogram = LatencyHistogram::new();
+    for micros in 1..=10_000 {
+        histogram.record(Duration::from_micros(micros));
+    }
+
+    assert_eq!(histogram.count(), 10_000);
+    assert_eq!(histogram.min(), Duration::from_micros(1));
+    assert_eq!(histogram.max(), Duration::from_micros(10_000));
+    assert_eq!(histogram.mean(), Duration::from_nanos(5_000_500));
+    for (percentile, expected) in [(50.0, 5_000), (90.0, 9_000), (99.0, 9_900), (99.9, 9_990)] {
+        let actual = histogram.percentile(percentile);
+        assert!(within_one_percent(actual, Duration::from_micros(expected)), "p{} = {:?}", percentile, actual);
+    }
+    assert_eq!(histogram.percentile(100.0), Duration::from_micros(10_000));
+}
+
+#[test]
+fn test_histogram_small_values_are_exact_and_merge() {
+    let mut fast = LatencyHistogram::new();
+    for nanos in [5, 17, 17, 90] {
+        fast.record(Duration::from_nanos(nanos));
+    }
+    assert_eq!(fast.percentile(50.0), Duration::from_nanos(17));
+
+    let mut slow = LatencyHistogram::new();
+    slow.record(Duration::from_secs(3));
+    slow.record(Duration::MAX);
+    fast.merge(&slow);
+
+    assert_eq!(fast.count(), 6);
+    assert_eq!(fast.min(), Duration::from_nanos(5));
+    assert_eq!(fast.max(), Duration::from_nanos(u64::MAX));
+    assert!(within_one_percent(fast.percentile(80.0), Duration::from_secs(3)));
+    assert_eq!(LatencyHistogram::new().percentile(50.0), Duration::ZERO);
+}
+
+fn metric(name: &str, device: &str, ms: u64, elements: Option<u64>) -> Metric {
+    let mut metric = Metric::new(name, "SIMD", device, Duration::from_millis(ms));
+    if let Some(elements) = elements {
+        metric.metadata.insert("elements".into(), elements.to_string());
+        metric.metadata.insert("bytes".into(), (elements * 12).to_string());
+    }
+    metric
+}
+
+#[test]
+fn test_stats_group_by_operation_and_compute_throughput() {
+    let metrics = vec![
+        metric("Add", "CPU", 10, Some(1_000_000)),
+        metric("Add", "iGPU", 4, None),
+        metric("Add", "CPU", 30, Some(3_000_000)),
+        metric("Dot", "CPU", 1, None),
+    ];
+    let stats = operation_stats(&metrics);
+
+    let keys: Vec<(&str, &str, u64)> = stats.iter().map(|s| (s.name.as_str(), s.device.as_str(), s.count)).collect();
+    assert_eq!(keys, vec![("Add", "CPU", 2), ("Add", "iGPU", 1), ("Dot", "CPU", 1)]);
+
+    let add = &stats[0];
+    assert_eq!(add.total, Duration::from_millis(40));
+    assert_eq!(add.min, Duration::from_millis(10));
+    assert_eq!(add.mean, Duration::from_millis(20));
+    assert!(within_one_percent(add.p99, Duration::from_millis(30)));
+    assert_eq!(add.elements_per_sec, Some(1e8));
+    assert!((add.gb_per_sec.unwrap() - 1.2).abs() < 1e-9);
+    assert_eq!(stats[2].elements_per_sec, None);
+}
+
+#[test]
+fn test_scope_sizes_reach_the_stats_and_export_as_json() {
+    let profiler = get_profiler();
+    profiler.clear();
+    profiler.set_enabled(true);
+    for _ in 0..10 {
+        let _scope = ProfileScope::new("Stats Copy", "CPU", "Test").with_size(1 << 20, 8 << 20);
+        std::thread::sleep(Duration::from_micros(200));
+    }
+    let stats = profiler.stats();
+    profiler.set_enabled(false);
+    profiler.clear();
+
+    let copy = stats.iter().find(|s| s.name == "Stats Copy").unwrap();
+    assert_eq!(copy.count, 10);
+    assert!(copy.p50 >= Duration::from_micros(200));
+    assert!(copy.elements_per_sec.unwrap() > 0.0);
+    assert!(copy.gb_per_sec.unwrap() > 0.0);
+
+    let dir = FakeSysfs::new("profiler-stats");
+    let path = dir.root().join("stats.json");
+    JsonExporter.export(&stats, path.to_str().unwrap()).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    assert_eq!(json[0]["name"], "Stats Copy");
+    assert_eq!(json[0]["count"], 10);
+    assert!(json[0]["p999"].is_object());
+    assert!(json[0].get("histogram").is_none());
+}
@@ -0,0 +1,186 @@
+mod common;
+
+use archx::detect::rapl::read_rapl_zones;
+use archx::detect::RaplDomain;
+use archx::profiler::energy::{energy_sampler, start_energy_sampling_from};
+use archx::profiler::{get_profiler, stop_energy_sampling, EnergySampler, EnergySource, Metric, ProfileScope};
+use common::FakeSysfs;
+use std::sync::Mutex;
+use std::time::Duration;
+
+static LOCK: Mutex<()> = Mutex::new(());
+
+const PACKAGE: &str = "class/powercap/intel-rapl:0";
+const CORE: &str = "class/powercap/intel-rapl:0:0";
+const DRAM: &str = "class/powercap/intel-rapl:0:2";
+
+fn rapl_sysfs() -> FakeSysfs {
+    let sysfs = FakeSysfs::new("rapl");
+    sysfs.write("class/powercap/intel-rapl/enabled", "1")
+        .write(&format!("{}/name", PACKAGE), "package-0")
+        .write(&format!("{}/energy_uj", PACKAGE), "1000000")
+        .write(&format!("{}/max_energy_range_uj", PACKAGE), "262143328850")
+        .write(&format!("{}/name", CORE), "core")
+        .write(&format!("{}/energy_uj", CORE), "500000")
+        .write(&format!("{}/max_energy_range_uj", CORE), "1000000")
+        .write(&format!("{}/name", DRAM), "dram")
+        .write(&format!("{}/energy_uj", DRAM), "0")
+        .write(&format!("{}/max_energy_range_uj", DRAM), "262143328850")
+        // A zone without a readable counter is ignored.
+        .write("class/powercap/intel-rapl:1/name", "package-1");
+    sysfs
+}
+
+fn set(sysfs: &FakeSysfs, zone: &str, uj: u64) {
+    sysfs.write(&format!("{}/energy_uj", zone), &uj.to_string());
+}
+
+#[test]
+fn test_zones_are_discovered_by_domain() {
+    let sysfs = rapl_sysfs();
+    let zones = read_rapl_zones(sysfs.root());
+
+    let found: Vec<(&str, &str, RaplDomain)> = zones.iter().map(|z| (z.zone.as_str(), z.name.as_str(), z.domain)).collect();
+    assert_eq!(found, vec![
+        ("intel-rapl:0", "package-0", RaplDomain::Package),
+        ("intel-rapl:0:0", "core", RaplDomain::Core),
+        ("intel-rapl:0:2", "dram", RaplDomain::Dram),
+    ]);
+    assert_eq!(zones[1].max_energy_range_uj, 1_000_000);
+    assert!(read_rapl_zones(FakeSysfs::new("no-rapl").root()).is_empty());
+}
+
+#[test]
+fn test_sampler_accumulates_across_wraparound() {
+    let sysfs = rapl_sysfs();
+    let sampler = EnergySampler::discover(sysfs.root()).unwrap();
+    sampler.sample();
+
+    set(&sysfs, PACKAGE, 3_000_000);
+    set(&sysfs, CORE, 900_000);
+    set(&sysfs, DRAM, 250_000);
+    sampler.sample();
+
+    // The core counter wraps at 1 J: 900000 -> 1000000 -> 0 -> 200000.
+    set(&sysfs, CORE, 200_000);
+    sampler.sample();
+
+    let totals = sampler.totals();
+    assert!((totals.package_j - 2.0).abs() < 1e-9);
+    assert!((totals.core_j - 0.7).abs() < 1e-9);
+    assert!((totals.dram_j - 0.25).abs() < 1e-9);
+}
+
+#[test]
+fn test_counter_reset_without_range_rebaselines() {
+    let sysfs = FakeSysfs::new("rapl-no-range");
+    sysfs.write("class/powercap/intel-rapl:0/name", "package-0")
+        .write("class/powercap/intel-rapl:0/energy_uj", "5000000");
+    let sampler = EnergySampler::discover(sysfs.root()).unwrap();
+    assert_eq!(sampler.zones()[0].max_energy_range_uj, u64::MAX);
+    sampler.sample();
+
+    set(&sysfs, "class/powercap/intel-rapl:0", 6_000_000);
+    sampler.sample();
+    // The counter restarts from zero, e.g. after suspend; only the growth after it counts.
+    set(&sysfs, "class/powercap/intel-rapl:0", 100_000);
+    sampler.sample();
+    set(&sysfs, "class/powercap/intel-rapl:0", 600_000);
+    sampler.sample();
+
+    assert!((sampler.totals().package_j - 1.5).abs() < 1e-9, "{:?}", sampler.totals());
+}
+
+#[test]
+fn test_energy_is_interpolated_between_samples() {
+    let sysfs = rapl_sysfs();
+    let sampler = EnergySampler::discover(sysfs.root()).unwrap();
+    let start = get_profiler().now();
+    sampler.sample();
+    std::thread::sleep(Duration::from_millis(20));
+    set(&sysfs, PACKAGE, 5_000_000);
+    sampler.sample();
+    let end = get_profiler().now();
+
+    let covered = sampler.energy_between(start + Duration::from_millis(5), start + Duration::from_millis(10)).unwrap();
+    assert!(covered.package_j > 0.0 && covered.package_j < 4.0, "{:?}", covered);
+    assert!(sampler.energy_between(end + Duration::from_secs(1), end + Duration::from_secs(2)).is_none());
+}
+
+#[test]
+fn test_profiler_attributes_measured_energy() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let sysfs = rapl_sysfs();
+    let profiler = get_profiler();
+    profiler.clear();
+    profiler.set_enabled(true);
+
+    assert!(start_energy_sampling_from(sysfs.root(), Duration::from_secs(3600)));
+    assert!(energy_sampler().is_some());
+    {
+        let _scope = ProfileScope::new("Energy Work", "CPU", "Test");
+        std::thread::sleep(Duration::from_millis(20));
+    }
+    profiler.record(Metric::new("Energy GPU", "Test", "dGPU", Duration::from_millis(1)));
+    set(&sysfs, PACKAGE, 3_000_000);
+
+    let snapshot = profiler.get_snapshot();
+    let work = snapshot.iter().find(|m| m.name == "Energy Work").unwrap();
+    assert_eq!(work.energy_source, EnergySource::Rapl);
+    assert!(work.energy_estimate > 0.0 && work.energy_estimate <= 2.0, "{}", work.energy_estimate);
+    assert!(work.metadata.contains_key("energy_core_j"));
+    let gpu = snapshot.iter().find(|m| m.name == "Energy GPU").unwrap();
+    assert_eq!(gpu.energy_source, EnergySource::Estimate);
+
+    stop_energy_sampling();
+    let snapshot = profiler.get_snapshot();
+    profiler.set_enabled(false);
+    profiler.clear();
+
+    let work = snapshot.iter().find(|m| m.name == "Energy Work").unwrap();
+    assert_eq!(work.energy_source, EnergySource::Estimate);
+    assert!(work.energy_estimate > 0.0);
+    assert!(!start_energy_sampling_from(FakeSysfs::new("no-rapl").root(), Duration::from_millis(10)));
+}
+
+#[test]
+fn test_concurrent_spans_share_package_energy() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let sysfs = rapl_sysfs();
+    let profiler = get_profiler();
+    profiler.clear();
+    profiler.set_enabled(true);
+
+    let t0 = profiler.now();
+    assert!(start_energy_sampling_from(sysfs.root(), Duration::from_secs(3600)));
+    std::thread::sleep(Duration::from_millis(50));
+    let span = move |name: &str, id: u64, parent: Option<u64>, from_ms: u64, ms: u64| Metric {
+        start: t0 + Duration::from_millis(from_ms),
+        id,
+        parent_id: parent,
+        ..Metric::new(name, "Test", "CPU", Duration::from_millis(ms))
+    };
+    // Two top-level spans on different threads overlap for 10 ms; one has a child.
+    profiler.record(span("Left", 9001, None, 10, 20));
+    profiler.record(span("Inner", 9003, Some(9001), 12, 5));
+    std::thread::spawn(move || get_profiler().record(span("Right", 9002, None, 20, 20))).join().unwrap();
+    set(&sysfs, PACKAGE, 4_000_000);
+
+    let snapshot = profiler.get_snapshot();
+    let sampler = energy_sampler().unwrap();
+    stop_energy_sampling();
+    profiler.set_enabled(false);
+    profiler.clear();
+
+    let find = |name: &str| snapshot.iter().find(|m| m.name == name).unwrap();
+    let (left, right, inner) = (find("Left"), find("Right"), find("Inner"));
+    assert_eq!((left.energy_source, right.energy_source), (EnergySource::Rapl, EnergySource::Rapl));
+    assert_eq!(inner.energy_source, EnergySource::Estimate);
+
+    // Together they claim the package energy of their union, not more.
+    let union = sampler.energy_between(left.start, right.start + right.duration).unwrap().package_j as f32;
+    let claimed = left.energy_estimate + right.energy_estimate;
+    assert!(union > 0.0 && (claimed - union).abs() < union * 1e-3, "{} vs {}", claimed, union);
+    // The counter grows linearly between the samples, so equal lengths get equal shares.
+    assert!((left.energy_estimate - right.energy_estimate).abs() < union * 1e-3);
+}
@@ -0,0 +1,84 @@
+use archx::profiler::{get_profiler, start_system_sampling, stop_system_sampling, system_sampler, ProfileExport, Sampler, SystemSample};
+use archx::detect::HardwareCache;
+use archx::runtime::Governor;
+use std::time::Duration;
+
+fn load_at(ms: u64, cpu_load: f32) -> SystemSample {
+    SystemSample { at: Duration::from_millis(ms), cpu_load, ..Default::default() }
+}
+
+#[test]
+fn test_ring_buffer_keeps_latest_samples() {
+    let sampler = Sampler::new(3);
+    assert!(sampler.is_empty() && sampler.latest().is_none());
+    for i in 0..5 {
+        sampler.record(load_at(i * 100, i as f32));
+    }
+
+    assert_eq!(sampler.len(), 3);
+    let loads: Vec<f32> = sampler.history().iter().map(|s| s.cpu_load).collect();
+    assert_eq!(loads, vec![2.0, 3.0, 4.0]);
+    assert_eq!(sampler.current_load(), 4.0);
+    sampler.clear();
+    assert!(sampler.is_empty());
+}
+
+#[test]
+fn test_window_average_and_trend() {
+    let sampler = Sampler::new(100);
+    assert_eq!(sampler.average_load(Duration::from_secs(1)), None);
+    // Load rises 10 points per second for 5 seconds.
+    for i in 0..=50 {
+        sampler.record(load_at(i * 100, i as f32));
+    }
+
+    assert_eq!(sampler.window(Duration::from_secs(1)).len(), 11);
+    assert_eq!(sampler.average_load(Duration::from_secs(1)), Some(45.0));
+    assert_eq!(sampler.average_load(Duration::from_secs(60)), Some(25.0));
+    let trend = sampler.load_trend(Duration::from_secs(2)).unwrap();
+    assert!((trend - 10.0).abs() < 1e-3, "{}", trend);
+
+    let flat = Sampler::new(10);
+    flat.record(load_at(0, 50.0));
+    assert_eq!(flat.load_trend(Duration::from_secs(1)), None);
+    flat.record(load_at(500, 50.0));
+    assert_eq!(flat.load_trend(Duration::from_secs(1)), Some(0.0));
+}
+
+#[test]
+fn test_background_sampling_feeds_exports_and_governor() {
+    let sampler = start_system_sampling(Duration::from_millis(10), 1_000);
+    // Recording rides on the hardware cache's sampler instead of a thread of its own.
+    assert!(HardwareCache::global().is_sampling());
+    std::thread::sleep(Duration::from_millis(150));
+    assert!(sampler.len() >= 3, "only {} samples", sampler.len());
+    let history = sampler.history();
+    assert!(history.windows(2).all(|w| w[0].at <= w[1].at));
+    assert!(history[0].memory_total_kb > 0);
+    assert!(history.iter().all(|s| s.memory_used_kb <= s.memory_total_kb));
+
+    let trace: serde_json::Value = serde_json::from_str(&get_profiler().to_chrome_trace()).unwrap();
+    let counters = trace["traceEvents"].as_array().unwrap().iter().filter(|e| e["ph"] == "C").count();
+    assert!(counters >= 3 * 3, "{} counter events", counters);
+    assert!(ProfileExport::capture().system.len() >= 3);
+
+    stop_system_sampling();
+    assert!(system_sampler().is_none());
+    assert!(!HardwareCache::global().is_sampling());
+    let len = sampler.len();
+    std::thread::sleep(Duration::from_millis(50));
+    assert_eq!(sampler.len(), len, "sampling continued after stop");
+
+    // The governor throttles on the load averaged by the running sampler.
+    let sampler = start_system_sampling(Duration::from_secs(3600), 1_000);
+    let now = get_profiler().now();
+    for _ in 0..99 {
+        sampler.record(SystemSample { at: now, cpu_load: 100.0, ..Default::default() });
+    }
+    assert!(Governor::state().high_load);
+    for _ in 0..999 {
+        sampler.record(SystemSample { at: now + Duration::from_secs(5), cpu_load: 0.0, ..Default::default() });
+    }
+    assert!(!Governor::state().high_load);
+    stop_system_sampling();
+}
@@ -0,0 +1,139 @@
+use archx::{ArchXError, TaskGraph, TaskSpec};
+use std::time::Duration;
+
+#[test]
+fn test_pipeline_normalize_dot_reduce() {
+    let mut graph = TaskGraph::new();
+    let raw = graph.buffer(vec![2.0; 4096]);
+    let norm = graph.buffer(vec![0.0; 4096]);
+    let weights: Vec<_> = (1..=3).map(|w| graph.buffer(vec![w as f32; 4096])).collect();
+    let partials: Vec<_> = (0..3).map(|_| graph.buffer(vec![0.0])).collect();
+    let total = graph.buffer(vec![0.0]);
+
+    let normalize = graph.task("normalize", TaskSpec::new().reads(&[raw]).writes(&[norm]), move |ctx| {
+        let src = ctx.read(raw)?;
+        let mut dst = ctx.write(norm)?;
+        for (d, s) in dst.iter_mut().zip(src.iter()) {
+            *d = s / 2.0;
+        }
+        Ok(())
+    });
+
+    // Each dot reads `norm`, so it is ordered after `normalize` automatically.
+    let dots: Vec<_> = (0..3)
+        .map(|i| graph.dot(&format!("dot{}", i), norm, weights[i], partials[i]))
+        .collect();
+
+    let reduce_reads = partials.clone();
+    graph.task("reduce", TaskSpec::new().reads(&partials).writes(&[total]), move |ctx| {
+        let mut sum = 0.0;
+        for p in &reduce_reads {
+            sum += ctx.read(*p)?[0];
+        }
+        ctx.write(total)?[0] = sum;
+        Ok(())
+    });
+
+    let out = graph.run().unwrap();
+    assert_eq!(out.buffer(total)[0], 4096.0 * (1.0 + 2.0 + 3.0));
+
+    let report = &out.report;
+    assert_eq!(report.nodes.len(), 5);
+    assert_eq!(report.critical_path.first(), Some(&normalize));
+    assert_eq!(report.critical_path_names().first(), Some(&"normalize"));
+    assert_eq!(report.critical_path_names().last(), Some(&"reduce"));
+    assert_eq!(report.critical_path.len(), 3);
+    assert!(report.critical_path_time <= report.wall_time);
+    assert_eq!(dots.len(), 3);
+    assert_ne!(normalize, dots[0]);
+}
+
+#[test]
+fn test_independent_nodes_run_concurrently() {
+    let cores = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+    let mut graph = TaskGraph::new();
+    for i in 0..2 {
+        graph.task(&format!("sleep{}", i), TaskSpec::new(), |_| {
+            std::thread::sleep(Duration::from_millis(150));
+            Ok(())
+        });
+    }
+
+    let out = graph.run().unwrap();
+    if cores >= 2 {
+        assert_eq!(out.report.max_concurrency, 2);
+        // Each node started before the other one finished.
+        let (a, b) = (&out.report.nodes[0], &out.report.nodes[1]);
+        assert!(a.start < b.start + b.duration && b.start < a.start + a.duration, "{:?}", out.report.nodes);
+    }
+    assert_eq!(out.report.critical_path.len(), 1);
+}
+
+#[test]
+fn test_critical_flag_follows_nodes_not_names() {
+    let profiler = archx::get_profiler();
+    profiler.set_enabled(true);
+    let mut graph = TaskGraph::new();
+    let buf = graph.buffer(vec![0.0]);
+    // Two nodes share a name; only the slow one, which `tail` waits for, is critical.
+    let slow = graph.task("dup-stage", TaskSpec::new().writes(&[buf]), |_| {
+        std::thread::sleep(Duration::from_millis(30));
+        Ok(())
+    });
+    let fast = graph.task("dup-stage", TaskSpec::new(), |_| Ok(()));
+    let tail = graph.task("dup-tail", TaskSpec::new().reads(&[buf]), |_| Ok(()));
+
+    let out = graph.run().unwrap();
+    assert_eq!(out.report.critical_path, vec![slow, tail]);
+    assert!(!out.report.critical_path.contains(&fast));
+
+    let critical: Vec<String> = profiler.get_snapshot().into_iter()
+        .filter(|m| m.name == "dup-stage")
+        .filter_map(|m| m.metadata.get("critical").cloned())
+        .collect();
+    profiler.set_enabled(false);
+    let mut critical: Vec<&str> = critical.iter().map(String::as_str).collect();
+    critical.sort();
+    assert_eq!(critical, vec!["false", "true"]);
+}
+
+#[test]
+fn test_failure_and_validation() {
+    let mut graph = TaskGraph::new();
+    let first = graph.task("fails", TaskSpec::new(), |_| Err(ArchXError::ExecutionError("bad stage".into())));
+    graph.task("never", TaskSpec::new().after(&[first]), |_| panic!("must not run"));
+    assert!(matches!(graph.run(), Err(ArchXError::ExecutionError(_))));
+
+    let mut graph = TaskGraph::new();
+    let buf = graph.buffer(vec![1.0; 8]);
+    graph.task("sneaky", TaskSpec::new().reads(&[buf]), move |ctx| {
+        ctx.write(buf)?[0] = 2.0;
+        Ok(())
+    });
+    assert!(matches!(graph.run(), Err(ArchXError::InvalidInput(_))));
+
+    let mut graph = TaskGraph::new();
+    let a = graph.buffer(vec![1.0; 8]);
+    let b = graph.buffer(vec![1.0; 4]);
+    let out = graph.buffer(vec![0.0; 8]);
+    graph.add("mismatch", a, b, out);
+    assert!(matches!(graph.run(), Err(ArchXError::InvalidInput(_))));
+}
+
+#[test]
+fn test_panicking_node_fails_the_run() {
+    let (tx, rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        let engine = archx::core::CoreEngine::new();
+        let mut graph = TaskGraph::new();
+        let boom = graph.task("boom", TaskSpec::new(), |_| panic!("node bug"));
+        graph.task("after", TaskSpec::new().after(&[boom]), |_| Ok(()));
+        graph.task("sibling", TaskSpec::new(), |_| Ok(()));
+        let result = graph.run_on(&engine);
+        let _ = tx.send((result, engine.resources().get_current_usage().active_threads));
+    });
+
+    let (result, active_threads) = rx.recv_timeout(Duration::from_secs(10)).expect("graph run hung after a panic");
+    assert!(matches!(result, Err(ArchXError::ExecutionError(ref m)) if m.contains("boom")), "{:?}", result.err());
+    assert_eq!(active_threads, 0, "thread reservations leaked");
+}